@@ -0,0 +1,197 @@
+use crate::{CommitCertificate, Error, Event, Height, Message, Moderator, Notification};
+
+use async_trait::async_trait;
+use fuel_types::Bytes32;
+use time::OffsetDateTime;
+
+use alloc::boxed::Box;
+use core::time::Duration;
+
+/// Application hook to validate the blocks proposed to the reactor.
+#[async_trait]
+pub trait BlockValidator: Send + Sync {
+    /// Validate a proposed block for the given height.
+    ///
+    /// A failure will cause the node to prevote nil for the round.
+    async fn validate_block(&mut self, height: Height, block_id: &Bytes32) -> Result<(), Error>;
+}
+
+/// Block lifecycle of the application that is replicated by the consensus.
+///
+/// The callbacks are driven by the reactor when its moderator is wrapped into an
+/// [`ApplicationModerator`].
+#[async_trait]
+pub trait Application: Send + Sync {
+    /// Produce a block to be proposed by the node for the given height.
+    async fn prepare_proposal(&mut self, height: Height) -> Result<Bytes32, Error>;
+
+    /// Validate a block proposed by another validator.
+    ///
+    /// A failure will cause the node to prevote nil for the round.
+    async fn process_proposal(&mut self, height: Height, block_id: &Bytes32) -> Result<(), Error>;
+
+    /// Execute a block that was committed by the network.
+    ///
+    /// The certificate is `None` if the node committed without collecting 2/3+ of the
+    /// precommits, as the leader of the block. Forced commits carry no block, so they are only
+    /// committed.
+    async fn finalize_block(
+        &mut self,
+        height: Height,
+        block_id: &Bytes32,
+        certificate: Option<&CommitCertificate>,
+    ) -> Result<(), Error>;
+
+    /// Persist the state of the application after the block of the given height was finalized.
+    async fn commit(&mut self, height: Height) -> Result<(), Error>;
+}
+
+/// Adapter of an [`Application`] into the message flow of a [`Moderator`].
+///
+/// The block requests of the reactor are resolved with [`Application::prepare_proposal`], the
+/// proposed blocks are validated with [`Application::process_proposal`], and the commits are
+/// finalized with [`Application::finalize_block`] and [`Application::commit`]. All the messages
+/// are still dispatched to the underlying moderator, even if the application fails to process
+/// them; the failure is returned afterwards.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ApplicationModerator<M, A> {
+    moderator: M,
+    application: A,
+}
+
+impl<M, A> ApplicationModerator<M, A> {
+    /// Wrap a moderator with an application
+    pub const fn new(moderator: M, application: A) -> Self {
+        Self {
+            moderator,
+            application,
+        }
+    }
+
+    /// Underlying moderator
+    pub const fn moderator(&self) -> &M {
+        &self.moderator
+    }
+
+    /// Underlying moderator
+    pub fn moderator_mut(&mut self) -> &mut M {
+        &mut self.moderator
+    }
+
+    /// Replicated application
+    pub const fn application(&self) -> &A {
+        &self.application
+    }
+
+    /// Replicated application
+    pub fn application_mut(&mut self) -> &mut A {
+        &mut self.application
+    }
+
+    /// Split the adapter into its moderator and application
+    pub fn into_inner(self) -> (M, A) {
+        (self.moderator, self.application)
+    }
+}
+
+impl<M, A> ApplicationModerator<M, A>
+where
+    A: Application,
+{
+    /// Process a message with the application, returning the message to be rebounded to the
+    /// reactor, if any.
+    async fn apply(&mut self, message: &Message) -> Result<Option<Message>, Error> {
+        match message {
+            Message::Event(Event::AwaitingBlock { height }) => {
+                let height = *height;
+                let block_id = self.application.prepare_proposal(height).await?;
+
+                // The authorization is consumed by the reactor with the inbound queue
+                let authorized = Message::Notification(Notification::BlockProposeAuthorized {
+                    height,
+                    block_id,
+                });
+
+                Ok(Some(authorized))
+            }
+
+            Message::Event(Event::Commit {
+                height,
+                block_id,
+                certificate,
+                ..
+            }) => {
+                self.application
+                    .finalize_block(*height, block_id, certificate.as_ref())
+                    .await?;
+
+                self.application.commit(*height).await?;
+
+                Ok(None)
+            }
+
+            // A forced commit carries no block to be executed
+            Message::Event(Event::ForcedCommit { height, .. }) => {
+                self.application.commit(*height).await?;
+
+                Ok(None)
+            }
+
+            _ => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl<M, A> BlockValidator for ApplicationModerator<M, A>
+where
+    M: Moderator,
+    A: Application,
+{
+    async fn validate_block(&mut self, height: Height, block_id: &Bytes32) -> Result<(), Error> {
+        self.application.process_proposal(height, block_id).await
+    }
+}
+
+#[async_trait]
+impl<M, A> Moderator for ApplicationModerator<M, A>
+where
+    M: Moderator,
+    M::Error: From<Error>,
+    A: Application,
+{
+    type Error = M::Error;
+
+    fn now(&self) -> OffsetDateTime {
+        self.moderator.now()
+    }
+
+    fn block_validator(&mut self) -> Option<&mut dyn BlockValidator> {
+        Some(self)
+    }
+
+    async fn inbound(&mut self) -> Result<Option<Message>, Self::Error> {
+        self.moderator.inbound().await
+    }
+
+    fn inbound_blocking(&mut self) -> Result<Option<Message>, Self::Error> {
+        self.moderator.inbound_blocking()
+    }
+
+    async fn outbound(&mut self, message: Message, timeout: Duration) -> Result<(), Self::Error> {
+        let applied = self.apply(&message).await;
+
+        // The message is dispatched even if the application failed, so the commit is not lost
+        self.moderator.outbound(message, timeout).await?;
+
+        if let Some(message) = applied? {
+            self.moderator.rebound(message, timeout).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn rebound(&mut self, message: Message, timeout: Duration) -> Result<(), Self::Error> {
+        self.moderator.rebound(message, timeout).await
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{
+    ChainId, Consensus, Decode, DecodeError, Decoder, Encode, Error, Height, Keychain, Round, Step,
+    ValidatorSet, ValidatorStakes, Vote,
+};
+
+use fuel_types::Bytes32;
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+/// Proof that a block was finalized by 2/3+ of the voting power of a height.
+///
+/// The certificate is composed by the signed precommit and commit votes that formed the quorum,
+/// so any component with access to the validator stakes can verify the commit. It is bound to the
+/// [`ValidatorSet::hash`] of the height, so it can't be verified against a different set.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommitCertificate {
+    height: Height,
+    round: Round,
+    block_id: Bytes32,
+    validator_set: Bytes32,
+    votes: Vec<Vote>,
+}
+
+impl CommitCertificate {
+    /// Create a new certificate from a set of votes.
+    ///
+    /// The votes are sorted by validator so the certificate is deterministic.
+    pub fn new<I>(
+        height: Height,
+        round: Round,
+        block_id: Bytes32,
+        validator_set: Bytes32,
+        votes: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = Vote>,
+    {
+        let mut votes: Vec<Vote> = votes.into_iter().collect();
+
+        votes.sort_by(|a, b| a.validator().cmp(b.validator()));
+
+        Self {
+            height,
+            round,
+            block_id,
+            validator_set,
+            votes,
+        }
+    }
+
+    /// Committed block height.
+    pub const fn height(&self) -> Height {
+        self.height
+    }
+
+    /// Round of the commit.
+    pub const fn round(&self) -> Round {
+        self.round
+    }
+
+    /// Committed block identifier.
+    pub const fn block_id(&self) -> &Bytes32 {
+        &self.block_id
+    }
+
+    /// Hash of the validator set of the height.
+    pub const fn validator_set(&self) -> &Bytes32 {
+        &self.validator_set
+    }
+
+    /// Precommit and commit votes that compose the quorum.
+    pub fn votes(&self) -> &[Vote] {
+        self.votes.as_slice()
+    }
+
+    /// Verify the certificate against a validator set.
+    ///
+    /// Every vote must be a valid precommit or commit for the certified block, signed by a
+    /// distinct validator of the height; and the voting power of the signers must achieve
+    /// consensus. The votes must be signed for the provided chain, and the certified validator set
+    /// must match the one of the stakes.
+    pub fn verify<K>(&self, chain_id: ChainId, stakes: &ValidatorStakes) -> Result<(), Error>
+    where
+        K: Keychain,
+    {
+        let height = self.height;
+        let set = ValidatorSet::from_stakes(stakes, height);
+
+        if set.hash() != self.validator_set {
+            return Err(Error::ValidatorSetMismatch);
+        }
+
+        let validators = set.len();
+        let total_power = set.total_power();
+
+        let mut signers = BTreeSet::new();
+        let mut approved = 0u64;
+
+        for vote in self.votes.iter() {
+            let is_consistent = vote.height() == height
+                && vote.round() == self.round
+                && vote.block_id() == Some(&self.block_id)
+                && vote.step() >= Step::Precommit;
+
+            if !is_consistent {
+                return Err(Error::VoteInconsistent);
+            }
+
+            if !signers.insert(vote.validator()) {
+                return Err(Error::DuplicatedVote);
+            }
+
+            let power = set.voting_power(vote.validator());
+            if power == 0 {
+                return Err(Error::ValidatorNotFound);
+            }
+
+            vote.validate::<K>(chain_id)?;
+
+            approved = approved.saturating_add(power);
+        }
+
+        Consensus::evaluate(validators, total_power, approved)
+            .is_consensus()
+            .then_some(())
+            .ok_or(Error::NotEnoughVotingPower)
+    }
+}
+
+impl Encode for CommitCertificate {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.height.encode(buf);
+        self.round.encode(buf);
+        self.block_id.encode(buf);
+        self.validator_set.encode(buf);
+        self.votes.encode(buf);
+    }
+}
+
+impl Decode for CommitCertificate {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let height = decoder.decode()?;
+        let round = decoder.decode()?;
+        let block_id = decoder.decode()?;
+        let validator_set = decoder.decode()?;
+        let votes: Vec<Vote> = decoder.decode()?;
+
+        // The votes are sorted by distinct validators
+        if votes
+            .windows(2)
+            .any(|w| w[0].validator() >= w[1].validator())
+        {
+            return Err(DecodeError::NonCanonical);
+        }
+
+        Ok(Self {
+            height,
+            round,
+            block_id,
+            validator_set,
+            votes,
+        })
+    }
+}
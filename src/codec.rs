@@ -0,0 +1,383 @@
+//! Canonical binary encoding of the protocol types.
+//!
+//! The wire form of a value is the [`CODEC_VERSION`] byte followed by its encoding. Integers are
+//! big-endian, collections are prefixed with their `u32` length, optional values and enum variants
+//! are prefixed with a tag byte. Every value has exactly one valid encoding, so the decoding will
+//! reject any input that wouldn't be produced by the encoder.
+
+use crate::{Error, Step};
+
+use fuel_crypto::{PublicKey, Signature};
+use fuel_types::{Bytes32, Bytes64};
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Version of the canonical encoding.
+pub const CODEC_VERSION: u8 = 0x01;
+
+const NONE_TAG: u8 = 0x00;
+const SOME_TAG: u8 = 0x01;
+
+/// Malformed input for the canonical decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DecodeError {
+    /// The input ended before the value was complete.
+    UnexpectedEnd,
+
+    /// The input contains bytes after the encoded value.
+    TrailingBytes,
+
+    /// The encoding version is not supported by this implementation.
+    UnsupportedVersion {
+        /// Version of the input.
+        version: u8,
+    },
+
+    /// A tag doesn't represent any variant of the decoded type.
+    InvalidTag {
+        /// Provided tag.
+        tag: u8,
+    },
+
+    /// A collection length exceeds the remaining input.
+    InvalidLength {
+        /// Provided length.
+        len: u32,
+    },
+
+    /// The value is valid, but its encoding isn't the canonical one.
+    NonCanonical,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+impl From<DecodeError> for Error {
+    fn from(e: DecodeError) -> Self {
+        match e {
+            DecodeError::UnsupportedVersion { version } => Error::UnsupportedVersion { version },
+            _ => Error::InvalidEncoding,
+        }
+    }
+}
+
+/// Canonical encoding of a type.
+pub trait Encode {
+    /// Append the encoding of the value to the buffer.
+    fn encode(&self, buf: &mut Vec<u8>);
+
+    /// Versioned wire form of the value.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = alloc::vec![CODEC_VERSION];
+
+        self.encode(&mut buf);
+
+        buf
+    }
+}
+
+/// Canonical decoding of a type.
+pub trait Decode: Sized {
+    /// Consume the encoding of a value from the decoder.
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError>;
+
+    /// Decode a value from its versioned wire form, rejecting any trailing bytes.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut decoder = Decoder::new(bytes);
+
+        let version = decoder.decode()?;
+        if version != CODEC_VERSION {
+            return Err(DecodeError::UnsupportedVersion { version });
+        }
+
+        let value = decoder.decode()?;
+
+        decoder.finish()?;
+
+        Ok(value)
+    }
+}
+
+/// Encode the length prefix of a collection.
+pub fn encode_len(len: usize, buf: &mut Vec<u8>) {
+    (len as u32).encode(buf);
+}
+
+/// Cursor over an encoded input.
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    /// Create a new decoder for the provided input.
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Bytes not consumed yet.
+    pub const fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Assert the input was entirely consumed.
+    pub fn finish(self) -> Result<(), DecodeError> {
+        self.bytes
+            .is_empty()
+            .then_some(())
+            .ok_or(DecodeError::TrailingBytes)
+    }
+
+    /// Consume the provided number of bytes.
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.bytes.len() < len {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        let (bytes, remainder) = self.bytes.split_at(len);
+        self.bytes = remainder;
+
+        Ok(bytes)
+    }
+
+    /// Consume a fixed size array.
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let mut array = [0u8; N];
+
+        array.copy_from_slice(self.take(N)?);
+
+        Ok(array)
+    }
+
+    /// Consume the length prefix of a collection.
+    ///
+    /// Every element is encoded with at least one byte, so a length that exceeds the remaining
+    /// input is rejected before any allocation.
+    pub fn decode_len(&mut self) -> Result<usize, DecodeError> {
+        let len: u32 = self.decode()?;
+
+        if len as usize > self.bytes.len() {
+            return Err(DecodeError::InvalidLength { len });
+        }
+
+        Ok(len as usize)
+    }
+
+    /// Consume a value.
+    pub fn decode<T>(&mut self) -> Result<T, DecodeError>
+    where
+        T: Decode,
+    {
+        T::decode(self)
+    }
+}
+
+impl<T> Encode for &T
+where
+    T: Encode + ?Sized,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (*self).encode(buf);
+    }
+}
+
+impl Encode for u8 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(*self);
+    }
+}
+
+impl Decode for u8 {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        decoder.array().map(|[b]| b)
+    }
+}
+
+impl Encode for bool {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (*self as u8).encode(buf);
+    }
+}
+
+impl Decode for bool {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        match decoder.decode()? {
+            0x00 => Ok(false),
+            0x01 => Ok(true),
+            tag => Err(DecodeError::InvalidTag { tag }),
+        }
+    }
+}
+
+impl Encode for u32 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_be_bytes());
+    }
+}
+
+impl Decode for u32 {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        decoder.array().map(u32::from_be_bytes)
+    }
+}
+
+impl Encode for u64 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_be_bytes());
+    }
+}
+
+impl Decode for u64 {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        decoder.array().map(u64::from_be_bytes)
+    }
+}
+
+impl Encode for Bytes32 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_ref());
+    }
+}
+
+impl Decode for Bytes32 {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        decoder.array().map(Bytes32::from)
+    }
+}
+
+impl Encode for Bytes64 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_ref());
+    }
+}
+
+impl Decode for Bytes64 {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        decoder.array().map(Bytes64::from)
+    }
+}
+
+impl Encode for PublicKey {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_ref());
+    }
+}
+
+impl Decode for PublicKey {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let mut public = PublicKey::default();
+
+        public
+            .as_mut()
+            .copy_from_slice(decoder.take(PublicKey::LEN)?);
+
+        Ok(public)
+    }
+}
+
+impl Encode for Signature {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_ref());
+    }
+}
+
+impl Decode for Signature {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        decoder.decode::<Bytes64>().map(Signature::from)
+    }
+}
+
+impl Encode for Step {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (*self as u8).encode(buf);
+    }
+}
+
+impl Decode for Step {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        match decoder.decode()? {
+            tag if tag <= Step::Commit as u8 => Ok(Step::from_u8(tag)),
+            tag => Err(DecodeError::InvalidTag { tag }),
+        }
+    }
+}
+
+impl<T> Encode for Option<T>
+where
+    T: Encode,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Some(t) => {
+                SOME_TAG.encode(buf);
+                t.encode(buf);
+            }
+
+            None => NONE_TAG.encode(buf),
+        }
+    }
+}
+
+impl<T> Decode for Option<T>
+where
+    T: Decode,
+{
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        match decoder.decode()? {
+            NONE_TAG => Ok(None),
+            SOME_TAG => decoder.decode().map(Some),
+            tag => Err(DecodeError::InvalidTag { tag }),
+        }
+    }
+}
+
+impl<A, B> Encode for (A, B)
+where
+    A: Encode,
+    B: Encode,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.0.encode(buf);
+        self.1.encode(buf);
+    }
+}
+
+impl<A, B> Decode for (A, B)
+where
+    A: Decode,
+    B: Decode,
+{
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        Ok((decoder.decode()?, decoder.decode()?))
+    }
+}
+
+impl<T> Encode for Vec<T>
+where
+    T: Encode,
+{
+    fn encode(&self, buf: &mut Vec<u8>) {
+        encode_len(self.len(), buf);
+
+        self.iter().for_each(|t| t.encode(buf));
+    }
+}
+
+impl<T> Decode for Vec<T>
+where
+    T: Decode,
+{
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let len = decoder.decode_len()?;
+
+        (0..len).map(|_| decoder.decode()).collect()
+    }
+}
@@ -1,6 +1,7 @@
 /// Evaluation of the outcome of a mutation produced by a peer message
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
+#[allow(clippy::enum_variant_names)]
 pub enum Consensus {
     /// The votes weren't enough to produce a consensus
     Inconclusive = 0x00,
@@ -24,10 +25,16 @@ impl Consensus {
         matches!(self, Self::Consensus)
     }
 
-    /// Given the number of validators and computed approvals, evaluate the consensus outcome
-    pub const fn evaluate(validators: usize, approvals: usize) -> Self {
+    /// Given the number of validators, their total voting power and the voting power of the
+    /// approvals, evaluate the consensus outcome.
+    ///
+    /// The consensus is achieved with strictly more than 2/3 of the total voting power.
+    pub const fn evaluate(validators: usize, total: u64, approvals: u64) -> Self {
         let minimum = Self::is_bft(validators);
-        let consensus = validators * 2 / 3;
+
+        // Widen to avoid overflow of large stakes
+        let consensus = total as u128 * 2;
+        let approvals = approvals as u128 * 3;
 
         if !minimum {
             Consensus::Reject
@@ -44,7 +51,15 @@ fn evaluate() {
     assert!(!Consensus::is_bft(3));
     assert!(Consensus::is_bft(4));
 
-    assert!(!Consensus::evaluate(3, 3).is_consensus());
-    assert!(!Consensus::evaluate(4, 2).is_consensus());
-    assert!(Consensus::evaluate(4, 3).is_consensus());
+    assert!(!Consensus::evaluate(3, 3, 3).is_consensus());
+    assert!(!Consensus::evaluate(4, 4, 2).is_consensus());
+    assert!(Consensus::evaluate(4, 4, 3).is_consensus());
+
+    // Voting power is weighted by stake
+    assert!(!Consensus::evaluate(3, 100, 100).is_consensus());
+    assert!(!Consensus::evaluate(4, 30, 20).is_consensus());
+    assert!(Consensus::evaluate(4, 30, 21).is_consensus());
+    assert!(Consensus::evaluate(4, 100, 67).is_consensus());
+    assert!(!Consensus::evaluate(4, 100, 66).is_consensus());
+    assert!(Consensus::evaluate(4, u64::MAX, u64::MAX).is_consensus());
 }
@@ -1,10 +1,13 @@
-use crate::Height;
+use crate::{Decode, DecodeError, Decoder, Encode, Height, Round, Step};
+
+use alloc::vec::Vec;
 
 use core::convert::Infallible;
 use core::fmt;
 
 /// Consensus error variants
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Error {
     /// The block validation failed
     BlockValidation,
@@ -17,23 +20,190 @@ pub enum Error {
         valid_before: Height,
     },
 
+    /// The signature was refused because it would conflict with, or regress from, the last signed
+    /// message of the node.
+    DoubleSign {
+        /// Height of the refused message.
+        height: Height,
+        /// Round of the refused message.
+        round: Round,
+        /// Step of the refused message.
+        step: Step,
+    },
+
+    /// A validator contributed more than one vote to a quorum.
+    DuplicatedVote,
+
     /// Failed to define elapsed time since genesis
     ElapsedTimeFailure,
 
+    /// The provided data doesn't belong to the current height.
+    HeightMismatch {
+        /// Current height of the reactor.
+        expected: Height,
+        /// Height of the provided data.
+        found: Height,
+    },
+
+    /// The provided bytes are not a valid encoding.
+    InvalidEncoding,
+
     /// The provided signature is invalid
     InvalidSignature,
 
+    /// The moderator failed to dispatch a message of the reactor, including the failures of the
+    /// application that consumes it.
+    ModeratorFailure,
+
+    /// The votes do not achieve 2/3+ of the voting power.
+    NotEnoughVotingPower,
+
     /// The node isn't a round validator
     NotRoundValidator,
 
     /// The requested resource is not available
     ResourceNotAvailable,
 
+    /// The encoding version is not supported by this implementation.
+    UnsupportedVersion {
+        /// Version of the provided encoding.
+        version: u8,
+    },
+
     /// The validator is not included for this round.
     ValidatorNotFound,
 
+    /// The validator set doesn't match the one committed to.
+    ValidatorSetMismatch,
+
     /// Vote is missing either the block id or round.
     VoteInconsistent,
+
+    /// The write-ahead log failed to persist or load its entries.
+    WalFailure,
+}
+
+impl Error {
+    const BLOCK_VALIDATION: u8 = 0x00;
+    const DUPLICATED_STAKE: u8 = 0x01;
+    const DUPLICATED_VOTE: u8 = 0x02;
+    const ELAPSED_TIME_FAILURE: u8 = 0x03;
+    const HEIGHT_MISMATCH: u8 = 0x04;
+    const INVALID_ENCODING: u8 = 0x05;
+    const INVALID_SIGNATURE: u8 = 0x06;
+    const NOT_ENOUGH_VOTING_POWER: u8 = 0x07;
+    const NOT_ROUND_VALIDATOR: u8 = 0x08;
+    const RESOURCE_NOT_AVAILABLE: u8 = 0x09;
+    const UNSUPPORTED_VERSION: u8 = 0x0a;
+    const VALIDATOR_NOT_FOUND: u8 = 0x0b;
+    const VOTE_INCONSISTENT: u8 = 0x0c;
+    const WAL_FAILURE: u8 = 0x0d;
+    const VALIDATOR_SET_MISMATCH: u8 = 0x0e;
+    const DOUBLE_SIGN: u8 = 0x0f;
+    const MODERATOR_FAILURE: u8 = 0x10;
+}
+
+impl Encode for Error {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::BlockValidation => Self::BLOCK_VALIDATION.encode(buf),
+
+            Self::DuplicatedStake {
+                height,
+                valid_before,
+            } => {
+                Self::DUPLICATED_STAKE.encode(buf);
+                height.encode(buf);
+                valid_before.encode(buf);
+            }
+
+            Self::DuplicatedVote => Self::DUPLICATED_VOTE.encode(buf),
+            Self::ElapsedTimeFailure => Self::ELAPSED_TIME_FAILURE.encode(buf),
+
+            Self::HeightMismatch { expected, found } => {
+                Self::HEIGHT_MISMATCH.encode(buf);
+                expected.encode(buf);
+                found.encode(buf);
+            }
+
+            Self::InvalidEncoding => Self::INVALID_ENCODING.encode(buf),
+            Self::InvalidSignature => Self::INVALID_SIGNATURE.encode(buf),
+            Self::NotEnoughVotingPower => Self::NOT_ENOUGH_VOTING_POWER.encode(buf),
+            Self::NotRoundValidator => Self::NOT_ROUND_VALIDATOR.encode(buf),
+            Self::ResourceNotAvailable => Self::RESOURCE_NOT_AVAILABLE.encode(buf),
+
+            Self::UnsupportedVersion { version } => {
+                Self::UNSUPPORTED_VERSION.encode(buf);
+                version.encode(buf);
+            }
+
+            Self::ValidatorNotFound => Self::VALIDATOR_NOT_FOUND.encode(buf),
+            Self::VoteInconsistent => Self::VOTE_INCONSISTENT.encode(buf),
+            Self::WalFailure => Self::WAL_FAILURE.encode(buf),
+            Self::ValidatorSetMismatch => Self::VALIDATOR_SET_MISMATCH.encode(buf),
+
+            Self::DoubleSign {
+                height,
+                round,
+                step,
+            } => {
+                Self::DOUBLE_SIGN.encode(buf);
+                height.encode(buf);
+                round.encode(buf);
+                step.encode(buf);
+            }
+
+            Self::ModeratorFailure => Self::MODERATOR_FAILURE.encode(buf),
+        }
+    }
+}
+
+impl Decode for Error {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let error = match decoder.decode()? {
+            Self::BLOCK_VALIDATION => Self::BlockValidation,
+
+            Self::DUPLICATED_STAKE => Self::DuplicatedStake {
+                height: decoder.decode()?,
+                valid_before: decoder.decode()?,
+            },
+
+            Self::DUPLICATED_VOTE => Self::DuplicatedVote,
+            Self::ELAPSED_TIME_FAILURE => Self::ElapsedTimeFailure,
+
+            Self::HEIGHT_MISMATCH => Self::HeightMismatch {
+                expected: decoder.decode()?,
+                found: decoder.decode()?,
+            },
+
+            Self::INVALID_ENCODING => Self::InvalidEncoding,
+            Self::INVALID_SIGNATURE => Self::InvalidSignature,
+            Self::NOT_ENOUGH_VOTING_POWER => Self::NotEnoughVotingPower,
+            Self::NOT_ROUND_VALIDATOR => Self::NotRoundValidator,
+            Self::RESOURCE_NOT_AVAILABLE => Self::ResourceNotAvailable,
+
+            Self::UNSUPPORTED_VERSION => Self::UnsupportedVersion {
+                version: decoder.decode()?,
+            },
+
+            Self::VALIDATOR_NOT_FOUND => Self::ValidatorNotFound,
+            Self::VOTE_INCONSISTENT => Self::VoteInconsistent,
+            Self::WAL_FAILURE => Self::WalFailure,
+            Self::VALIDATOR_SET_MISMATCH => Self::ValidatorSetMismatch,
+
+            Self::DOUBLE_SIGN => Self::DoubleSign {
+                height: decoder.decode()?,
+                round: decoder.decode()?,
+                step: decoder.decode()?,
+            },
+
+            Self::MODERATOR_FAILURE => Self::ModeratorFailure,
+
+            tag => return Err(DecodeError::InvalidTag { tag }),
+        };
+
+        Ok(error)
+    }
 }
 
 impl fmt::Display for Error {
@@ -42,6 +212,12 @@ impl fmt::Display for Error {
     }
 }
 
+impl From<fuel_crypto::Error> for Error {
+    fn from(_: fuel_crypto::Error) -> Error {
+        Error::ResourceNotAvailable
+    }
+}
+
 impl From<Error> for Infallible {
     fn from(_: Error) -> Infallible {
         unreachable!()
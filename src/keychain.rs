@@ -1,17 +1,37 @@
-use crate::Height;
+use crate::{ChainId, Error, Height, Proposal, Round, Step, Vote};
 
+use async_trait::async_trait;
 use fuel_crypto::borrown::Borrown;
 use fuel_crypto::{Hasher, Keystore, PublicKey, SecretKey, Signature, Signer};
+use fuel_types::Bytes32;
+
+use alloc::boxed::Box;
+
+#[cfg(feature = "file-keychain")]
+pub mod file;
 
 #[cfg(feature = "memory")]
 pub mod memory;
 
+#[cfg(feature = "std")]
+pub mod protection;
+
+#[cfg(any(feature = "file-keychain", feature = "memory"))]
+mod ranges;
+
+#[cfg(feature = "remote-signer")]
+pub mod remote;
+
 /// Keychain provider for the protocol.
 pub trait Keychain {
     /// Concrete error type
+    ///
+    /// The conversion into the consensus error reports the failures of the signature of votes and
+    /// proposals.
     type Error: From<fuel_crypto::Error>
         + From<<Self::Signer as Signer>::Error>
-        + From<<<Self::Signer as Signer>::Keystore as Keystore>::Error>;
+        + From<<<Self::Signer as Signer>::Keystore as Keystore>::Error>
+        + Into<Error>;
 
     /// Signature provider
     type Signer: Signer<Keystore = Self::Keystore>;
@@ -52,6 +72,49 @@ pub trait Keychain {
         Ok(signature)
     }
 
+    /// Sign a vote of the node for the given chain.
+    ///
+    /// Signs the vote digest with the key of the height by default. A signer that must inspect the
+    /// vote, such as a remote signer, can override it instead of signing an opaque digest.
+    fn sign_vote(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Option<&Bytes32>,
+        step: Step,
+    ) -> Result<Signature, Self::Error> {
+        let digest = Vote::_digest(Hasher::default(), chain_id, height, round, block_id, step);
+
+        self.sign(height, digest)
+    }
+
+    /// Sign a block proposal of the node for the given chain.
+    ///
+    /// Signs the proposal digest with the key of the height by default. A signer that must inspect
+    /// the proposal, such as a remote signer, can override it instead of signing an opaque digest.
+    fn sign_proposal(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: &Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+    ) -> Result<Signature, Self::Error> {
+        let digest = Proposal::_digest(
+            Hasher::default(),
+            chain_id,
+            height,
+            round,
+            block_id,
+            pol_round,
+            timestamp,
+        );
+
+        self.sign(height, digest)
+    }
+
     /// Sign the result of a given digest with a provided key
     #[cfg(not(feature = "std"))]
     fn sign_with_key(secret: &SecretKey, digest: Hasher) -> Signature;
@@ -78,3 +141,88 @@ pub trait Keychain {
         Ok(())
     }
 }
+
+/// Asynchronous keychain provider for the protocol.
+///
+/// Consumed by the reactor so a signer that performs I/O, such as an HSM or a remote signer,
+/// doesn't block the reactor task. Every synchronous [`Keychain`] is also an asynchronous keychain
+/// that completes immediately.
+#[async_trait]
+pub trait AsyncKeychain: Send + Sync {
+    /// Concrete error type
+    ///
+    /// The conversion into the consensus error reports the failures of the signature of votes and
+    /// proposals.
+    type Error: Into<Error> + Send;
+
+    /// Provider of the verification of the signatures of the peers.
+    type Verifier: Keychain;
+
+    /// Fetch the public key of the node for the given height
+    async fn public_async(&self, height: Height) -> Result<Option<PublicKey>, Self::Error>;
+
+    /// The node is a validator if the keychain provide a public key for the requested height
+    async fn is_validator_for_async(&self, height: Height) -> Result<bool, Self::Error> {
+        self.public_async(height).await.map(|p| p.is_some())
+    }
+
+    /// Sign a vote of the node for the given chain.
+    async fn sign_vote_async(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Option<Bytes32>,
+        step: Step,
+    ) -> Result<Signature, Self::Error>;
+
+    /// Sign a block proposal of the node for the given chain.
+    async fn sign_proposal_async(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+    ) -> Result<Signature, Self::Error>;
+}
+
+#[async_trait]
+impl<K> AsyncKeychain for K
+where
+    K: Keychain + Send + Sync,
+    K::Error: Send,
+{
+    type Error = K::Error;
+    type Verifier = K;
+
+    async fn public_async(&self, height: Height) -> Result<Option<PublicKey>, Self::Error> {
+        Keychain::public(self, height).map(|p| p.map(Borrown::into_owned))
+    }
+
+    async fn sign_vote_async(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Option<Bytes32>,
+        step: Step,
+    ) -> Result<Signature, Self::Error> {
+        Keychain::sign_vote(self, chain_id, height, round, block_id.as_ref(), step)
+    }
+
+    async fn sign_proposal_async(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+    ) -> Result<Signature, Self::Error> {
+        Keychain::sign_proposal(
+            self, chain_id, height, round, &block_id, pol_round, timestamp,
+        )
+    }
+}
@@ -0,0 +1,468 @@
+use super::ranges::KeyRanges;
+use crate::{Decode, DecodeError, Decoder, Encode, Height, Keychain};
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use fuel_crypto::borrown::Borrown;
+use fuel_crypto::{Keystore, SecretKey, Signer};
+use fuel_types::Bytes32;
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+use core::convert::Infallible;
+use core::fmt;
+use core::ops::{Range, RangeBounds};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Cost parameters of the scrypt derivation of the encryption key from the passphrase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KdfParams {
+    /// Base 2 logarithm of the CPU/memory cost
+    pub log_n: u8,
+
+    /// Block size
+    pub r: u32,
+
+    /// Parallelization
+    pub p: u32,
+}
+
+impl KdfParams {
+    /// Recommended parameters for interactive usage
+    pub const RECOMMENDED: Self = Self {
+        log_n: scrypt::Params::RECOMMENDED_LOG_N,
+        r: scrypt::Params::RECOMMENDED_R,
+        p: scrypt::Params::RECOMMENDED_P,
+    };
+
+    /// Highest accepted parameters, costing up to 1 GiB of memory
+    pub const MAX: Self = Self {
+        log_n: 20,
+        r: 8,
+        p: 16,
+    };
+
+    /// Check if the cost of the parameters doesn't exceed [`KdfParams::MAX`].
+    pub const fn is_bounded(&self) -> bool {
+        self.log_n <= Self::MAX.log_n && self.r <= Self::MAX.r && self.p <= Self::MAX.p
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self::RECOMMENDED
+    }
+}
+
+/// Encrypted file-backed implementation of a keychain
+///
+/// Every key is valid for a range of heights, as in
+/// [`MemoryKeychain`](crate::MemoryKeychain), and is stored encrypted with ChaCha20-Poly1305 under
+/// a key derived from a passphrase with scrypt. The file is replaced atomically on every update,
+/// so the keys can be added and retired while the reactor is running.
+pub struct FileKeychain {
+    path: PathBuf,
+    params: KdfParams,
+    salt: Bytes32,
+    cipher: ChaCha20Poly1305,
+    keys: KeyRanges,
+}
+
+impl FileKeychain {
+    /// Version of the file format
+    pub const VERSION: u8 = 0x01;
+
+    /// Domain separation tag bound to every encrypted value
+    pub const DOMAIN_TAG: &'static [u8] = b"fuel-bft/file-keychain/v1";
+
+    /// Create a new empty keychain at the given path with the recommended derivation parameters.
+    ///
+    /// Fails if the file already exists.
+    pub fn create<P, S>(path: P, passphrase: S) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+        S: AsRef<[u8]>,
+    {
+        Self::create_with_params(path, passphrase, KdfParams::RECOMMENDED)
+    }
+
+    /// Create a new empty keychain at the given path.
+    ///
+    /// Fails if the file already exists.
+    pub fn create_with_params<P, S>(path: P, passphrase: S, params: KdfParams) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+        S: AsRef<[u8]>,
+    {
+        let path = path.as_ref().to_path_buf();
+
+        if path.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "keychain file already exists",
+            ));
+        }
+
+        let mut salt = Bytes32::zeroed();
+        OsRng.try_fill_bytes(salt.as_mut())?;
+
+        let cipher = Self::cipher(passphrase.as_ref(), &params, &salt)?;
+        let keychain = Self {
+            path,
+            params,
+            salt,
+            cipher,
+            keys: KeyRanges::default(),
+        };
+
+        keychain.persist()?;
+
+        Ok(keychain)
+    }
+
+    /// Open an existing keychain, decrypting its keys with the passphrase.
+    ///
+    /// A wrong passphrase is reported as [`io::ErrorKind::InvalidData`].
+    pub fn open<P, S>(path: P, passphrase: S) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+        S: AsRef<[u8]>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let file = KeychainFile::read(&path)?;
+
+        let cipher = Self::cipher(passphrase.as_ref(), &file.params, &file.salt)?;
+        let mut keychain = Self {
+            path,
+            params: file.params,
+            salt: file.salt,
+            cipher,
+            keys: KeyRanges::default(),
+        };
+
+        keychain.keys = keychain.unseal(&file)?;
+
+        Ok(keychain)
+    }
+
+    /// Read the keys of the file again, discarding the keys in memory.
+    ///
+    /// Allows the keys to be updated by another process while the reactor is running.
+    pub fn reload(&mut self) -> io::Result<()> {
+        let file = KeychainFile::read(&self.path)?;
+
+        if file.params != self.params || file.salt != self.salt {
+            return Err(invalid_data("keychain was encrypted with another key"));
+        }
+
+        self.keys = self.unseal(&file)?;
+
+        Ok(())
+    }
+
+    /// Add a secret to the keychain, valid for the provided heights, and persist it.
+    ///
+    /// The keys previously inserted for the same heights are replaced; the ranges that intersect
+    /// with the provided heights are split, so their keys remain valid for the other heights.
+    pub fn insert<H>(&mut self, height: H, secret: SecretKey) -> io::Result<()>
+    where
+        H: RangeBounds<Height>,
+    {
+        let mut keys = self.keys.clone();
+
+        keys.insert(height, secret);
+
+        self.replace(keys)
+    }
+
+    /// Remove the keys of the provided heights and persist the keychain.
+    ///
+    /// The ranges that intersect with the provided heights are split, so their keys remain valid
+    /// for the other heights.
+    pub fn retire<H>(&mut self, height: H) -> io::Result<()>
+    where
+        H: RangeBounds<Height>,
+    {
+        let mut keys = self.keys.clone();
+
+        keys.remove(height);
+
+        self.replace(keys)
+    }
+
+    /// Iterate the sorted height ranges of the keychain
+    pub fn ranges(&self) -> impl Iterator<Item = Range<Height>> + '_ {
+        self.keys.iter().map(|(range, _)| range)
+    }
+
+    /// Path of the keychain file.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Derivation parameters of the encryption key.
+    pub const fn params(&self) -> &KdfParams {
+        &self.params
+    }
+
+    fn cipher(
+        passphrase: &[u8],
+        params: &KdfParams,
+        salt: &Bytes32,
+    ) -> io::Result<ChaCha20Poly1305> {
+        // The parameters are read from the file, so they are bounded before the derivation
+        // allocates its memory
+        if !params.is_bounded() {
+            return Err(invalid_data("key derivation parameters exceed the maximum"));
+        }
+
+        let params = scrypt::Params::new(params.log_n, params.r, params.p, Bytes32::LEN)
+            .map_err(|_| invalid_data("invalid key derivation parameters"))?;
+
+        let mut key = Key::default();
+
+        scrypt::scrypt(passphrase, salt.as_ref(), &params, key.as_mut_slice())
+            .map_err(|_| invalid_data("invalid key derivation parameters"))?;
+
+        Ok(ChaCha20Poly1305::new(&key))
+    }
+
+    fn aad(range: &Range<Height>) -> Vec<u8> {
+        let mut aad = Self::DOMAIN_TAG.to_vec();
+
+        range.start.encode(&mut aad);
+        range.end.encode(&mut aad);
+
+        aad
+    }
+
+    fn seal<const N: usize>(&self, msg: &[u8], aad: &[u8]) -> io::Result<[u8; N]> {
+        let mut nonce = Nonce::default();
+        OsRng.try_fill_bytes(&mut nonce)?;
+
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, Payload { msg, aad })
+            .map_err(|_| io::Error::other("failed to encrypt key"))?;
+
+        let mut sealed = [0u8; N];
+
+        sealed[..NONCE_LEN].copy_from_slice(&nonce);
+        sealed[NONCE_LEN..].copy_from_slice(&ciphertext);
+
+        Ok(sealed)
+    }
+
+    fn open_sealed(&self, sealed: &[u8], aad: &[u8]) -> io::Result<Vec<u8>> {
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+
+        self.cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad,
+                },
+            )
+            .map_err(|_| invalid_data("invalid passphrase or corrupted keychain"))
+    }
+
+    fn unseal(&self, file: &KeychainFile) -> io::Result<KeyRanges> {
+        self.open_sealed(&file.check, Self::DOMAIN_TAG)?;
+
+        file.keys
+            .iter()
+            .try_fold(KeyRanges::default(), |mut keys, sealed| {
+                let range = sealed.start..sealed.end;
+                let secret = self.open_sealed(&sealed.secret, &Self::aad(&range))?;
+                let secret = SecretKey::try_from(secret.as_slice())
+                    .map_err(|_| invalid_data("invalid secret key"))?;
+
+                keys.insert(range, secret);
+
+                Ok(keys)
+            })
+    }
+
+    /// Persist the provided keys and replace the keys in memory.
+    fn replace(&mut self, keys: KeyRanges) -> io::Result<()> {
+        let keys = core::mem::replace(&mut self.keys, keys);
+
+        if let Err(e) = self.persist() {
+            self.keys = keys;
+
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    fn persist(&self) -> io::Result<()> {
+        let keys = self
+            .keys
+            .iter()
+            .map(|(range, secret)| {
+                Ok(SealedKey {
+                    start: range.start,
+                    end: range.end,
+                    secret: self.seal(secret.as_ref(), &Self::aad(&range))?,
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let file = KeychainFile {
+            params: self.params,
+            salt: self.salt,
+            check: self.seal(&[], Self::DOMAIN_TAG)?,
+            keys,
+        };
+
+        // The keychain is replaced atomically so a crash will preserve either of the versions
+        let tmp = self.path.with_extension("tmp");
+
+        let mut f = File::create(&tmp)?;
+        f.write_all(&file.to_bytes())?;
+        f.sync_all()?;
+
+        fs::rename(&tmp, &self.path)
+    }
+}
+
+impl fmt::Debug for FileKeychain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileKeychain")
+            .field("path", &self.path)
+            .field("params", &self.params)
+            .field("ranges", &self.ranges().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Keystore for FileKeychain {
+    type Error = Infallible;
+    type KeyId = Height;
+
+    fn secret(&self, id: &Height) -> Result<Option<Borrown<'_, SecretKey>>, Self::Error> {
+        Ok(self.keys.get(id).map(Borrown::from))
+    }
+}
+
+impl Signer for FileKeychain {
+    type Error = fuel_crypto::Error;
+    type Keystore = Self;
+
+    fn keystore(&self) -> Result<&Self::Keystore, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl Keychain for FileKeychain {
+    type Error = <Self::Signer as Signer>::Error;
+    type Signer = Self;
+    type Keystore = Self;
+
+    fn signer(&self) -> &Self::Signer {
+        self
+    }
+}
+
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const CHECK_LEN: usize = NONCE_LEN + TAG_LEN;
+const SEALED_KEY_LEN: usize = NONCE_LEN + SecretKey::LEN + TAG_LEN;
+
+/// Encrypted secret of a range of heights
+struct SealedKey {
+    start: Height,
+    end: Height,
+    /// Nonce followed by the ciphertext and its tag
+    secret: [u8; SEALED_KEY_LEN],
+}
+
+impl Encode for SealedKey {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.start.encode(buf);
+        self.end.encode(buf);
+        buf.extend_from_slice(&self.secret);
+    }
+}
+
+impl Decode for SealedKey {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            start: decoder.decode()?,
+            end: decoder.decode()?,
+            secret: decoder.array()?,
+        })
+    }
+}
+
+/// Contents of the keychain file
+struct KeychainFile {
+    params: KdfParams,
+    salt: Bytes32,
+    /// Encryption of an empty message, to reject a wrong passphrase of an empty keychain
+    check: [u8; CHECK_LEN],
+    keys: Vec<SealedKey>,
+}
+
+impl KeychainFile {
+    fn read(path: &Path) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+
+        Self::from_bytes(&bytes).map_err(|_| invalid_data("invalid keychain file"))
+    }
+}
+
+impl Encode for KeychainFile {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        FileKeychain::VERSION.encode(buf);
+        self.params.log_n.encode(buf);
+        self.params.r.encode(buf);
+        self.params.p.encode(buf);
+        self.salt.encode(buf);
+        buf.extend_from_slice(&self.check);
+        self.keys.encode(buf);
+    }
+}
+
+impl Decode for KeychainFile {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let version = decoder.decode()?;
+        if version != FileKeychain::VERSION {
+            return Err(DecodeError::UnsupportedVersion { version });
+        }
+
+        let params = KdfParams {
+            log_n: decoder.decode()?,
+            r: decoder.decode()?,
+            p: decoder.decode()?,
+        };
+
+        let salt = decoder.decode()?;
+        let check = decoder.array()?;
+        let keys: Vec<SealedKey> = decoder.decode()?;
+
+        // The ranges are sorted, disjoint and not empty
+        let is_canonical =
+            keys.iter().all(|k| k.start < k.end) && keys.windows(2).all(|w| w[0].end <= w[1].start);
+
+        if !is_canonical {
+            return Err(DecodeError::NonCanonical);
+        }
+
+        Ok(Self {
+            params,
+            salt,
+            check,
+            keys,
+        })
+    }
+}
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -1,3 +1,4 @@
+use super::ranges::KeyRanges;
 use crate::{Height, Keychain};
 
 use fuel_crypto::borrown::Borrown;
@@ -7,31 +8,43 @@ use rand::SeedableRng;
 
 use core::convert::Infallible;
 use core::ops::{Range, RangeBounds};
-use std::collections::HashMap;
 
 /// Default in-memory implementation of a keychain
+///
+/// Every key is valid for a range of heights, so the node can be configured with scheduled
+/// rotations of its hot key.
 #[derive(Debug, Default, Clone)]
 pub struct MemoryKeychain {
-    keys: HashMap<Range<Height>, SecretKey>,
+    keys: KeyRanges,
 }
 
 impl MemoryKeychain {
-    /// Add a new password generated secret to the keychain
-    pub fn insert<H, P>(&mut self, _height: H, password: P)
+    /// Add a new password generated secret to the keychain, valid for the provided heights.
+    ///
+    /// The keys previously inserted for the same heights are replaced; the ranges that intersect
+    /// with the provided heights are split, so their keys remain valid for the other heights.
+    pub fn insert<H, P>(&mut self, height: H, password: P)
     where
         H: RangeBounds<Height>,
         P: AsRef<[u8]>,
     {
-        let secret = Self::secret(password);
+        self.insert_secret(height, Self::secret(password))
+    }
+
+    /// Add a secret to the keychain, valid for the provided heights.
+    ///
+    /// The keys previously inserted for the same heights are replaced; the ranges that intersect
+    /// with the provided heights are split, so their keys remain valid for the other heights.
+    pub fn insert_secret<H>(&mut self, height: H, secret: SecretKey)
+    where
+        H: RangeBounds<Height>,
+    {
+        self.keys.insert(height, secret);
+    }
 
-        // TODO implement range split?
-        self.keys.insert(
-            Range {
-                start: Height::MIN,
-                end: Height::MAX,
-            },
-            secret,
-        );
+    /// Iterate the sorted height ranges of the keychain with their secrets
+    pub fn iter(&self) -> impl Iterator<Item = (Range<Height>, &SecretKey)> {
+        self.keys.iter()
     }
 
     /// Generate a new secret
@@ -51,10 +64,7 @@ impl Keystore for MemoryKeychain {
     type KeyId = Height;
 
     fn secret(&self, id: &Height) -> Result<Option<Borrown<'_, SecretKey>>, Self::Error> {
-        self.keys
-            .iter()
-            .find_map(|(range, key)| range.contains(id).then(|| Ok(key.into())))
-            .transpose()
+        Ok(self.keys.get(id).map(Borrown::from))
     }
 }
 
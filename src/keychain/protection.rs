@@ -0,0 +1,285 @@
+use crate::{
+    ChainId, Decode, DecodeError, Decoder, Encode, Error, Height, Keychain, Proposal, Round, Step,
+    Vote,
+};
+
+use fuel_crypto::borrown::Borrown;
+use fuel_crypto::{Hasher, Keystore, PublicKey, SecretKey, Signature, Signer};
+use fuel_types::Bytes32;
+
+use core::cmp::Ordering;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Last message signed by a node.
+///
+/// A proposal is recorded with the [`Step::Propose`] step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LastSigned {
+    /// Block height
+    pub height: Height,
+    /// Height round
+    pub round: Round,
+    /// Signed step
+    pub step: Step,
+    /// Digest of the signed message, as computed by [`Vote::digest`] or [`Proposal::digest`]
+    pub digest: Bytes32,
+}
+
+impl LastSigned {
+    /// Check if a message can be signed after this one.
+    ///
+    /// The message must advance the height, round or step; only the identical message can be
+    /// signed again, so a message that differs in any signed field, or a proposal and a vote of
+    /// the same step, are refused.
+    pub fn allows(&self, height: Height, round: Round, step: Step, digest: &Bytes32) -> bool {
+        match (height, round, step).cmp(&(self.height, self.round, self.step)) {
+            Ordering::Greater => true,
+            Ordering::Equal => &self.digest == digest,
+            Ordering::Less => false,
+        }
+    }
+}
+
+impl Encode for LastSigned {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.height.encode(buf);
+        self.round.encode(buf);
+        self.step.encode(buf);
+        self.digest.encode(buf);
+    }
+}
+
+impl Decode for LastSigned {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            height: decoder.decode()?,
+            round: decoder.decode()?,
+            step: decoder.decode()?,
+            digest: decoder.decode()?,
+        })
+    }
+}
+
+/// Keychain wrapper that refuses to sign conflicting or regressing messages.
+///
+/// The last signed vote or proposal is recorded, and a new message is signed only if it advances
+/// the height, round or step, or repeats the recorded message. Otherwise, the signature fails
+/// with [`Error::DoubleSign`]. When opened with a file, the record is persisted before the
+/// signature is returned, so the protection holds across restarts.
+///
+/// Opaque digests can't be checked, so [`Keychain::sign`] fails with
+/// [`Error::ResourceNotAvailable`].
+#[derive(Debug)]
+pub struct SlashingProtection<K> {
+    keychain: K,
+    path: Option<PathBuf>,
+    last: Mutex<Option<LastSigned>>,
+}
+
+impl<K> SlashingProtection<K>
+where
+    K: Keychain,
+{
+    /// Protect a keychain, recording the last signed message in memory only.
+    pub const fn new(keychain: K) -> Self {
+        Self {
+            keychain,
+            path: None,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Protect a keychain, persisting the last signed message at the given path.
+    ///
+    /// The record of a previous execution is loaded if the file exists.
+    pub fn open<P>(keychain: K, path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+
+        let last = match fs::read(&path) {
+            Ok(bytes) => Some(LastSigned::from_bytes(&bytes).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid last signed record")
+            })?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            keychain,
+            path: Some(path),
+            last: Mutex::new(last),
+        })
+    }
+
+    /// Protected keychain.
+    pub const fn keychain(&self) -> &K {
+        &self.keychain
+    }
+
+    /// Path of the persisted record, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Last signed message.
+    pub fn last_signed(&self) -> Option<LastSigned> {
+        self.last.lock().ok().and_then(|last| *last)
+    }
+
+    /// Check the message against the last signed one, and record it once signed.
+    fn protect<F>(
+        &self,
+        height: Height,
+        round: Round,
+        step: Step,
+        digest: Bytes32,
+        sign: F,
+    ) -> Result<Signature, Error>
+    where
+        F: FnOnce(&K) -> Result<Signature, K::Error>,
+    {
+        let mut last = self.last.lock().map_err(|_| Error::ResourceNotAvailable)?;
+
+        if last
+            .as_ref()
+            .is_some_and(|l| !l.allows(height, round, step, &digest))
+        {
+            #[cfg(feature = "trace")]
+            tracing::warn!(
+                "refused to sign height {} round {} step {:?}; last signed {:?}",
+                height,
+                round,
+                step,
+                last
+            );
+
+            return Err(Error::DoubleSign {
+                height,
+                round,
+                step,
+            });
+        }
+
+        let signature = sign(&self.keychain).map_err(Into::into)?;
+
+        let signed = LastSigned {
+            height,
+            round,
+            step,
+            digest,
+        };
+
+        // The signature is released only after the record is persisted
+        if let Some(path) = &self.path {
+            Self::persist(path, &signed).map_err(|_e| {
+                #[cfg(feature = "trace")]
+                tracing::error!("failed to persist the last signed message: {}", _e);
+
+                Error::ResourceNotAvailable
+            })?;
+        }
+
+        *last = Some(signed);
+
+        Ok(signature)
+    }
+
+    fn persist(path: &Path, signed: &LastSigned) -> io::Result<()> {
+        // The record is replaced atomically so a crash will preserve either of the versions
+        let tmp = path.with_extension("tmp");
+
+        let mut file = File::create(&tmp)?;
+        file.write_all(&signed.to_bytes())?;
+        file.sync_all()?;
+
+        fs::rename(&tmp, path)
+    }
+}
+
+impl<K> Keystore for SlashingProtection<K>
+where
+    K: Keychain,
+{
+    type Error = Error;
+    type KeyId = Height;
+
+    fn secret(&self, _id: &Height) -> Result<Option<Borrown<'_, SecretKey>>, Self::Error> {
+        Ok(None)
+    }
+
+    fn public(&self, id: &Height) -> Result<Option<Borrown<'_, PublicKey>>, Self::Error> {
+        self.keychain.public(*id).map_err(Into::into)
+    }
+}
+
+impl<K> Signer for SlashingProtection<K>
+where
+    K: Keychain,
+{
+    type Error = Error;
+    type Keystore = Self;
+
+    fn keystore(&self) -> Result<&Self::Keystore, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl<K> Keychain for SlashingProtection<K>
+where
+    K: Keychain,
+{
+    type Error = Error;
+    type Signer = Self;
+    type Keystore = Self;
+
+    fn signer(&self) -> &Self::Signer {
+        self
+    }
+
+    fn sign_vote(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Option<&Bytes32>,
+        step: Step,
+    ) -> Result<Signature, Self::Error> {
+        let digest =
+            Vote::_digest(Hasher::default(), chain_id, height, round, block_id, step).digest();
+
+        self.protect(height, round, step, digest, |keychain| {
+            keychain.sign_vote(chain_id, height, round, block_id, step)
+        })
+    }
+
+    fn sign_proposal(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: &Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+    ) -> Result<Signature, Self::Error> {
+        let digest = Proposal::_digest(
+            Hasher::default(),
+            chain_id,
+            height,
+            round,
+            block_id,
+            pol_round,
+            timestamp,
+        )
+        .digest();
+
+        self.protect(height, round, Step::Propose, digest, |keychain| {
+            keychain.sign_proposal(chain_id, height, round, block_id, pol_round, timestamp)
+        })
+    }
+}
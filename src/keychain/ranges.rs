@@ -0,0 +1,81 @@
+use crate::stake::normalize_range;
+use crate::Height;
+
+use fuel_crypto::SecretKey;
+
+use core::ops::{Range, RangeBounds};
+use std::collections::BTreeMap;
+
+/// Secrets valid for disjoint ranges of heights.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct KeyRanges {
+    /// Initial height -> (final exclusive height, secret)
+    keys: BTreeMap<Height, (Height, SecretKey)>,
+}
+
+impl KeyRanges {
+    /// Add a secret valid for the provided heights.
+    ///
+    /// The ranges that intersect with the provided heights are split, so their secrets remain
+    /// valid for the other heights. An empty range is ignored.
+    pub fn insert<H>(&mut self, height: H, secret: SecretKey)
+    where
+        H: RangeBounds<Height>,
+    {
+        let Range { start, end } = normalize_range(height);
+
+        if end <= start {
+            return;
+        }
+
+        self.remove(start..end);
+        self.keys.insert(start, (end, secret));
+    }
+
+    /// Remove the secrets of the provided heights, splitting the ranges that intersect with them.
+    pub fn remove<H>(&mut self, height: H)
+    where
+        H: RangeBounds<Height>,
+    {
+        let Range { start, end } = normalize_range(height);
+
+        if end <= start {
+            return;
+        }
+
+        let intersecting: Vec<_> = self
+            .keys
+            .range(..end)
+            .filter(|(_, (e, _))| start < *e)
+            .map(|(s, (e, secret))| (*s, *e, *secret))
+            .collect();
+
+        intersecting.into_iter().for_each(|(s, e, secret)| {
+            self.keys.remove(&s);
+
+            if s < start {
+                self.keys.insert(s, (start, secret));
+            }
+
+            if end < e {
+                self.keys.insert(end, (e, secret));
+            }
+        });
+    }
+
+    /// Secret valid for the provided height
+    pub fn get(&self, height: &Height) -> Option<&SecretKey> {
+        self.keys
+            .range(..=height)
+            .next_back()
+            .filter(|(_, (end, _))| height < end)
+            .map(|(_, (_, secret))| secret)
+    }
+
+    /// Iterate the sorted height ranges with their secrets
+    pub fn iter(&self) -> impl Iterator<Item = (Range<Height>, &SecretKey)> {
+        self.keys
+            .iter()
+            .map(|(start, (end, secret))| (*start..*end, secret))
+    }
+}
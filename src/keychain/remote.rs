@@ -0,0 +1,714 @@
+use crate::{
+    AsyncKeychain, ChainId, Decode, DecodeError, Decoder, Encode, Height, Keychain, Round, Step,
+};
+
+use async_trait::async_trait;
+use fuel_crypto::borrown::Borrown;
+use fuel_crypto::{Keystore, PublicKey, SecretKey, Signature, Signer};
+use fuel_types::Bytes32;
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+use std::time::Duration;
+
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::path::Path;
+
+/// Maximum length of a protocol frame
+const MAX_FRAME_LEN: usize = 1024;
+
+/// Request of the remote signer protocol.
+///
+/// The signer receives the fields of the signed messages instead of their digests, so it can
+/// check what it signs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignerRequest {
+    /// Fetch the public key of the signer for a height.
+    PublicKey {
+        /// Block height
+        height: Height,
+    },
+
+    /// Sign a vote.
+    SignVote {
+        /// Chain of the vote
+        chain_id: ChainId,
+        /// Block height
+        height: Height,
+        /// Height round
+        round: Round,
+        /// Voted block, or `None` for a nil vote
+        block_id: Option<Bytes32>,
+        /// Voted step
+        step: Step,
+    },
+
+    /// Sign a block proposal.
+    SignProposal {
+        /// Chain of the proposal
+        chain_id: ChainId,
+        /// Block height
+        height: Height,
+        /// Height round
+        round: Round,
+        /// Proposed block
+        block_id: Bytes32,
+        /// Round of the proof-of-lock of the block
+        pol_round: Option<Round>,
+        /// Unix timestamp of the proposal (ms)
+        timestamp: u64,
+    },
+}
+
+impl SignerRequest {
+    const PUBLIC_KEY: u8 = 0x00;
+    const SIGN_VOTE: u8 = 0x01;
+    const SIGN_PROPOSAL: u8 = 0x02;
+
+    /// Target block height.
+    pub const fn height(&self) -> Height {
+        match self {
+            Self::PublicKey { height }
+            | Self::SignVote { height, .. }
+            | Self::SignProposal { height, .. } => *height,
+        }
+    }
+}
+
+impl Encode for SignerRequest {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::PublicKey { height } => {
+                Self::PUBLIC_KEY.encode(buf);
+                height.encode(buf);
+            }
+
+            Self::SignVote {
+                chain_id,
+                height,
+                round,
+                block_id,
+                step,
+            } => {
+                Self::SIGN_VOTE.encode(buf);
+                chain_id.encode(buf);
+                height.encode(buf);
+                round.encode(buf);
+                block_id.encode(buf);
+                step.encode(buf);
+            }
+
+            Self::SignProposal {
+                chain_id,
+                height,
+                round,
+                block_id,
+                pol_round,
+                timestamp,
+            } => {
+                Self::SIGN_PROPOSAL.encode(buf);
+                chain_id.encode(buf);
+                height.encode(buf);
+                round.encode(buf);
+                block_id.encode(buf);
+                pol_round.encode(buf);
+                timestamp.encode(buf);
+            }
+        }
+    }
+}
+
+impl Decode for SignerRequest {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let request = match decoder.decode()? {
+            Self::PUBLIC_KEY => Self::PublicKey {
+                height: decoder.decode()?,
+            },
+
+            Self::SIGN_VOTE => Self::SignVote {
+                chain_id: decoder.decode()?,
+                height: decoder.decode()?,
+                round: decoder.decode()?,
+                block_id: decoder.decode()?,
+                step: decoder.decode()?,
+            },
+
+            Self::SIGN_PROPOSAL => Self::SignProposal {
+                chain_id: decoder.decode()?,
+                height: decoder.decode()?,
+                round: decoder.decode()?,
+                block_id: decoder.decode()?,
+                pol_round: decoder.decode()?,
+                timestamp: decoder.decode()?,
+            },
+
+            tag => return Err(DecodeError::InvalidTag { tag }),
+        };
+
+        Ok(request)
+    }
+}
+
+/// Response of the remote signer protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignerResponse {
+    /// Public key of the requested height, or `None` if the signer has no key for it.
+    PublicKey {
+        /// Key of the height
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::serde_impl::option_public_key")
+        )]
+        key: Option<PublicKey>,
+    },
+
+    /// Signature of the requested message.
+    Signature {
+        /// Signature of the message digest
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::signature"))]
+        signature: Signature,
+    },
+
+    /// The signer has no key for the requested height.
+    KeyNotFound,
+
+    /// The signer refused or failed to sign the requested message.
+    Rejected,
+}
+
+impl SignerResponse {
+    const PUBLIC_KEY: u8 = 0x00;
+    const SIGNATURE: u8 = 0x01;
+    const KEY_NOT_FOUND: u8 = 0x02;
+    const REJECTED: u8 = 0x03;
+}
+
+impl Encode for SignerResponse {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::PublicKey { key } => {
+                Self::PUBLIC_KEY.encode(buf);
+                key.encode(buf);
+            }
+
+            Self::Signature { signature } => {
+                Self::SIGNATURE.encode(buf);
+                signature.encode(buf);
+            }
+
+            Self::KeyNotFound => Self::KEY_NOT_FOUND.encode(buf),
+
+            Self::Rejected => Self::REJECTED.encode(buf),
+        }
+    }
+}
+
+impl Decode for SignerResponse {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let response = match decoder.decode()? {
+            Self::PUBLIC_KEY => Self::PublicKey {
+                key: decoder.decode()?,
+            },
+
+            Self::SIGNATURE => Self::Signature {
+                signature: decoder.decode()?,
+            },
+
+            Self::KEY_NOT_FOUND => Self::KeyNotFound,
+
+            Self::REJECTED => Self::Rejected,
+
+            tag => return Err(DecodeError::InvalidTag { tag }),
+        };
+
+        Ok(response)
+    }
+}
+
+/// Keychain that delegates the signatures to a remote signer.
+///
+/// The secret keys never leave the signer; the keychain only holds a connection to it, and every
+/// request is a length-prefixed frame of the versioned encoding of a [`SignerRequest`], answered
+/// with a frame of a [`SignerResponse`]. Only votes and proposals are signed, since an opaque digest can't be checked by the signer.
+///
+/// The keychain is an [`AsyncKeychain`]: the requests run on the blocking pool of the tokio
+/// runtime, so a slow signer doesn't stall the reactor task. The requests must be awaited within
+/// a tokio runtime; otherwise, they fail with an [`io::ErrorKind::Unsupported`] error.
+///
+/// A connection failure, including a signer that doesn't respond within the timeout of the
+/// connection, is reported as [`fuel_crypto::Error::KeystoreNotAvailable`]. The connection is
+/// dropped after a failure, since the stream might hold a partial frame; the next request will
+/// connect again if the keychain was created with a connector, or it can be reconnected
+/// explicitly with [`Self::reconnect`].
+pub struct RemoteKeychain<S = TcpStream> {
+    connection: Arc<Connection<S>>,
+}
+
+/// Connection to the signer, shared with the blocking tasks of the requests.
+struct Connection<S> {
+    stream: Mutex<Option<S>>,
+    connector: Option<Box<dyn Fn() -> io::Result<S> + Send + Sync>>,
+}
+
+impl<S> fmt::Debug for RemoteKeychain<S>
+where
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteKeychain")
+            .field("stream", &self.connection.stream)
+            .field("reconnects", &self.connection.connector.is_some())
+            .finish()
+    }
+}
+
+impl<S> Connection<S>
+where
+    S: Read + Write,
+{
+    fn lock(&self) -> io::Result<MutexGuard<'_, Option<S>>> {
+        self.stream
+            .lock()
+            .map_err(|_| io::Error::other("signer connection poisoned"))
+    }
+
+    fn connect(&self) -> io::Result<S> {
+        let connector = self
+            .connector
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "no signer connector"))?;
+
+        connector()
+    }
+
+    fn request(&self, request: &SignerRequest) -> io::Result<SignerResponse> {
+        let mut guard = self.lock()?;
+
+        if guard.is_none() && self.connector.is_some() {
+            *guard = Some(self.connect()?);
+        }
+
+        let stream = guard.as_mut().ok_or(io::ErrorKind::NotConnected)?;
+
+        let response = write_frame(stream, request)
+            .and_then(|_| read_frame(stream)?.ok_or_else(|| io::ErrorKind::UnexpectedEof.into()));
+
+        if response.is_err() {
+            guard.take();
+        }
+
+        response
+    }
+}
+
+impl<S> RemoteKeychain<S>
+where
+    S: Read + Write + Send + 'static,
+{
+    /// Create a new keychain from a connected stream to the signer.
+    ///
+    /// The keychain can't connect again after a failure, unless a new stream is provided with
+    /// [`Self::reconnect_with`].
+    pub fn new(stream: S) -> Self {
+        Self::from_connection(Some(stream), None)
+    }
+
+    /// Create a new keychain that connects to the signer with the provided function.
+    ///
+    /// The function is used again to replace a failed connection.
+    pub async fn with_connector<F>(connector: F) -> io::Result<Self>
+    where
+        F: Fn() -> io::Result<S> + Send + Sync + 'static,
+    {
+        let keychain = Self::from_connection(None, Some(Box::new(connector)));
+
+        keychain.reconnect().await?;
+
+        Ok(keychain)
+    }
+
+    fn from_connection(
+        stream: Option<S>,
+        connector: Option<Box<dyn Fn() -> io::Result<S> + Send + Sync>>,
+    ) -> Self {
+        Self {
+            connection: Arc::new(Connection {
+                stream: Mutex::new(stream),
+                connector,
+            }),
+        }
+    }
+
+    /// Check if the connection to the signer is still usable.
+    ///
+    /// A connection with a request in flight is reported as usable.
+    pub fn is_connected(&self) -> bool {
+        match self.connection.stream.try_lock() {
+            Ok(stream) => stream.is_some(),
+            Err(TryLockError::WouldBlock) => true,
+            Err(TryLockError::Poisoned(_)) => false,
+        }
+    }
+
+    /// Replace the connection to the signer with a new one from the connector of the keychain.
+    pub async fn reconnect(&self) -> io::Result<()> {
+        self.blocking(|connection| {
+            let stream = connection.connect()?;
+
+            *connection.lock()? = Some(stream);
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Replace the connection to the signer with the provided stream.
+    pub async fn reconnect_with(&self, stream: S) -> io::Result<()> {
+        self.blocking(|connection| {
+            *connection.lock()? = Some(stream);
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Send a request to the signer and wait for its response.
+    ///
+    /// A dropped connection is replaced before the request, if the keychain has a connector.
+    pub async fn request(&self, request: SignerRequest) -> io::Result<SignerResponse> {
+        self.blocking(move |connection| connection.request(&request))
+            .await
+    }
+
+    /// Run an operation on the connection in the blocking pool of the runtime.
+    ///
+    /// Fail if the keychain is used outside of a tokio runtime.
+    async fn blocking<F, T>(&self, f: F) -> io::Result<T>
+    where
+        F: FnOnce(&Connection<S>) -> io::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let runtime = tokio::runtime::Handle::try_current().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                "remote signer used outside of a tokio runtime",
+            )
+        })?;
+
+        let connection = Arc::clone(&self.connection);
+
+        runtime
+            .spawn_blocking(move || f(&connection))
+            .await
+            .unwrap_or_else(|e| Err(io::Error::other(e)))
+    }
+
+    async fn call(&self, request: SignerRequest) -> Result<SignerResponse, fuel_crypto::Error> {
+        self.request(request).await.map_err(|_e| {
+            #[cfg(feature = "trace")]
+            tracing::warn!("remote signer request failed: {}", _e);
+
+            fuel_crypto::Error::KeystoreNotAvailable
+        })
+    }
+
+    async fn sign_request(&self, request: SignerRequest) -> Result<Signature, fuel_crypto::Error> {
+        match self.call(request).await? {
+            SignerResponse::Signature { signature } => Ok(signature),
+            SignerResponse::KeyNotFound => Err(fuel_crypto::Error::KeyNotFound),
+            _ => Err(fuel_crypto::Error::KeystoreNotAvailable),
+        }
+    }
+}
+
+impl RemoteKeychain<TcpStream> {
+    /// Connect to a signer over TCP.
+    ///
+    /// The timeout bounds the connection and every read and write of a request, so a signer that
+    /// hangs can't stall the consensus. The signer is connected again after a failure.
+    pub async fn connect_tcp<A>(addr: A, timeout: Duration) -> io::Result<Self>
+    where
+        A: ToSocketAddrs,
+    {
+        let addrs: Vec<SocketAddr> = addr.to_socket_addrs()?.collect();
+
+        Self::with_connector(move || connect_tcp(&addrs, timeout)).await
+    }
+}
+
+#[cfg(unix)]
+impl RemoteKeychain<UnixStream> {
+    /// Connect to a signer over a Unix domain socket.
+    ///
+    /// The timeout bounds every read and write of a request, so a signer that hangs can't stall
+    /// the consensus. The signer is connected again after a failure.
+    pub async fn connect_unix<P>(path: P, timeout: Duration) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+
+        Self::with_connector(move || {
+            let stream = UnixStream::connect(&path)?;
+
+            stream.set_read_timeout(Some(timeout))?;
+            stream.set_write_timeout(Some(timeout))?;
+
+            Ok(stream)
+        })
+        .await
+    }
+}
+
+/// Connect to the first responsive address, with the timeout applied to the connection and to the
+/// requests.
+fn connect_tcp(addrs: &[SocketAddr], timeout: Duration) -> io::Result<TcpStream> {
+    let mut error = io::Error::new(io::ErrorKind::InvalidInput, "no signer address");
+
+    for addr in addrs {
+        match TcpStream::connect_timeout(addr, timeout) {
+            Ok(stream) => {
+                stream.set_nodelay(true)?;
+                stream.set_read_timeout(Some(timeout))?;
+                stream.set_write_timeout(Some(timeout))?;
+
+                return Ok(stream);
+            }
+
+            Err(e) => error = e,
+        }
+    }
+
+    Err(error)
+}
+
+#[async_trait]
+impl<S> AsyncKeychain for RemoteKeychain<S>
+where
+    S: Read + Write + Send + 'static,
+{
+    type Error = fuel_crypto::Error;
+    type Verifier = RemoteVerifier;
+
+    async fn public_async(&self, height: Height) -> Result<Option<PublicKey>, Self::Error> {
+        match self.call(SignerRequest::PublicKey { height }).await? {
+            SignerResponse::PublicKey { key } => Ok(key),
+            SignerResponse::KeyNotFound => Ok(None),
+            _ => Err(fuel_crypto::Error::KeystoreNotAvailable),
+        }
+    }
+
+    async fn sign_vote_async(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Option<Bytes32>,
+        step: Step,
+    ) -> Result<Signature, Self::Error> {
+        self.sign_request(SignerRequest::SignVote {
+            chain_id,
+            height,
+            round,
+            block_id,
+            step,
+        })
+        .await
+    }
+
+    async fn sign_proposal_async(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+    ) -> Result<Signature, Self::Error> {
+        self.sign_request(SignerRequest::SignProposal {
+            chain_id,
+            height,
+            round,
+            block_id,
+            pol_round,
+            timestamp,
+        })
+        .await
+    }
+}
+
+/// Verifier of the signatures of the peers of a [`RemoteKeychain`].
+///
+/// The verifier holds no keys, so it can't sign any message.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RemoteVerifier;
+
+impl Keystore for RemoteVerifier {
+    type Error = fuel_crypto::Error;
+    type KeyId = Height;
+
+    fn secret(&self, _id: &Height) -> Result<Option<Borrown<'_, SecretKey>>, Self::Error> {
+        Ok(None)
+    }
+
+    fn public(&self, _id: &Height) -> Result<Option<Borrown<'_, PublicKey>>, Self::Error> {
+        Ok(None)
+    }
+}
+
+impl Signer for RemoteVerifier {
+    type Error = fuel_crypto::Error;
+    type Keystore = Self;
+
+    fn keystore(&self) -> Result<&Self::Keystore, Self::Error> {
+        Ok(self)
+    }
+}
+
+impl Keychain for RemoteVerifier {
+    type Error = fuel_crypto::Error;
+    type Signer = Self;
+    type Keystore = Self;
+
+    fn signer(&self) -> &Self::Signer {
+        self
+    }
+}
+
+/// Reference implementation of a remote signer, serving the requests with a local keychain.
+///
+/// The server signs every valid request of its clients: the connections are not authenticated,
+/// and the keychain alone doesn't refuse a conflicting signature. The keychain should be wrapped
+/// into a [`SlashingProtection`](crate::SlashingProtection), and the server should be exposed
+/// only on a loopback interface, a Unix domain socket, or an authenticated channel.
+#[derive(Debug, Clone)]
+pub struct SignerServer<K> {
+    keychain: K,
+}
+
+impl<K> SignerServer<K>
+where
+    K: Keychain,
+{
+    /// Create a new signer with the provided keychain.
+    pub const fn new(keychain: K) -> Self {
+        Self { keychain }
+    }
+
+    /// Underlying keychain of the signer.
+    pub const fn keychain(&self) -> &K {
+        &self.keychain
+    }
+
+    /// Serve the requests of a connection until it is closed by the client.
+    pub fn serve<S>(&self, mut stream: S) -> io::Result<()>
+    where
+        S: Read + Write,
+    {
+        while let Some(request) = read_frame(&mut stream)? {
+            write_frame(&mut stream, &self.handle(&request))?;
+        }
+
+        Ok(())
+    }
+
+    /// Produce the response of a request.
+    pub fn handle(&self, request: &SignerRequest) -> SignerResponse {
+        let height = request.height();
+
+        let key = match self.keychain.public(height) {
+            Ok(Some(key)) => key.into_owned(),
+            Ok(None) => return SignerResponse::KeyNotFound,
+            Err(_) => return SignerResponse::Rejected,
+        };
+
+        let signature = match *request {
+            SignerRequest::PublicKey { .. } => return SignerResponse::PublicKey { key: Some(key) },
+
+            SignerRequest::SignVote {
+                chain_id,
+                height,
+                round,
+                block_id,
+                step,
+            } => self
+                .keychain
+                .sign_vote(chain_id, height, round, block_id.as_ref(), step),
+
+            SignerRequest::SignProposal {
+                chain_id,
+                height,
+                round,
+                block_id,
+                pol_round,
+                timestamp,
+            } => self
+                .keychain
+                .sign_proposal(chain_id, height, round, &block_id, pol_round, timestamp),
+        };
+
+        match signature {
+            Ok(signature) => SignerResponse::Signature { signature },
+
+            Err(_) => {
+                #[cfg(feature = "trace")]
+                tracing::warn!("signer rejected the request {:?}", request);
+
+                SignerResponse::Rejected
+            }
+        }
+    }
+}
+
+/// Write a value as a length-prefixed frame of its versioned encoding.
+fn write_frame<W, T>(writer: &mut W, value: &T) -> io::Result<()>
+where
+    W: Write,
+    T: Encode,
+{
+    let payload = value.to_bytes();
+    let mut frame = Vec::with_capacity(4 + payload.len());
+
+    frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&payload);
+
+    writer.write_all(&frame)?;
+    writer.flush()
+}
+
+/// Read a length-prefixed frame, or `None` if the stream was closed before the frame.
+fn read_frame<R, T>(reader: &mut R) -> io::Result<Option<T>>
+where
+    R: Read,
+    T: Decode,
+{
+    let mut len = [0u8; 4];
+
+    match reader.read_exact(&mut len) {
+        Ok(()) => (),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(invalid_data("frame too long"));
+    }
+
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload)?;
+
+    T::from_bytes(&payload)
+        .map(Some)
+        .map_err(|_| invalid_data("invalid frame"))
+}
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
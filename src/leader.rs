@@ -0,0 +1,399 @@
+use crate::{Decode, DecodeError, Decoder, Encode, Height, Round};
+
+use fuel_crypto::PublicKey;
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// Stake weighted proposer selection, as specified by the Tendermint proposer priority.
+///
+/// Every selection round increments the priority of each validator by its voting power; the
+/// validator with the highest priority is the proposer, and its priority is decreased by the total
+/// voting power. Ties are resolved in favor of the lowest public key.
+///
+/// Over a sequence of rounds with a constant validator set, each validator will propose
+/// proportionally to its voting power. If all validators have the same voting power, the proposers
+/// are selected in round-robin over the sorted public keys.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProposerPriority {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key_map"))]
+    validators: BTreeMap<PublicKey, (u64, i64)>,
+}
+
+impl ProposerPriority {
+    /// Factor of the total voting power that bounds the distance between the lowest and highest
+    /// priorities.
+    pub const PRIORITY_WINDOW_SIZE_FACTOR: i128 = 2;
+
+    /// Replace the validator set with the provided `(key, voting power)` pairs.
+    ///
+    /// The priority of the remaining validators is preserved. The new validators will start with
+    /// a penalty of `1.125` times the total voting power so they cannot propose immediately after
+    /// joining the set. Validators without voting power are ignored.
+    pub fn update<I>(&mut self, validators: I)
+    where
+        I: IntoIterator<Item = (PublicKey, u64)>,
+    {
+        let validators: BTreeMap<PublicKey, u64> = validators
+            .into_iter()
+            .filter(|(_, power)| *power > 0)
+            .collect();
+
+        let total = validators
+            .values()
+            .fold(0u64, |total, power| total.saturating_add(*power));
+        let penalty = Self::clamp(-(total as i128 + (total as i128 >> 3)));
+
+        self.validators
+            .retain(|key, _| validators.contains_key(key));
+
+        validators.into_iter().for_each(|(key, power)| {
+            self.validators
+                .entry(key)
+                .and_modify(|(p, _)| *p = power)
+                .or_insert((power, penalty));
+        });
+    }
+
+    /// Transfer the voting power and priority of a key to its rotated key.
+    ///
+    /// The rotated validator will not be penalized as a new one. Nothing is changed if the new key
+    /// is already part of the set.
+    pub fn rotate(&mut self, old: &PublicKey, new: PublicKey) {
+        if self.validators.contains_key(&new) {
+            return;
+        }
+
+        if let Some(entry) = self.validators.remove(old) {
+            self.validators.insert(new, entry);
+        }
+    }
+
+    /// Perform the given number of selection rounds, returning the last selected proposer.
+    ///
+    /// The priorities are scaled and centered before the rounds are performed, so they remain
+    /// bounded after validator set changes.
+    pub fn increment(&mut self, rounds: u64) -> Option<PublicKey> {
+        if self.validators.is_empty() {
+            return None;
+        }
+
+        self.rescale();
+        self.center();
+
+        let total = self.total_power() as i128;
+
+        (0..rounds).fold(None, |_, _| {
+            self.validators.values_mut().for_each(|(power, priority)| {
+                *priority = Self::clamp(*priority as i128 + *power as i128);
+            });
+
+            // The lowest key wins the tie because `max_by_key` returns the last maximum element
+            let (key, (_, priority)) = self
+                .validators
+                .iter_mut()
+                .rev()
+                .max_by_key(|(_, (_, priority))| *priority)?;
+
+            *priority = Self::clamp(*priority as i128 - total);
+
+            Some(*key)
+        })
+    }
+
+    /// Proposer of the selection round `rounds_ahead` after the next, without mutating the
+    /// priorities.
+    pub fn proposer(&self, rounds_ahead: u64) -> Option<PublicKey> {
+        self.clone().increment(rounds_ahead.saturating_add(1))
+    }
+
+    /// Current priority of a validator, if it is part of the set.
+    pub fn priority(&self, key: &PublicKey) -> Option<i64> {
+        self.validators.get(key).map(|(_, priority)| *priority)
+    }
+
+    /// Sum of the voting power of the validator set.
+    pub fn total_power(&self) -> u64 {
+        self.validators
+            .values()
+            .fold(0u64, |total, (power, _)| total.saturating_add(*power))
+    }
+
+    /// Iterate the sorted keys of the validator set with their voting power and priority.
+    pub fn iter(&self) -> impl Iterator<Item = (&PublicKey, u64, i64)> {
+        self.validators
+            .iter()
+            .map(|(key, (power, priority))| (key, *power, *priority))
+    }
+
+    /// Shrink the priorities if the distance between the lowest and highest exceeds the window.
+    fn rescale(&mut self) {
+        let window = Self::PRIORITY_WINDOW_SIZE_FACTOR * self.total_power() as i128;
+
+        let (min, max) = self
+            .validators
+            .values()
+            .fold((i64::MAX, i64::MIN), |(min, max), (_, priority)| {
+                (min.min(*priority), max.max(*priority))
+            });
+
+        let diff = max as i128 - min as i128;
+        if window == 0 || diff <= window {
+            return;
+        }
+
+        let ratio = (diff + window - 1) / window;
+
+        self.validators
+            .values_mut()
+            .for_each(|(_, priority)| *priority = Self::clamp(*priority as i128 / ratio));
+    }
+
+    /// Shift the priorities so their average is zero.
+    fn center(&mut self) {
+        let sum = self
+            .validators
+            .values()
+            .fold(0i128, |sum, (_, priority)| sum + *priority as i128);
+
+        let average = sum.div_euclid(self.validators.len() as i128);
+
+        self.validators
+            .values_mut()
+            .for_each(|(_, priority)| *priority = Self::clamp(*priority as i128 - average));
+    }
+
+    fn clamp(priority: i128) -> i64 {
+        priority.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+}
+
+impl Encode for ProposerPriority {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let validators: Vec<_> = self
+            .validators
+            .iter()
+            .map(|(key, (power, priority))| (*key, (*power, *priority as u64)))
+            .collect();
+
+        validators.encode(buf);
+    }
+}
+
+impl Decode for ProposerPriority {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let validators: Vec<(PublicKey, (u64, u64))> = decoder.decode()?;
+        let validators = validators
+            .into_iter()
+            .map(|(key, (power, priority))| (key, (power, priority as i64)))
+            .collect();
+
+        Ok(Self { validators })
+    }
+}
+
+/// State of the consensus required to elect the leader of a round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Election<'a> {
+    height: Height,
+    round: Round,
+    committed_rounds: u64,
+    validators: &'a [(PublicKey, u64)],
+    priority: &'a ProposerPriority,
+    missed: &'a [PublicKey],
+}
+
+impl<'a> Election<'a> {
+    /// Create a new election context
+    pub const fn new(
+        height: Height,
+        round: Round,
+        committed_rounds: u64,
+        validators: &'a [(PublicKey, u64)],
+        priority: &'a ProposerPriority,
+    ) -> Self {
+        Self {
+            height,
+            round,
+            committed_rounds,
+            validators,
+            priority,
+            missed: &[],
+        }
+    }
+
+    /// Set the leaders of the rounds that failed to commit in the recent heights.
+    ///
+    /// A leader is repeated for every round it missed.
+    pub const fn with_missed_proposals(mut self, missed: &'a [PublicKey]) -> Self {
+        self.missed = missed;
+        self
+    }
+
+    /// Same election context with a different set of validators.
+    pub const fn with_validators(mut self, validators: &'a [(PublicKey, u64)]) -> Self {
+        self.validators = validators;
+        self
+    }
+
+    /// Target block height.
+    pub const fn height(&self) -> Height {
+        self.height
+    }
+
+    /// Target height round.
+    pub const fn round(&self) -> Round {
+        self.round
+    }
+
+    /// Total rounds performed by the chain before the target height.
+    pub const fn committed_rounds(&self) -> u64 {
+        self.committed_rounds
+    }
+
+    /// Sorted validators of the target height with their voting power.
+    pub const fn validators(&self) -> &[(PublicKey, u64)] {
+        self.validators
+    }
+
+    /// Proposer priorities of the validators after the committed rounds.
+    pub const fn priority(&self) -> &ProposerPriority {
+        self.priority
+    }
+
+    /// Leaders of the rounds that failed to commit in the recent heights.
+    pub const fn missed_proposals(&self) -> &[PublicKey] {
+        self.missed
+    }
+
+    /// Number of rounds the validator failed to commit as leader in the recent heights.
+    pub fn missed_proposals_of(&self, key: &PublicKey) -> usize {
+        self.missed.iter().filter(|k| *k == key).count()
+    }
+
+    /// Leader of the round, weighted by the proposer priority of the validators.
+    pub fn weighted_proposer(&self) -> Option<PublicKey> {
+        let mut priority = self.priority.clone();
+
+        priority.update(self.validators.iter().copied());
+        priority.proposer(self.round)
+    }
+}
+
+/// Strategy to elect the leader of a round.
+///
+/// The election must be deterministic, and all the validators of the network must use the same
+/// strategy; otherwise, the proposals of the leaders will be rejected by their peers.
+pub trait LeaderElection {
+    /// Elect the leader of a round, if there are validators for the height.
+    fn leader(&self, election: &Election<'_>) -> Option<PublicKey>;
+}
+
+/// Round-robin election over the sorted public keys of the validators.
+///
+/// The leader of a round is selected by the total number of rounds performed by the chain,
+/// regardless of the voting power of the validators.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RoundRobin;
+
+impl LeaderElection for RoundRobin {
+    fn leader(&self, election: &Election<'_>) -> Option<PublicKey> {
+        let validators = election.validators();
+
+        if validators.is_empty() {
+            return None;
+        }
+
+        let index =
+            election.committed_rounds().wrapping_add(election.round()) % validators.len() as u64;
+
+        validators.get(index as usize).map(|(key, _)| *key)
+    }
+}
+
+/// Stake weighted round-robin election, driven by the [`ProposerPriority`] of the validators.
+///
+/// Each validator leads rounds proportionally to its voting power. If all validators have the
+/// same voting power, the leaders are selected in round-robin over the sorted public keys. This
+/// is the default strategy of the reactor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WeightedRoundRobin;
+
+impl LeaderElection for WeightedRoundRobin {
+    fn leader(&self, election: &Election<'_>) -> Option<PublicKey> {
+        election.weighted_proposer()
+    }
+}
+
+/// Election that skips the leaders with a poor reputation, delegating the remaining validators to
+/// an underlying strategy.
+///
+/// A leader loses reputation for every round of a recent height that it failed to commit, as
+/// observed by the round advances of the committed certificates. The validators that missed at
+/// least `threshold` proposals are excluded from the election until their failures fall out of the
+/// [`Config::leader_history`](crate::Config::leader_history); if every validator would be
+/// excluded, the full set is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reputation<L = WeightedRoundRobin> {
+    election: L,
+    threshold: usize,
+}
+
+impl<L> Reputation<L> {
+    /// Skip the leaders that missed at least one proposal.
+    pub const DEFAULT_THRESHOLD: usize = 1;
+
+    /// Create a new reputation election over the provided strategy.
+    ///
+    /// A threshold of zero will never skip a leader.
+    pub const fn new(election: L, threshold: usize) -> Self {
+        Self {
+            election,
+            threshold,
+        }
+    }
+
+    /// Underlying election strategy
+    pub const fn election(&self) -> &L {
+        &self.election
+    }
+
+    /// Number of missed proposals that excludes a validator from the election.
+    pub const fn threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+impl<L> Default for Reputation<L>
+where
+    L: Default,
+{
+    fn default() -> Self {
+        Self::new(L::default(), Self::DEFAULT_THRESHOLD)
+    }
+}
+
+impl<L> LeaderElection for Reputation<L>
+where
+    L: LeaderElection,
+{
+    fn leader(&self, election: &Election<'_>) -> Option<PublicKey> {
+        if self.threshold == 0 {
+            return self.election.leader(election);
+        }
+
+        let validators: Vec<(PublicKey, u64)> = election
+            .validators()
+            .iter()
+            .filter(|(key, _)| election.missed_proposals_of(key) < self.threshold)
+            .copied()
+            .collect();
+
+        if validators.is_empty() {
+            return self.election.leader(election);
+        }
+
+        self.election.leader(&election.with_validators(&validators))
+    }
+}
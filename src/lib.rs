@@ -13,6 +13,9 @@ pub type Height = u64;
 /// Round representation.
 pub type Round = u64;
 
+/// Chain identifier, used to separate the signatures of different networks.
+pub type ChainId = u64;
+
 pub(crate) use consensus::Consensus;
 pub(crate) use metadata::Metadata;
 
@@ -25,23 +28,42 @@ pub use fuel_types;
 #[doc(no_inline)]
 pub use time;
 
+mod application;
+mod certificate;
+mod codec;
 mod consensus;
 mod error;
 mod keychain;
+mod leader;
 mod metadata;
 mod moderator;
+mod proposal;
 mod reactor;
 mod stake;
 mod step;
+mod validator_set;
 mod vote;
+mod wal;
 
+pub use application::{Application, ApplicationModerator, BlockValidator};
+pub use certificate::CommitCertificate;
+pub use codec::{encode_len, Decode, DecodeError, Decoder, Encode, CODEC_VERSION};
 pub use error::Error;
-pub use keychain::Keychain;
+pub use keychain::{AsyncKeychain, Keychain};
+pub use leader::{
+    Election, LeaderElection, ProposerPriority, Reputation, RoundRobin, WeightedRoundRobin,
+};
 pub use moderator::Moderator;
+pub use proposal::Proposal;
 pub use reactor::{Config, Event, Message, Notification, Reactor, Request, Response};
 pub use stake::{Stake, ValidatorStakes};
 pub use step::Step;
+pub use validator_set::ValidatorSet;
 pub use vote::Vote;
+pub use wal::{MemoryWal, Wal, WalEntry};
+
+#[cfg(feature = "serde")]
+mod serde_impl;
 
 #[cfg(feature = "tokio-reactor")]
 mod tokio_reactor;
@@ -49,5 +71,19 @@ mod tokio_reactor;
 #[cfg(feature = "tokio-reactor")]
 pub use tokio_reactor::TokioReactor;
 
+#[cfg(feature = "file-keychain")]
+pub use keychain::file::{FileKeychain, KdfParams};
+
 #[cfg(feature = "memory")]
 pub use keychain::memory::MemoryKeychain;
+
+#[cfg(feature = "remote-signer")]
+pub use keychain::remote::{
+    RemoteKeychain, RemoteVerifier, SignerRequest, SignerResponse, SignerServer,
+};
+
+#[cfg(feature = "std")]
+pub use keychain::protection::{LastSigned, SlashingProtection};
+
+#[cfg(feature = "std")]
+pub use wal::FileWal;
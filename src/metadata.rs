@@ -1,9 +1,14 @@
-use crate::{Error, Height, Keychain, Round, Step, Vote};
+use crate::{
+    encode_len, ChainId, CommitCertificate, Consensus, Decoder, Encode, Error, Height, Keychain,
+    Proposal, ProposerPriority, Round, Stake, Step, ValidatorSet, ValidatorStakes, Vote,
+};
 
 use fuel_crypto::PublicKey;
-use fuel_types::Bytes32;
+use fuel_types::{Bytes32, Bytes64};
 
 use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::ops::RangeBounds;
 
 /// Consensus metadata
 #[derive(Debug, Clone)]
@@ -11,36 +16,82 @@ pub struct Metadata {
     committed_height: Height,
     committed_rounds: u64,
 
+    /// Round of the current height reached via timeouts
+    round: Round,
+
     /// Set of authorized blocks for commit
     authorized_blocks: BTreeMap<Bytes32, Height>,
 
     /// Blocks authorized for the propose protocol.
     propose_blocks: BTreeMap<Height, Bytes32>,
 
-    /// key -> (from, to) inclusive height range
-    validators: BTreeMap<PublicKey, (Height, Height)>,
+    /// Voting power of the validators per height range
+    validators: ValidatorStakes,
+
+    /// Proposer priorities after the committed rounds
+    proposer_priority: ProposerPriority,
+
+    /// (height, round, proposer) -> block id of the first signed proposal
+    proposals: BTreeMap<(Height, Round, PublicKey), Bytes32>,
+
+    /// Leaders of the rounds that failed to commit, per recent committed height
+    missed_proposals: BTreeMap<Height, Vec<PublicKey>>,
+
+    /// Hot key rotations that are not yet committed, per initial height
+    rotations: BTreeMap<Height, Vec<(PublicKey, PublicKey)>>,
+
+    /// (height, round, key) -> (step, block id or nil)
+    step: BTreeMap<(Height, Round, PublicKey), (Step, Option<Bytes32>)>,
 
-    /// (height, round, key) -> step
-    step: BTreeMap<(Height, Round, PublicKey), Step>,
+    /// (height, round, step, key) -> first vote received
+    votes: BTreeMap<(Height, Round, Step, PublicKey), Vote>,
+
+    /// Certificates of the most recent committed heights
+    certificates: BTreeMap<Height, CommitCertificate>,
+
+    /// Block the node precommitted for the current height, with the round of the precommit.
+    locked: Option<(Round, Bytes32)>,
+
+    /// Most recent block with a proof-of-lock for the current height, with the round of the
+    /// prevotes.
+    valid: Option<(Round, Bytes32)>,
 }
 
 impl Default for Metadata {
     fn default() -> Self {
         let committed_height = Self::HEIGHT_NEVER;
         let committed_rounds = 0;
+        let round = 0;
 
         let authorized_blocks = Default::default();
         let propose_blocks = Default::default();
         let step = Default::default();
+        let votes = Default::default();
+        let certificates = Default::default();
         let validators = Default::default();
+        let proposer_priority = Default::default();
+        let proposals = Default::default();
+        let missed_proposals = Default::default();
+        let rotations = Default::default();
+        let locked = None;
+        let valid = None;
 
         Self {
             authorized_blocks,
             committed_height,
             committed_rounds,
+            round,
             propose_blocks,
             validators,
+            proposer_priority,
+            proposals,
+            missed_proposals,
+            rotations,
             step,
+            votes,
+            certificates,
+            locked,
+            valid,
         }
     }
 }
@@ -49,21 +100,73 @@ impl Metadata {
     /// Height representing a `never` step
     pub const HEIGHT_NEVER: Height = Height::MAX;
 
+    /// Version of the snapshot encoding
+    pub const SNAPSHOT_VERSION: u8 = 0x01;
+
+    /// Identity of a validator that is registered with its key.
+    pub fn key_identity(key: &PublicKey) -> Bytes64 {
+        Bytes64::from(<[u8; PublicKey::LEN]>::from(*key))
+    }
+
+    /// Add a validator with a unit voting power, replacing the windows of the key from the
+    /// provided height onwards.
     pub fn add_validator(&mut self, validator: PublicKey, height: Height, validity: u64) {
-        let validity = height + validity;
+        let identity = Self::key_identity(&validator);
+        let validity = height.saturating_add(validity);
+        let stake = Stake {
+            key: validator,
+            value: 1,
+        };
 
-        match self.validators.get_mut(&validator) {
-            Some((from, to)) => {
-                *from = height;
-                *to = validity;
-            }
+        self.validators.truncate(&identity, height);
 
-            None => {
-                self.validators.insert(validator, (height, validity));
-            }
+        let _result = self.validators.stake(identity, height..=validity, stake);
+
+        debug_assert!(_result.is_ok());
+    }
+
+    /// Remove a validator key from the provided height onwards.
+    pub fn remove_validator(&mut self, validator: &PublicKey, height: Height) {
+        self.validators.truncate_key(validator, height);
+    }
+
+    /// Replace the hot key of a validator from the provided height onwards.
+    ///
+    /// The rotated validator preserves its proposer priority and reputation.
+    pub fn rotate_key(&mut self, old: &PublicKey, new: PublicKey, height: Height) {
+        self.validators.rotate_key(old, new, height);
+
+        if self.committed_height.wrapping_add(1) <= height {
+            self.rotations.entry(height).or_default().push((*old, new));
         }
     }
 
+    /// Key rotations that are effective at the given height, but not yet committed.
+    fn rotations_at(&self, height: Height) -> impl Iterator<Item = &(PublicKey, PublicKey)> {
+        self.rotations.range(..=height).flat_map(|(_, r)| r.iter())
+    }
+
+    /// Add a stake for a validator identity within the provided height bounds.
+    pub fn stake<B>(&mut self, validator: Bytes64, bounds: B, stake: Stake) -> Result<(), Error>
+    where
+        B: RangeBounds<Height>,
+    {
+        self.validators.stake(validator, bounds, stake)
+    }
+
+    /// Check if a stake can be added for a validator, without changing the stakes.
+    pub fn check_stake<B>(&self, validator: &Bytes64, bounds: B, stake: Stake) -> Result<(), Error>
+    where
+        B: RangeBounds<Height>,
+    {
+        self.validators.check_stake(validator, bounds, stake)
+    }
+
+    /// Stakes that define the voting power of the validators.
+    pub const fn stakes(&self) -> &ValidatorStakes {
+        &self.validators
+    }
+
     /// Authorize the provided block in the given height
     pub fn authorize_block(&mut self, block_id: Bytes32, height: Height) {
         if self.committed_height.wrapping_add(1) <= height {
@@ -92,10 +195,10 @@ impl Metadata {
     }
 
     /// Sorted validators filtered per height.
+    ///
+    /// A key without voting power is not a validator.
     pub fn validators_at_height(&self, height: Height) -> impl Iterator<Item = &PublicKey> {
-        self.validators
-            .iter()
-            .filter_map(move |(k, (from, to))| (*from <= height && height <= *to).then(|| k))
+        self.validators.keys_at_height(height)
     }
 
     /// Validators count per height.
@@ -103,17 +206,122 @@ impl Metadata {
         self.validators_at_height(height).count()
     }
 
-    /// Evaluate the step count for a given round, including the validators that are in subsequent
-    /// steps.
-    pub fn evaluate_step_count(&self, height: Height, round: Round, step: Step) -> usize {
-        let current = self.step_count(height, round, step);
+    /// Validator set of the given height.
+    pub fn validator_set(&self, height: Height) -> ValidatorSet {
+        ValidatorSet::from_stakes(&self.validators, height)
+    }
+
+    /// Sorted validators with their voting power for the given height.
+    pub fn validators_power_at_height(&self, height: Height) -> Vec<(PublicKey, u64)> {
+        self.validator_set(height).as_slice().to_vec()
+    }
+
+    /// Proposer priorities of the validators for the election of the given height, including the
+    /// key rotations that are effective for the height.
+    pub fn proposer_priority_at(&self, height: Height) -> ProposerPriority {
+        let mut priority = self.proposer_priority.clone();
+
+        self.rotations_at(height)
+            .for_each(|(old, new)| priority.rotate(old, *new));
+
+        priority
+    }
+
+    /// Block of the proposal signed by the proposer for a round, if any.
+    pub fn proposal(&self, height: Height, round: Round, proposer: &PublicKey) -> Option<&Bytes32> {
+        self.proposals.get(&(height, round, *proposer))
+    }
+
+    /// Record a signed proposal.
+    ///
+    /// Only the first proposal of the proposer for the round is kept. Return true if the proposal
+    /// was recorded.
+    pub fn record_proposal(&mut self, proposal: &Proposal) -> bool {
+        let key = (proposal.height(), proposal.round(), *proposal.proposer());
+        if self.proposals.contains_key(&key) {
+            return false;
+        }
+
+        self.proposals.insert(key, *proposal.block_id());
+
+        true
+    }
+
+    /// Leaders of the rounds that failed to commit, per recent committed height.
+    pub const fn missed_proposals(&self) -> &BTreeMap<Height, Vec<PublicKey>> {
+        &self.missed_proposals
+    }
+
+    /// Leaders of the rounds that failed to commit in the recent heights, with the rotated keys
+    /// that are effective at the given height.
+    pub fn missed_proposals_at(&self, height: Height) -> Vec<PublicKey> {
+        self.missed_proposals
+            .values()
+            .flatten()
+            .map(|key| {
+                self.rotations_at(height)
+                    .fold(*key, |key, (old, new)| if old == &key { *new } else { key })
+            })
+            .collect()
+    }
+
+    /// Record the leaders of the failed rounds of a height that is about to be committed,
+    /// discarding the heights that are not within the provided history.
+    pub fn record_missed_proposals(
+        &mut self,
+        height: Height,
+        leaders: Vec<PublicKey>,
+        history: u64,
+    ) {
+        if !leaders.is_empty() {
+            self.missed_proposals.insert(height, leaders);
+        }
+
+        self.missed_proposals
+            .retain(|h, _| h.saturating_add(history) > height);
+    }
+
+    /// Total voting power for the given height.
+    pub fn total_power(&self, height: Height) -> u64 {
+        self.validators.total_staked(height)
+    }
+
+    /// Evaluate the voting power of a given round step for any block, including the validators that
+    /// are in subsequent steps.
+    pub fn evaluate_any_step_power(&self, height: Height, round: Round, step: Step) -> u64 {
+        let validators = self.validator_set(height);
+
+        self.step
+            .range((height, round, PublicKey::default())..)
+            .take_while(|((h, r, _), _)| h == &height && r == &round)
+            .filter(|(_, (s, _))| s >= &step)
+            .fold(0u64, |power, ((_, _, key), _)| {
+                power.saturating_add(validators.voting_power(key))
+            })
+    }
 
-        // FIXME optimize
-        let subsequent: usize = step
-            .map(|s| self.step_count(height, round, s))
-            .sum::<usize>();
+    /// Evaluate the voting power of a given round step, including the validators that are in
+    /// subsequent steps.
+    ///
+    /// From the prevote step onwards, only the votes for the provided block are counted. Nil votes
+    /// are tallied separately from the block votes and are evaluated with a `None` block id.
+    pub fn evaluate_step_power(
+        &self,
+        height: Height,
+        round: Round,
+        step: Step,
+        block_id: Option<&Bytes32>,
+    ) -> u64 {
+        let is_block_vote = step >= Step::Prevote;
+        let validators = self.validator_set(height);
 
-        current + subsequent
+        self.step
+            .range((height, round, PublicKey::default())..)
+            .take_while(|((h, r, _), _)| h == &height && r == &round)
+            .filter(|(_, (s, b))| s >= &step && (!is_block_vote || b.as_ref() == block_id))
+            .fold(0u64, |power, ((_, _, key), _)| {
+                power.saturating_add(validators.voting_power(key))
+            })
     }
 
     /// Block height of the last commit
@@ -126,34 +334,178 @@ impl Metadata {
         self.committed_rounds
     }
 
-    pub fn commit(&mut self, height: Height, round: Round) -> bool {
-        // Commit only to the subsequent block
-        if !self.committed_height.wrapping_add(1) == height {
+    /// Round of the current height reached via timeouts
+    pub const fn round(&self) -> Round {
+        self.round
+    }
+
+    /// Move the current height to the provided round, if it is ahead of the current one.
+    ///
+    /// Return true if the round was changed.
+    pub fn advance_round(&mut self, height: Height, round: Round) -> bool {
+        if !self.is_next_round(height, round) {
             return false;
         }
 
+        #[cfg(feature = "trace")]
+        tracing::debug!("advancing round; height: {}, round: {}", height, round);
+
+        self.round = round;
+
+        true
+    }
+
+    /// Check if the provided round is ahead of the current round of the height.
+    pub fn is_next_round(&self, height: Height, round: Round) -> bool {
+        self.committed_height.wrapping_add(1) == height && self.round < round
+    }
+
+    /// Set the committed height with the total number of rounds performed by the chain.
+    pub fn commit_rounds(&mut self, height: Height, rounds: u64) {
+        // The rotated keys inherit the priority and reputation of the validator
+        let rotations: Vec<_> = self.rotations_at(height).copied().collect();
+
+        rotations.iter().for_each(|(old, new)| {
+            self.proposer_priority.rotate(old, *new);
+            self.missed_proposals
+                .values_mut()
+                .flatten()
+                .filter(|key| *key == old)
+                .for_each(|key| *key = *new);
+        });
+
+        self.rotations.retain(|h, _| height < *h);
+
+        // Every performed round selected a proposer
+        let performed = rounds.saturating_sub(self.committed_rounds);
+        if performed > 0 {
+            let validators = self.validators_power_at_height(height);
+
+            self.proposer_priority.update(validators);
+            self.proposer_priority.increment(performed);
+        }
+
         // Remove all expired content
         self.authorized_blocks.retain(|_, h| height < *h);
         self.propose_blocks.retain(|h, _| height < *h);
-        self.validators.retain(|_, &mut (_, to)| height < to);
+        self.validators.prune(height);
         self.step.retain(|(h, _, _), _| height < *h);
+        self.votes.retain(|(h, _, _, _), _| height < *h);
+        self.proposals.retain(|(h, _, _), _| height < *h);
+
+        self.round = 0;
+        self.locked = None;
+        self.valid = None;
 
-        self.committed_rounds += 1 + round;
+        self.committed_rounds = rounds;
         self.committed_height = height;
+    }
+
+    /// Block locked by the node for the current height, with the round it was locked in.
+    pub const fn locked(&self) -> Option<&(Round, Bytes32)> {
+        self.locked.as_ref()
+    }
+
+    /// Most recent block with a proof-of-lock for the current height, with the round of the
+    /// prevotes.
+    pub const fn valid(&self) -> Option<&(Round, Bytes32)> {
+        self.valid.as_ref()
+    }
+
+    /// Lock the node to a block it precommitted in the given round.
+    ///
+    /// A precommit implies a proof-of-lock for the same round, so the block is also valid.
+    pub fn lock(&mut self, height: Height, round: Round, block_id: Bytes32) {
+        if self.committed_height.wrapping_add(1) != height {
+            return;
+        }
+
+        #[cfg(feature = "trace")]
+        tracing::debug!(
+            "locking block; height: {}, round: {}, block: {:08x}",
+            height,
+            round,
+            block_id
+        );
+
+        self.locked.replace((round, block_id));
+        self.observe_pol(height, round, block_id);
+    }
+
+    /// Record a proof-of-lock (2/3+ prevotes) for a block in the given round.
+    ///
+    /// Only the most recent proof-of-lock is retained. Return true if the valid block was changed.
+    pub fn observe_pol(&mut self, height: Height, round: Round, block_id: Bytes32) -> bool {
+        if !self.is_newer_pol(height, round) {
+            return false;
+        }
+
+        self.valid.replace((round, block_id));
 
         true
     }
 
-    /// Step count for a given round
-    pub fn step_count(&self, height: Height, round: Round, step: Step) -> usize {
-        self.step
-            .iter()
-            .filter(|((h, r, _), s)| h == &height && r == &round && s == &&step)
-            .count()
+    /// Check if a proof-of-lock in the given round would replace the valid block.
+    pub fn is_newer_pol(&self, height: Height, round: Round) -> bool {
+        self.committed_height.wrapping_add(1) == height
+            && !matches!(self.valid, Some((r, _)) if r >= round)
+    }
+
+    /// Check if 2/3+ of the voting power prevoted the block in the given round.
+    ///
+    /// The prevotes of other validators are not logged, so the proof-of-lock recorded by the node
+    /// is accepted as well.
+    pub fn is_pol(&self, height: Height, round: Round, block_id: &Bytes32) -> bool {
+        if self.valid == Some((round, *block_id)) {
+            return true;
+        }
+
+        let set = self.validator_set(height);
+
+        let approved = self
+            .votes
+            .range((height, round, Step::Prevote, PublicKey::default())..)
+            .take_while(|((h, r, s, _), _)| h == &height && r == &round && s == &Step::Prevote)
+            .filter(|(_, vote)| vote.block_id() == Some(block_id))
+            .fold(0u64, |power, ((_, _, _, key), _)| {
+                power.saturating_add(set.voting_power(key))
+            });
+
+        Consensus::evaluate(set.len(), set.total_power(), approved).is_consensus()
+    }
+
+    /// Check if the node is allowed to prevote the block in the given round.
+    ///
+    /// A locked node can only prevote its locked block, unless the block is justified by a
+    /// proof-of-lock round `vr` such that `locked_round <= vr < round`, in which 2/3+ of the voting
+    /// power prevoted the block. Nil prevotes are always allowed.
+    pub fn is_prevote_allowed(
+        &self,
+        height: Height,
+        round: Round,
+        block_id: Option<&Bytes32>,
+        pol_round: Option<Round>,
+    ) -> bool {
+        let block_id = match block_id {
+            Some(b) if self.committed_height.wrapping_add(1) == height => b,
+            _ => return true,
+        };
+
+        match (self.locked, pol_round) {
+            (None, _) => true,
+            (Some((_, locked)), _) if &locked == block_id => true,
+            (Some((locked_round, _)), Some(pol_round)) => {
+                locked_round <= pol_round
+                    && pol_round < round
+                    && self.is_pol(height, pol_round, block_id)
+            }
+            _ => false,
+        }
     }
 
-    /// Validate a vote, checking if the author is a validator of the round, and if the signature is valid.
-    pub fn validate<K>(&self, vote: &Vote) -> Result<(), Error>
+    /// Validate a vote, checking if the author is a validator of the round, and if the signature is
+    /// valid for the chain.
+    pub fn validate<K>(&self, chain_id: ChainId, vote: &Vote) -> Result<(), Error>
     where
         K: Keychain,
     {
@@ -165,25 +517,115 @@ impl Metadata {
             return Err(Error::ValidatorNotFound);
         }
 
-        vote.validate::<K>().map_err(|_| Error::InvalidSignature)?;
+        vote.validate::<K>(chain_id)
+            .map_err(|_| Error::InvalidSignature)?;
 
         Ok(())
     }
 
     /// Fetch the current step of a validator for a given round
     pub fn validator_step(&self, height: Height, round: Round, key: &PublicKey) -> Option<Step> {
-        self.step.get(&(height, round, *key)).copied()
+        self.step.get(&(height, round, *key)).map(|(s, _)| *s)
+    }
+
+    /// Record the first vote of a validator for a given round step.
+    ///
+    /// If a vote for a different block was already recorded for the same validator, height, round
+    /// and step, the first vote is returned as evidence of equivocation.
+    pub fn record_vote(&mut self, vote: &Vote) -> Option<Vote> {
+        let key = (vote.height(), vote.round(), vote.step(), *vote.validator());
+
+        match self.votes.get(&key) {
+            Some(first) if first.block_id() != vote.block_id() => Some(*first),
+            Some(_) => None,
+            None => {
+                self.votes.insert(key, *vote);
+
+                None
+            }
+        }
+    }
+
+    /// Compose a commit certificate from the precommit and commit votes recorded for the block.
+    ///
+    /// Only one vote per validator is included, favoring the precommit.
+    pub fn certify(&self, height: Height, round: Round, block_id: Bytes32) -> CommitCertificate {
+        let mut votes = BTreeMap::new();
+
+        self.votes
+            .range((height, round, Step::Precommit, PublicKey::default())..)
+            .take_while(|((h, r, _, _), _)| h == &height && r == &round)
+            .filter(|(_, vote)| vote.block_id() == Some(&block_id))
+            .for_each(|((_, _, _, key), vote)| {
+                votes.entry(*key).or_insert(*vote);
+            });
+
+        let validator_set = self.validator_set(height).hash();
+
+        CommitCertificate::new(height, round, block_id, validator_set, votes.into_values())
+    }
+
+    /// Check if the votes of a certificate achieve consensus for the validator set of its height.
+    ///
+    /// The signatures are not verified, so only certificates composed by the node or verified
+    /// with [`CommitCertificate::verify`] should be checked.
+    pub fn is_certified(&self, certificate: &CommitCertificate) -> bool {
+        let set = self.validator_set(certificate.height());
+
+        let approved = certificate.votes().iter().fold(0u64, |power, vote| {
+            power.saturating_add(set.voting_power(vote.validator()))
+        });
+
+        Consensus::evaluate(set.len(), set.total_power(), approved).is_consensus()
+    }
+
+    /// Retain the certificate of a committed height, discarding the oldest ones that exceed the
+    /// provided capacity.
+    pub fn store_certificate(&mut self, certificate: CommitCertificate, capacity: usize) {
+        self.certificates.insert(certificate.height(), certificate);
+
+        while self.certificates.len() > capacity {
+            self.certificates.pop_first();
+        }
+    }
+
+    /// Certificate of a committed height, if retained.
+    pub fn certificate(&self, height: Height) -> Option<&CommitCertificate> {
+        self.certificates.get(&height)
     }
 
     /// Upgrade a validator step, returning true if there was a change
     pub fn upgrade_validator_step(&mut self, vote: &Vote) -> bool {
-        let height = vote.height();
-        let round = vote.round();
-        let validator = *vote.validator();
-        let step = vote.step();
+        self.upgrade_step(
+            vote.height(),
+            vote.round(),
+            *vote.validator(),
+            vote.step(),
+            vote.block_id().copied(),
+        )
+    }
+
+    /// Upgrade the step of a proposer to [`Step::Propose`], returning true if there was a change
+    pub fn upgrade_proposer_step(&mut self, proposal: &Proposal) -> bool {
+        self.upgrade_step(
+            proposal.height(),
+            proposal.round(),
+            *proposal.proposer(),
+            Step::Propose,
+            Some(*proposal.block_id()),
+        )
+    }
 
+    fn upgrade_step(
+        &mut self,
+        height: Height,
+        round: Round,
+        validator: PublicKey,
+        step: Step,
+        block_id: Option<Bytes32>,
+    ) -> bool {
         let updated = match self.step.get_mut(&(height, round, validator)) {
-            Some(s) if &step > s => {
+            Some((s, b)) if &step > s => {
                 #[cfg(feature = "trace")]
                 tracing::debug!(
                     "upgrading step; validator: {:08x}, height: {}, round: {}, step: {:?}",
@@ -194,6 +636,7 @@ impl Metadata {
                 );
 
                 *s = step;
+                *b = block_id;
                 true
             }
 
@@ -207,7 +650,8 @@ impl Metadata {
                     step
                 );
 
-                self.step.insert((height, round, validator), step);
+                self.step
+                    .insert((height, round, validator), (step, block_id));
                 true
             }
 
@@ -227,4 +671,178 @@ impl Metadata {
 
         updated
     }
+
+    /// Deterministic binary representation of the metadata, prefixed by the encoding version.
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        Self::SNAPSHOT_VERSION.encode(&mut buf);
+        self.committed_height.encode(&mut buf);
+        self.committed_rounds.encode(&mut buf);
+        self.round.encode(&mut buf);
+
+        // The stakes are stored in hash maps, so they must be sorted
+        let mut stakes: Vec<_> = self.validators.iter().collect();
+        stakes.sort_by_key(|(validator, range, _)| (*validator, range.start));
+
+        encode_len(stakes.len(), &mut buf);
+        stakes.into_iter().for_each(|(validator, range, stake)| {
+            validator.encode(&mut buf);
+            range.start.encode(&mut buf);
+            range.end.encode(&mut buf);
+            stake.encode(&mut buf);
+        });
+
+        self.proposer_priority.encode(&mut buf);
+
+        encode_len(self.proposals.len(), &mut buf);
+        self.proposals
+            .iter()
+            .for_each(|((height, round, proposer), block_id)| {
+                height.encode(&mut buf);
+                round.encode(&mut buf);
+                proposer.encode(&mut buf);
+                block_id.encode(&mut buf);
+            });
+
+        encode_len(self.missed_proposals.len(), &mut buf);
+        self.missed_proposals
+            .iter()
+            .for_each(|entry| entry.encode(&mut buf));
+
+        encode_len(self.rotations.len(), &mut buf);
+        self.rotations
+            .iter()
+            .for_each(|entry| entry.encode(&mut buf));
+
+        encode_len(self.authorized_blocks.len(), &mut buf);
+        self.authorized_blocks
+            .iter()
+            .for_each(|entry| entry.encode(&mut buf));
+
+        encode_len(self.propose_blocks.len(), &mut buf);
+        self.propose_blocks
+            .iter()
+            .for_each(|entry| entry.encode(&mut buf));
+
+        encode_len(self.step.len(), &mut buf);
+        self.step
+            .iter()
+            .for_each(|((height, round, validator), (step, block_id))| {
+                height.encode(&mut buf);
+                round.encode(&mut buf);
+                validator.encode(&mut buf);
+                step.encode(&mut buf);
+                block_id.encode(&mut buf);
+            });
+
+        encode_len(self.votes.len(), &mut buf);
+        self.votes.values().for_each(|vote| vote.encode(&mut buf));
+
+        encode_len(self.certificates.len(), &mut buf);
+        self.certificates
+            .values()
+            .for_each(|certificate| certificate.encode(&mut buf));
+
+        self.locked.encode(&mut buf);
+        self.valid.encode(&mut buf);
+
+        buf
+    }
+
+    /// Restore the metadata from its snapshot representation.
+    pub fn restore(bytes: &[u8]) -> Result<Self, Error> {
+        let mut d = Decoder::new(bytes);
+
+        let version = d.decode()?;
+        if version != Self::SNAPSHOT_VERSION {
+            return Err(Error::UnsupportedVersion { version });
+        }
+
+        let mut metadata = Self {
+            committed_height: d.decode()?,
+            committed_rounds: d.decode()?,
+            round: d.decode()?,
+            ..Default::default()
+        };
+
+        for _ in 0..d.decode_len()? {
+            let validator = d.decode()?;
+            let start: Height = d.decode()?;
+            let end: Height = d.decode()?;
+            let stake = d.decode()?;
+
+            metadata.validators.stake(validator, start..end, stake)?;
+        }
+
+        metadata.proposer_priority = d.decode()?;
+        for _ in 0..d.decode_len()? {
+            let height = d.decode()?;
+            let round = d.decode()?;
+            let proposer = d.decode()?;
+            let block_id = d.decode()?;
+
+            metadata
+                .proposals
+                .insert((height, round, proposer), block_id);
+        }
+
+        for _ in 0..d.decode_len()? {
+            let (height, leaders) = d.decode()?;
+
+            metadata.missed_proposals.insert(height, leaders);
+        }
+
+        for _ in 0..d.decode_len()? {
+            let (height, rotations) = d.decode()?;
+
+            metadata.rotations.insert(height, rotations);
+        }
+
+        for _ in 0..d.decode_len()? {
+            let (block_id, height) = d.decode()?;
+
+            metadata.authorized_blocks.insert(block_id, height);
+        }
+
+        for _ in 0..d.decode_len()? {
+            let (height, block_id) = d.decode()?;
+
+            metadata.propose_blocks.insert(height, block_id);
+        }
+
+        for _ in 0..d.decode_len()? {
+            let height = d.decode()?;
+            let round = d.decode()?;
+            let validator = d.decode()?;
+            let step = d.decode()?;
+            let block_id = d.decode()?;
+
+            metadata
+                .step
+                .insert((height, round, validator), (step, block_id));
+        }
+
+        for _ in 0..d.decode_len()? {
+            let vote: Vote = d.decode()?;
+            let key = (vote.height(), vote.round(), vote.step(), *vote.validator());
+
+            metadata.votes.insert(key, vote);
+        }
+
+        for _ in 0..d.decode_len()? {
+            let certificate: CommitCertificate = d.decode()?;
+
+            metadata
+                .certificates
+                .insert(certificate.height(), certificate);
+        }
+
+        metadata.locked = d.decode()?;
+        metadata.valid = d.decode()?;
+
+        d.finish()?;
+
+        Ok(metadata)
+    }
 }
@@ -1,4 +1,4 @@
-use crate::Message;
+use crate::{BlockValidator, Message};
 
 use async_trait::async_trait;
 use time::OffsetDateTime;
@@ -23,6 +23,14 @@ pub trait Moderator: Send + Sync {
         OffsetDateTime::now_utc()
     }
 
+    /// Block validation hook of the application.
+    ///
+    /// If `None`, the proposed blocks are expected to be authorized via
+    /// [`Notification::BlockAuthorized`](crate::Notification::BlockAuthorized).
+    fn block_validator(&mut self) -> Option<&mut dyn BlockValidator> {
+        None
+    }
+
     /// Messages consumed by the reactor
     async fn inbound(&mut self) -> Result<Option<Message>, Self::Error>;
 
@@ -36,14 +44,21 @@ pub trait Moderator: Send + Sync {
     async fn rebound(&mut self, message: Message, timeout: Duration) -> Result<(), Self::Error>;
 
     /// Send a message from the reactor.
-    async fn send(&mut self, message: Message, timeout: Duration) {
+    ///
+    /// The failure of the outbound dispatch is returned, so the reactor can report the errors of
+    /// the application that consumes its events.
+    async fn send(&mut self, message: Message, timeout: Duration) -> Result<(), Self::Error> {
         #[cfg(feature = "trace")]
         tracing::debug!("sending message {:?}", message);
 
-        if let Err(_e) = self.outbound(message, timeout).await {
-            #[cfg(feature = "trace")]
-            tracing::error!("error sending outbound message: {}", _e);
+        let sent = self.outbound(message, timeout).await;
+
+        #[cfg(feature = "trace")]
+        if let Err(e) = &sent {
+            tracing::error!("error sending outbound message: {}", e);
         }
+
+        sent
     }
 
     /// Requeue a message that cannot be consumed by the reactor.
@@ -0,0 +1,270 @@
+use crate::{
+    AsyncKeychain, ChainId, Decode, DecodeError, Decoder, Encode, Error, Height, Keychain, Round,
+};
+
+use fuel_crypto::{Hasher, PublicKey, SecretKey, Signature};
+use fuel_types::Bytes32;
+
+use alloc::vec::Vec;
+
+/// A block proposal from the leader of a round.
+///
+/// A block that was locked in a previous round is proposed again with the round of its
+/// proof-of-lock, so the locked validators can prevote it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Proposal {
+    block_id: Bytes32,
+    height: Height,
+    pol_round: Option<Round>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
+    proposer: PublicKey,
+    round: Round,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::signature"))]
+    signature: Signature,
+    timestamp: u64,
+}
+
+impl Proposal {
+    /// Domain separation tag of the proposal digest, including the version of the signed encoding.
+    ///
+    /// Guarantees a proposal signature cannot be replayed as a signature of any other message.
+    pub const DOMAIN_TAG: &'static [u8] = b"fuel-bft/proposal/v1";
+
+    /// Create a new proposal from a given signature
+    pub const fn new(
+        proposer: PublicKey,
+        signature: Signature,
+        height: Height,
+        round: Round,
+        block_id: Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+    ) -> Self {
+        Self {
+            block_id,
+            height,
+            pol_round,
+            proposer,
+            round,
+            signature,
+            timestamp,
+        }
+    }
+
+    /// Canonical encoding of the signed fields of a proposal.
+    fn encode_body(
+        height: Height,
+        round: Round,
+        block_id: &Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+        buf: &mut Vec<u8>,
+    ) {
+        height.encode(buf);
+        round.encode(buf);
+        block_id.encode(buf);
+        pol_round.encode(buf);
+        timestamp.encode(buf);
+    }
+
+    pub(crate) fn _digest(
+        h: Hasher,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: &Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+    ) -> Hasher {
+        let mut body = Vec::new();
+
+        Self::encode_body(height, round, block_id, pol_round, timestamp, &mut body);
+
+        h.chain(Self::DOMAIN_TAG)
+            .chain(chain_id.to_be_bytes())
+            .chain(body)
+    }
+
+    /// Compute the digest of the proposal for a given chain. Will be used by the signature
+    pub fn digest(&self, chain_id: ChainId, h: Hasher) -> Hasher {
+        Self::_digest(
+            h,
+            chain_id,
+            self.height,
+            self.round,
+            &self.block_id,
+            self.pol_round,
+            self.timestamp,
+        )
+    }
+
+    /// Proposed block
+    pub const fn block_id(&self) -> &Bytes32 {
+        &self.block_id
+    }
+
+    /// Target block height.
+    pub const fn height(&self) -> Height {
+        self.height
+    }
+
+    /// Round of the proof-of-lock of the block, if it was locked in a previous round.
+    pub const fn pol_round(&self) -> Option<Round> {
+        self.pol_round
+    }
+
+    /// Network identification of the author
+    pub const fn proposer(&self) -> &PublicKey {
+        &self.proposer
+    }
+
+    /// Target height round.
+    pub const fn round(&self) -> Round {
+        self.round
+    }
+
+    /// Signature provided by the proposer
+    pub const fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// Unix timestamp of the proposal (ms)
+    pub const fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Produce a guaranteed correctness signed proposal
+    pub fn signed<K>(
+        keychain: &K,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+    ) -> Result<Self, Error>
+    where
+        K: Keychain,
+    {
+        let signature = keychain
+            .sign_proposal(chain_id, height, round, &block_id, pol_round, timestamp)
+            .map_err(Into::into)?;
+
+        let proposer = keychain
+            .public(height)
+            .map_err(|_| Error::ResourceNotAvailable)?
+            .ok_or(Error::NotRoundValidator)?
+            .into_owned();
+
+        let proposal = Self::new(
+            proposer, signature, height, round, block_id, pol_round, timestamp,
+        );
+
+        Ok(proposal)
+    }
+
+    /// Produce a guaranteed correctness signed proposal with an asynchronous keychain
+    pub async fn signed_async<K>(
+        keychain: &K,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+    ) -> Result<Self, Error>
+    where
+        K: AsyncKeychain,
+    {
+        let signature = keychain
+            .sign_proposal_async(chain_id, height, round, block_id, pol_round, timestamp)
+            .await
+            .map_err(Into::into)?;
+
+        let proposer = keychain
+            .public_async(height)
+            .await
+            .map_err(|_| Error::ResourceNotAvailable)?
+            .ok_or(Error::NotRoundValidator)?;
+
+        let proposal = Self::new(
+            proposer, signature, height, round, block_id, pol_round, timestamp,
+        );
+
+        Ok(proposal)
+    }
+
+    /// Produce a guaranteed correctness signed proposal
+    pub fn signed_with_key<K>(
+        secret: &SecretKey,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+    ) -> Self
+    where
+        K: Keychain,
+    {
+        let digest = Self::_digest(
+            Hasher::default(),
+            chain_id,
+            height,
+            round,
+            &block_id,
+            pol_round,
+            timestamp,
+        );
+
+        let proposer = K::public_with_key(secret);
+        let signature = K::sign_with_key(secret, digest);
+
+        Self::new(
+            proposer, signature, height, round, block_id, pol_round, timestamp,
+        )
+    }
+
+    /// Validate the signature of the proposal for a given chain
+    pub fn validate<K>(&self, chain_id: ChainId) -> Result<(), Error>
+    where
+        K: Keychain,
+    {
+        let digest = self.digest(chain_id, Hasher::default());
+
+        K::verify(self.signature, &self.proposer, digest).map_err(|_| Error::InvalidSignature)
+    }
+}
+
+impl Encode for Proposal {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        Self::encode_body(
+            self.height,
+            self.round,
+            &self.block_id,
+            self.pol_round,
+            self.timestamp,
+            buf,
+        );
+
+        self.proposer.encode(buf);
+        self.signature.encode(buf);
+    }
+}
+
+impl Decode for Proposal {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let height = decoder.decode()?;
+        let round = decoder.decode()?;
+        let block_id = decoder.decode()?;
+        let pol_round = decoder.decode()?;
+        let timestamp = decoder.decode()?;
+        let proposer = decoder.decode()?;
+        let signature = decoder.decode()?;
+
+        Ok(Self::new(
+            proposer, signature, height, round, block_id, pol_round, timestamp,
+        ))
+    }
+}
@@ -1,10 +1,16 @@
-use crate::{Consensus, Error, Height, Keychain, Metadata, Moderator, Round, Step, Vote};
+use crate::{
+    AsyncKeychain, CommitCertificate, Consensus, Election, Error, Height, Keychain, LeaderElection,
+    MemoryWal, Metadata, Moderator, Proposal, Round, Stake, Step, ValidatorSet, ValidatorStakes,
+    Vote, Wal, WalEntry, WeightedRoundRobin,
+};
 
 use fuel_crypto::PublicKey;
-use fuel_types::Bytes32;
+use fuel_types::{Bytes32, Bytes64};
 use time::OffsetDateTime;
 
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
+use core::cmp;
 use core::time::Duration;
 
 mod config;
@@ -15,24 +21,66 @@ pub use message::{Event, Message, Notification, Request, Response};
 
 /// State machine of the consensus
 #[derive(Debug)]
-pub struct Reactor {
+pub struct Reactor<W = MemoryWal, L = WeightedRoundRobin> {
     capacity: usize,
+    config: Config,
     consensus: u128,
+    election: L,
     genesis: OffsetDateTime,
     metadata: Metadata,
     timeout: Duration,
     should_quit: bool,
-}
+    wal: W,
 
-impl Default for Reactor {
-    fn default() -> Self {
-        Self::new(Default::default())
-    }
+    /// (height, round, step) -> step timeout deadline; `None` if already fired
+    deadlines: BTreeMap<(Height, Round, Step), Option<OffsetDateTime>>,
+
+    /// Highest network height announced via [`Event::SyncRequired`]
+    sync_target: Height,
+
+    /// Lowest effective height of the logged validator set updates not captured by a snapshot
+    pending_snapshot: Option<Height>,
+
+    /// Certificate of the last committed height without a quorum, and the validator set of the
+    /// height; completed by the precommits received after the commit
+    uncertified: Option<(CommitCertificate, ValidatorSet)>,
 }
 
 impl Reactor {
     /// Create a new reactor with the provided arguments
+    ///
+    /// The state transitions are logged in memory.
     pub fn new(config: Config) -> Self {
+        Self::with_wal(config, MemoryWal::default())
+    }
+
+    /// Restore a reactor from a snapshot produced by [`Reactor::snapshot`].
+    ///
+    /// The state transitions are logged in memory.
+    pub fn restore(config: Config, snapshot: &[u8]) -> Result<Self, Error> {
+        Self::restore_with_wal(config, MemoryWal::default(), snapshot)
+    }
+}
+
+impl<W, L> Reactor<W, L>
+where
+    W: Wal,
+    L: LeaderElection,
+{
+    /// Create a new reactor that logs its state transitions to the provided write-ahead log.
+    ///
+    /// The log is expected to be empty; use [`Reactor::recover`] to restore the state of a log.
+    pub fn with_wal(config: Config, wal: W) -> Self
+    where
+        L: Default,
+    {
+        Self::with_election(config, wal, L::default())
+    }
+
+    /// Create a new reactor that elects the round leaders with the provided strategy.
+    ///
+    /// The log is expected to be empty; use [`Reactor::recover`] to restore the state of a log.
+    pub fn with_election(config: Config, wal: W, election: L) -> Self {
         let Config {
             capacity,
             consensus,
@@ -43,15 +91,245 @@ impl Reactor {
 
         let metadata = Default::default();
         let should_quit = false;
+        let deadlines = Default::default();
+        let sync_target = 0;
+        let pending_snapshot = None;
+        let uncertified = None;
 
         Self {
             capacity,
+            config,
             consensus,
+            election,
             genesis,
             metadata,
             timeout,
             should_quit,
+            wal,
+            deadlines,
+            sync_target,
+            pending_snapshot,
+            uncertified,
+        }
+    }
+
+    /// Restore a reactor from the entries of a write-ahead log.
+    ///
+    /// The votes signed by the node before the restart are restored, so it will not sign
+    /// conflicting votes for the same height, round and step.
+    pub fn recover(config: Config, wal: W) -> Result<Self, Error>
+    where
+        L: Default,
+    {
+        Self::recover_with_election(config, wal, L::default())
+    }
+
+    /// Restore a reactor that elects the round leaders with the provided strategy from the entries
+    /// of a write-ahead log.
+    ///
+    /// The strategy must be the one used before the restart; otherwise, the replayed commits
+    /// might produce a different leader schedule.
+    pub fn recover_with_election(config: Config, wal: W, election: L) -> Result<Self, Error> {
+        let entries = wal.entries().map_err(|_e| {
+            #[cfg(feature = "trace")]
+            tracing::error!("error reading the write-ahead log: {}", _e);
+
+            Error::WalFailure
+        })?;
+
+        let mut reactor = Self::with_election(config, wal, election);
+
+        for entry in entries {
+            reactor.replay(entry)?;
+        }
+
+        Ok(reactor)
+    }
+
+    /// Restore a reactor from a snapshot, logging the subsequent state transitions to the provided
+    /// write-ahead log.
+    pub fn restore_with_wal(config: Config, wal: W, snapshot: &[u8]) -> Result<Self, Error>
+    where
+        L: Default,
+    {
+        Self::restore_with_election(config, wal, L::default(), snapshot)
+    }
+
+    /// Restore a reactor that elects the round leaders with the provided strategy from a
+    /// snapshot, logging the subsequent state transitions to the provided write-ahead log.
+    pub fn restore_with_election(
+        config: Config,
+        wal: W,
+        election: L,
+        snapshot: &[u8],
+    ) -> Result<Self, Error> {
+        let mut reactor = Self::with_election(config, wal, election);
+
+        reactor.metadata = Metadata::restore(snapshot)?;
+
+        Ok(reactor)
+    }
+
+    /// Versioned and deterministic binary representation of the consensus state.
+    ///
+    /// The snapshot contains the committed height and rounds, the validator stakes, the proposer
+    /// priorities of the leader election, the authorized blocks, the validator steps
+    /// and votes of the pending heights, the retained commit certificates and the lock of the
+    /// node. Pending step timeouts are not included.
+    pub fn snapshot(&self) -> Vec<u8> {
+        self.metadata.snapshot()
+    }
+
+    /// Underlying write-ahead log.
+    pub const fn wal(&self) -> &W {
+        &self.wal
+    }
+
+    /// Apply a logged state transition without logging it again.
+    fn replay(&mut self, entry: WalEntry) -> Result<(), Error> {
+        match entry {
+            WalEntry::Validator {
+                height,
+                validity,
+                validator,
+            } => self.metadata.add_validator(validator, height, validity),
+
+            WalEntry::RemoveValidator { height, validator } => {
+                self.metadata.remove_validator(&validator, height);
+                self.schedule_snapshot(height);
+            }
+
+            WalEntry::RotateKey {
+                old,
+                new,
+                from_height,
+            } => {
+                self.metadata.rotate_key(&old, new, from_height);
+                self.schedule_snapshot(from_height);
+            }
+
+            WalEntry::Snapshot { snapshot } => {
+                self.metadata = Metadata::restore(&snapshot)?;
+                self.pending_snapshot = None;
+            }
+
+            WalEntry::Stake {
+                height,
+                validity,
+                validator,
+                stake,
+            } => {
+                let validity = height.saturating_add(validity);
+
+                self.metadata.stake(validator, height..=validity, stake)?;
+            }
+
+            WalEntry::BlockAuthorized { height, block_id } => {
+                self.metadata.authorize_block(block_id, height);
+            }
+
+            WalEntry::BlockProposeAuthorized { height, block_id } => {
+                self.metadata.authorize_block_propose(height, block_id);
+            }
+
+            WalEntry::Vote { vote } => {
+                self.metadata.upgrade_validator_step(&vote);
+                self.metadata.record_vote(&vote);
+
+                if let Some(block_id) = vote.block_id().filter(|_| vote.step().is_precommit()) {
+                    self.metadata.lock(vote.height(), vote.round(), *block_id);
+                }
+            }
+
+            WalEntry::Proposal { proposal } => {
+                self.metadata.record_proposal(&proposal);
+                self.metadata.upgrade_proposer_step(&proposal);
+            }
+
+            WalEntry::ProofOfLock {
+                height,
+                round,
+                block_id,
+            } => {
+                self.metadata.observe_pol(height, round, block_id);
+            }
+
+            WalEntry::Round { height, round } => {
+                self.metadata.advance_round(height, round);
+            }
+
+            WalEntry::Commit {
+                rounds,
+                certificate,
+            } => {
+                let is_certified = self.metadata.is_certified(&certificate);
+
+                self.apply_commit(&certificate, rounds);
+
+                if is_certified {
+                    self.metadata
+                        .store_certificate(certificate, self.config.certificates);
+                }
+            }
+
+            WalEntry::Certificate { certificate } => {
+                self.uncertified = None;
+                self.metadata
+                    .store_certificate(certificate, self.config.certificates);
+            }
         }
+
+        Ok(())
+    }
+
+    /// Persist a state transition in the write-ahead log.
+    fn log(&mut self, entry: WalEntry) -> Result<(), Error> {
+        self.wal.append(&entry).map_err(|_e| {
+            #[cfg(feature = "trace")]
+            tracing::error!("error appending to the write-ahead log: {}", _e);
+
+            Error::WalFailure
+        })
+    }
+
+    /// Send an event consumed by the application, returning the failure of its dispatch.
+    async fn dispatch<M>(&self, moderator: &mut M, message: Message) -> Result<(), Error>
+    where
+        M: Moderator,
+    {
+        moderator
+            .send(message, self.timeout)
+            .await
+            .map_err(|_| Error::ModeratorFailure)
+    }
+
+    /// Capture the consensus state in the log once the given height is committed, so the
+    /// validator set updates effective from it can be pruned.
+    fn schedule_snapshot(&mut self, height: Height) {
+        let height = self
+            .pending_snapshot
+            .map_or(height, |pending| cmp::min(pending, height));
+
+        self.pending_snapshot = Some(height);
+    }
+
+    /// Authorize a block for commit in the given height, once the authorization is logged.
+    fn authorize_block(&mut self, height: Height, block_id: Bytes32) -> Result<(), Error> {
+        self.log(WalEntry::BlockAuthorized { height, block_id })?;
+
+        self.metadata.authorize_block(block_id, height);
+
+        Ok(())
+    }
+
+    /// Authorize a block for the propose protocol of the given height, once the authorization is
+    /// logged.
+    fn authorize_block_propose(&mut self, height: Height, block_id: Bytes32) -> Result<(), Error> {
+        self.log(WalEntry::BlockProposeAuthorized { height, block_id })?;
+
+        self.metadata.authorize_block_propose(height, block_id);
+
+        Ok(())
     }
 
     /// Flag on whether should quit
@@ -65,6 +343,9 @@ impl Reactor {
     }
 
     /// Current height round
+    ///
+    /// The round is derived from the elapsed time since genesis, and can be moved further by the
+    /// precommit timeouts.
     pub fn round(&self, now: OffsetDateTime) -> Round {
         let elapsed = now - self.genesis;
         let elapsed = elapsed.whole_milliseconds() as u128;
@@ -75,7 +356,7 @@ impl Reactor {
         let remainder_ms = elapsed.saturating_sub(committed_ms);
         let round = remainder_ms / self.consensus;
 
-        round as Round
+        cmp::max(round as Round, self.metadata.round())
     }
 
     /// Evaluate the consensus step of a validator for a given round
@@ -83,49 +364,317 @@ impl Reactor {
         self.metadata.validator_step(height, round, public)
     }
 
+    /// Block locked by the node for the current height, with the round of its precommit.
+    ///
+    /// While locked, the node will prevote only the locked block unless it observes a newer
+    /// proof-of-lock for a different block.
+    pub fn locked(&self) -> Option<(Round, &Bytes32)> {
+        self.metadata.locked().map(|(r, b)| (*r, b))
+    }
+
+    /// Most recent block with a proof-of-lock for the current height, with the round of the
+    /// prevotes.
+    pub fn valid(&self) -> Option<(Round, &Bytes32)> {
+        self.metadata.valid().map(|(r, b)| (*r, b))
+    }
+
     /// Attempt a forced commit to a round.
-    pub async fn commit<M>(&mut self, moderator: &mut M, height: Height, round: Round) -> bool
+    ///
+    /// The height is committed without a block, announced with [`Event::ForcedCommit`]. Return
+    /// `false` if the height cannot be committed, and [`Error::ModeratorFailure`] if it was
+    /// committed but the event failed to be dispatched.
+    pub async fn commit<M>(
+        &mut self,
+        moderator: &mut M,
+        height: Height,
+        round: Round,
+    ) -> Result<bool, Error>
     where
         M: Moderator,
     {
-        let committed = self.metadata.commit(height, round);
+        // A forced commit has no block, so its certificate can't achieve consensus
+        let certificate = self.metadata.certify(height, round, Bytes32::zeroed());
 
-        if committed {
-            let commit = Message::Event(Event::Commit {
-                height,
-                round,
-                block_id: Bytes32::zeroed(),
+        if !self.commit_certificate(certificate)? {
+            return Ok(false);
+        }
+
+        let commit = Message::Event(Event::ForcedCommit { height, round });
+
+        self.dispatch(moderator, commit).await?;
+
+        Ok(true)
+    }
+
+    /// Commit certificate of a committed height, if retained by the reactor.
+    pub fn certificate(&self, height: Height) -> Option<&CommitCertificate> {
+        self.metadata.certificate(height)
+    }
+
+    /// Leaders of the rounds that failed to commit, per recent committed height.
+    pub const fn missed_proposals(&self) -> &BTreeMap<Height, Vec<PublicKey>> {
+        self.metadata.missed_proposals()
+    }
+
+    /// Commit the current height from a certificate produced by the network.
+    ///
+    /// The certificate is verified against the validator stakes of the height, so the votes that
+    /// formed the quorum are not required to be received by the node.
+    pub async fn sync_commit<K, M>(
+        &mut self,
+        moderator: &mut M,
+        certificate: CommitCertificate,
+    ) -> Result<(), Error>
+    where
+        K: AsyncKeychain,
+        M: Moderator,
+    {
+        let expected = self.height();
+        let height = certificate.height();
+        let round = certificate.round();
+        let block_id = *certificate.block_id();
+
+        if height != expected {
+            return Err(Error::HeightMismatch {
+                expected,
+                found: height,
             });
+        }
+
+        certificate.verify::<K::Verifier>(self.config.chain_id, self.stakes())?;
 
-            moderator.send(commit, self.timeout).await;
+        if !self.commit_certificate(certificate.clone())? {
+            return Err(Error::HeightMismatch {
+                expected,
+                found: height,
+            });
         }
 
-        committed
+        #[cfg(feature = "trace")]
+        tracing::debug!("height {} synced from commit certificate", height);
+
+        let commit = Message::Event(Event::Commit {
+            height,
+            round,
+            block_id,
+            certificate: Some(certificate),
+        });
+
+        self.dispatch(moderator, commit).await
+    }
+
+    /// Commit a block, composing its certificate from the votes collected for the round.
+    ///
+    /// Return the commit event, or `None` if the height cannot be committed. The certificate is
+    /// included only if the collected precommits achieve consensus, so a leader that commits its
+    /// own block will not certify the block with the event; its certificate is retained once the
+    /// precommits of the round are received.
+    fn finalize(
+        &mut self,
+        height: Height,
+        round: Round,
+        block_id: Bytes32,
+    ) -> Result<Option<Event>, Error> {
+        let certificate = self.metadata.certify(height, round, block_id);
+        let is_certified = self.metadata.is_certified(&certificate);
+
+        let committed = self.commit_certificate(certificate.clone())?;
+
+        Ok(committed.then(|| Event::Commit {
+            height,
+            round,
+            block_id,
+            certificate: is_certified.then_some(certificate),
+        }))
+    }
+
+    /// Commit the height of a certificate, logging the transition.
+    ///
+    /// The certificate is retained only if its votes achieve consensus. Return false if the height
+    /// cannot be committed.
+    fn commit_certificate(&mut self, certificate: CommitCertificate) -> Result<bool, Error> {
+        let height = certificate.height();
+
+        // Commit only to the subsequent block
+        if height != self.height() {
+            return Ok(false);
+        }
+
+        let is_certified = self.metadata.is_certified(&certificate);
+        let rounds = self
+            .metadata
+            .committed_rounds()
+            .saturating_add(1)
+            .saturating_add(certificate.round());
+
+        self.log(WalEntry::Commit {
+            rounds,
+            certificate: certificate.clone(),
+        })?;
+
+        self.apply_commit(&certificate, rounds);
+
+        // The announced sync target was reached
+        if self.sync_target <= self.height() {
+            self.sync_target = 0;
+        }
+
+        if is_certified {
+            self.metadata
+                .store_certificate(certificate, self.config.certificates);
+        }
+
+        // The validator set updates that took effect are superseded by the snapshot
+        if self.pending_snapshot.filter(|h| h <= &height).is_some() {
+            self.log(WalEntry::Snapshot {
+                snapshot: self.metadata.snapshot(),
+            })?;
+
+            self.pending_snapshot = None;
+        }
+
+        // The log is compacted; a failure here will only delay the pruning
+        if let Err(_e) = self.wal.prune(height) {
+            #[cfg(feature = "trace")]
+            tracing::error!("error pruning the write-ahead log: {}", _e);
+        }
+
+        Ok(true)
+    }
+
+    /// Commit the current height with the total number of rounds performed by the chain.
+    ///
+    /// The missed leaders are derived only from the certificate, so a local commit, a synced
+    /// certificate and the replay of the log produce the same leader schedule.
+    fn apply_commit(&mut self, certificate: &CommitCertificate, rounds: u64) {
+        let height = certificate.height();
+        let missed = self.missed_leaders(certificate);
+
+        // The validator set of the height is pruned with the commit
+        self.uncertified = (!self.metadata.is_certified(certificate))
+            .then(|| (certificate.clone(), self.metadata.validator_set(height)));
+
+        self.metadata
+            .record_missed_proposals(height, missed, self.config.leader_history);
+        self.metadata.commit_rounds(height, rounds);
+    }
+
+    /// Include a precommit received after the commit into the certificate of its height, if it
+    /// was committed without a quorum.
+    ///
+    /// The certificate is logged and retained once its votes achieve consensus.
+    fn complete_certificate<K>(&mut self, vote: Vote) -> Result<(), Error>
+    where
+        K: Keychain,
+    {
+        let (certificate, set) = match &self.uncertified {
+            Some(uncertified) => uncertified,
+            None => return Ok(()),
+        };
+
+        let is_precommit = vote.height() == certificate.height()
+            && vote.round() == certificate.round()
+            && vote.step() >= Step::Precommit
+            && vote.block_id() == Some(certificate.block_id())
+            && set.contains(vote.validator())
+            && certificate
+                .votes()
+                .iter()
+                .all(|v| v.validator() != vote.validator());
+
+        if !is_precommit || vote.validate::<K>(self.config.chain_id).is_err() {
+            return Ok(());
+        }
+
+        let certificate = CommitCertificate::new(
+            certificate.height(),
+            certificate.round(),
+            *certificate.block_id(),
+            *certificate.validator_set(),
+            certificate.votes().iter().copied().chain([vote]),
+        );
+
+        let approved = certificate.votes().iter().fold(0u64, |power, vote| {
+            power.saturating_add(set.voting_power(vote.validator()))
+        });
+
+        if !Consensus::evaluate(set.len(), set.total_power(), approved).is_consensus() {
+            if let Some((uncertified, _)) = self.uncertified.as_mut() {
+                *uncertified = certificate;
+            }
+
+            return Ok(());
+        }
+
+        #[cfg(feature = "trace")]
+        tracing::debug!("height {} certified after commit", certificate.height());
+
+        self.log(WalEntry::Certificate {
+            certificate: certificate.clone(),
+        })?;
+
+        self.uncertified = None;
+        self.metadata
+            .store_certificate(certificate, self.config.certificates);
+
+        Ok(())
+    }
+
+    /// Leaders of the rounds of the current height that failed before the certificate round.
+    ///
+    /// The failures are derived from the round of the certificate, which is attested by the
+    /// precommits of its quorum. A certificate without a quorum, as the one of a forced commit, has
+    /// a round chosen by the node, so it records no failures.
+    ///
+    /// A strategy might elect the same leader for several rounds, so every failed round is
+    /// recorded.
+    fn missed_leaders(&self, certificate: &CommitCertificate) -> Vec<PublicKey> {
+        if certificate.height() != self.height() || !self.metadata.is_certified(certificate) {
+            return Vec::new();
+        }
+
+        (0..certificate.round())
+            .filter_map(|round| self.leader(round).ok().copied())
+            .collect()
     }
 
-    /// Compute the round leader for the current height.
+    /// Compute the round leader for the current height, using the [`LeaderElection`] strategy of
+    /// the reactor.
     pub fn leader(&self, round: Round) -> Result<&PublicKey, Error> {
         let height = self.height();
-        let committed_rounds = self.metadata.committed_rounds();
-        let validators = self.metadata.validators_at_height_count(height) as u64;
+        let validators = self.metadata.validators_power_at_height(height);
+        let missed = self.metadata.missed_proposals_at(height);
+        let priority = self.metadata.proposer_priority_at(height);
 
         #[cfg(feature = "trace")]
         tracing::trace!(
             "choosing leader for height {} round {} with {} validators",
             height,
             round,
-            validators
+            validators.len()
         );
 
-        if validators == 0 {
+        if validators.is_empty() {
             return Err(Error::ValidatorNotFound);
         }
 
-        let index = (committed_rounds + round) % validators;
+        let election = Election::new(
+            height,
+            round,
+            self.metadata.committed_rounds(),
+            &validators,
+            &priority,
+        )
+        .with_missed_proposals(&missed);
+
+        let proposer = self
+            .election
+            .leader(&election)
+            .ok_or(Error::ValidatorNotFound)?;
         let leader = self
             .metadata
             .validators_at_height(height)
-            .nth(index as usize)
+            .find(|key| *key == &proposer)
             .ok_or(Error::ValidatorNotFound)?;
 
         #[cfg(feature = "trace")]
@@ -140,8 +689,167 @@ impl Reactor {
     }
 
     /// Add a new validator for the inclusive range `[height..height+validity]`.
-    pub fn add_validator(&mut self, validator: PublicKey, height: Height, validity: u64) {
+    ///
+    /// The validator will have a unit voting power. The update takes effect immediately, so it
+    /// should be used only for the genesis set; the updates agreed by the network are applied via
+    /// [`Self::schedule_validator`].
+    pub fn add_validator(
+        &mut self,
+        validator: PublicKey,
+        height: Height,
+        validity: u64,
+    ) -> Result<(), Error> {
+        self.log(WalEntry::Validator {
+            height,
+            validity,
+            validator,
+        })?;
+
         self.metadata.add_validator(validator, height, validity);
+
+        Ok(())
+    }
+
+    /// Schedule a validator that was included in the block of the given height.
+    ///
+    /// The validator will be active for `validity` heights from the epoch boundary
+    /// `height + Config::validator_set_delay`.
+    pub fn schedule_validator(
+        &mut self,
+        validator: PublicKey,
+        height: Height,
+        validity: u64,
+    ) -> Result<(), Error> {
+        let height = height.saturating_add(self.config.validator_set_delay);
+
+        self.add_validator(validator, height, validity)
+    }
+
+    /// Remove a validator from the provided height onwards.
+    ///
+    /// The update takes effect immediately; the removals agreed by the network are applied via
+    /// [`Self::schedule_removal`].
+    pub fn remove_validator(&mut self, validator: PublicKey, height: Height) -> Result<(), Error> {
+        self.log(WalEntry::RemoveValidator { height, validator })?;
+
+        self.metadata.remove_validator(&validator, height);
+        self.schedule_snapshot(height);
+
+        Ok(())
+    }
+
+    /// Schedule the removal of a validator that was included in the block of the given height.
+    ///
+    /// The validator will be removed from the epoch boundary
+    /// `height + Config::validator_set_delay`.
+    pub fn schedule_removal(&mut self, validator: PublicKey, height: Height) -> Result<(), Error> {
+        let height = height.saturating_add(self.config.validator_set_delay);
+
+        self.remove_validator(validator, height)
+    }
+
+    /// Replace the hot key of a validator from the provided height onwards.
+    ///
+    /// The votes of the validator for the heights before `from_height` are still verified with
+    /// the old key. The validator preserves its proposer priority and reputation.
+    ///
+    /// The votes of the current height might be already signed and in flight, so the rotation
+    /// must start after it. The update takes effect immediately; the rotations agreed by the
+    /// network are applied via [`Self::schedule_rotation`].
+    pub fn rotate_key(
+        &mut self,
+        old: PublicKey,
+        new: PublicKey,
+        from_height: Height,
+    ) -> Result<(), Error> {
+        let expected = self.height().saturating_add(1);
+        if from_height < expected {
+            return Err(Error::HeightMismatch {
+                expected,
+                found: from_height,
+            });
+        }
+
+        self.log(WalEntry::RotateKey {
+            old,
+            new,
+            from_height,
+        })?;
+
+        self.metadata.rotate_key(&old, new, from_height);
+        self.schedule_snapshot(from_height);
+
+        Ok(())
+    }
+
+    /// Schedule the key rotation of a validator that was included in the block of the given
+    /// height.
+    ///
+    /// The new key will replace the old one from the epoch boundary
+    /// `height + Config::validator_set_delay`.
+    pub fn schedule_rotation(
+        &mut self,
+        old: PublicKey,
+        new: PublicKey,
+        height: Height,
+    ) -> Result<(), Error> {
+        let from_height = height.saturating_add(self.config.validator_set_delay);
+
+        self.rotate_key(old, new, from_height)
+    }
+
+    /// Add a stake for a validator, valid for the inclusive range `[height..height+validity]`.
+    ///
+    /// The consensus thresholds are computed from the voting power of the staked keys. The stake
+    /// takes effect immediately, so it should be used only for the genesis set; the stakes agreed
+    /// by the network are applied via [`Self::schedule_stake`].
+    pub fn stake(
+        &mut self,
+        validator: Bytes64,
+        height: Height,
+        validity: u64,
+        stake: Stake,
+    ) -> Result<(), Error> {
+        let bounds = height..=height.saturating_add(validity);
+
+        // A stake refused by the metadata must not be replayed from the log
+        self.metadata
+            .check_stake(&validator, bounds.clone(), stake)?;
+
+        self.log(WalEntry::Stake {
+            height,
+            validity,
+            validator,
+            stake,
+        })?;
+
+        self.metadata.stake(validator, bounds, stake)
+    }
+
+    /// Schedule a stake that was included in the block of the given height.
+    ///
+    /// The stake will be valid for `validity` heights from the epoch boundary
+    /// `height + Config::validator_set_delay`.
+    pub fn schedule_stake(
+        &mut self,
+        validator: Bytes64,
+        height: Height,
+        validity: u64,
+        stake: Stake,
+    ) -> Result<(), Error> {
+        let height = height.saturating_add(self.config.validator_set_delay);
+
+        self.stake(validator, height, validity, stake)
+    }
+
+    /// Validator set of the given height.
+    pub fn validator_set(&self, height: Height) -> ValidatorSet {
+        self.metadata.validator_set(height)
+    }
+
+    /// Stakes that define the voting power of the validators.
+    pub const fn stakes(&self) -> &ValidatorStakes {
+        self.metadata.stakes()
     }
 
     pub(crate) async fn propose<K, M>(
@@ -150,7 +858,7 @@ impl Reactor {
         moderator: &mut M,
     ) -> Result<(), Error>
     where
-        K: Keychain,
+        K: AsyncKeychain,
         M: Moderator,
     {
         let height = self.height();
@@ -161,50 +869,83 @@ impl Reactor {
         tracing::trace!("propose request for height {} round {}", height, round);
 
         let public = keychain
-            .public(height)
+            .public_async(height)
+            .await
             .map_err(|_| Error::ResourceNotAvailable)?
             .ok_or(Error::NotRoundValidator)?;
 
         // Sanity check
-        debug_assert_eq!(public.as_ref(), self.leader(round)?);
+        debug_assert_eq!(&public, self.leader(round)?);
 
-        // If the block is not authorized, send `awaiting` event
-        let block_id = match self.metadata.authorized_propose(height) {
-            Some(b) => *b,
+        // A proposal for the round was already signed; a new one would be conflicting
+        if self.metadata.proposal(height, round, &public).is_some() {
+            return Ok(());
+        }
+
+        // A block with a proof-of-lock must be proposed again, along with the round of the
+        // prevotes; otherwise, if the block is not authorized, send `awaiting` event
+        let (block_id, pol_round) = match self
+            .metadata
+            .valid()
+            .map(|(r, b)| (b, Some(*r)))
+            .or_else(|| self.metadata.authorized_propose(height).map(|b| (b, None)))
+        {
+            Some((b, pol_round)) => (*b, pol_round),
             None => {
                 #[cfg(feature = "trace")]
                 tracing::trace!("propose blocked for height {} round {}", height, round);
 
                 let awaiting = Message::Event(Event::AwaitingBlock { height });
 
-                moderator.send(awaiting, self.timeout).await;
-
-                return Ok(());
+                return self.dispatch(moderator, awaiting).await;
             }
         };
 
-        if self.metadata.commit(height, round) {
+        let timestamp = now.unix_timestamp_nanos() / 1_000_000;
+        let timestamp = u64::try_from(timestamp).unwrap_or_default();
+
+        let proposal = Proposal::signed_async(
+            keychain,
+            self.config.chain_id,
+            height,
+            round,
+            block_id,
+            pol_round,
+            timestamp,
+        )
+        .await?;
+
+        // Always commit to own blocks
+        let commit = Vote::signed_async(
+            keychain,
+            self.config.chain_id,
+            height,
+            round,
+            Some(block_id),
+            Step::Commit,
+        )
+        .await?;
+
+        self.log(WalEntry::Proposal { proposal })?;
+        self.log(WalEntry::Vote { vote: commit })?;
+
+        self.metadata.record_proposal(&proposal);
+        self.metadata.upgrade_proposer_step(&proposal);
+        self.metadata.record_vote(&commit);
+
+        if let Some(event) = self.finalize(height, round, block_id)? {
             #[cfg(feature = "trace")]
             tracing::debug!("propose authorized for height {} round {}", height, round);
 
-            let vote = Vote::signed(keychain, height, round, block_id, Step::Propose)?;
-            let vote = Message::Event(Event::Broadcast { vote });
+            let proposal = Message::Event(Event::Proposal { proposal });
 
-            moderator.send(vote, self.timeout).await;
+            moderator.send(proposal, self.timeout).await.ok();
 
-            // Always commit to own blocks
-            let vote = Vote::signed(keychain, height, round, block_id, Step::Commit)?;
-            let vote = Message::Event(Event::Broadcast { vote });
+            let vote = Message::Event(Event::Broadcast { vote: commit });
 
-            moderator.send(vote, self.timeout).await;
+            moderator.send(vote, self.timeout).await.ok();
 
-            let event = Message::Event(Event::Commit {
-                height,
-                round,
-                block_id,
-            });
-
-            moderator.send(event, self.timeout).await;
+            self.dispatch(moderator, Message::Event(event)).await?;
         }
 
         Ok(())
@@ -216,11 +957,11 @@ impl Reactor {
         moderator: &mut M,
         height: Height,
         round: Round,
-        block_id: Bytes32,
+        block_id: Option<Bytes32>,
         step: Step,
     ) -> Result<(), Error>
     where
-        K: Keychain,
+        K: AsyncKeychain,
         M: Moderator,
     {
         #[cfg(feature = "trace")]
@@ -231,59 +972,291 @@ impl Reactor {
             step,
         );
 
-        let vote = Vote::signed(keychain, height, round, block_id, step)?;
-        let is_upgraded = self.metadata.upgrade_validator_step(&vote);
-        if !is_upgraded {
-            // State not affected; ignore
+        let public = keychain
+            .public_async(height)
+            .await
+            .map_err(|_| Error::ResourceNotAvailable)?
+            .ok_or(Error::NotRoundValidator)?;
+
+        // The step was already signed; a new vote wouldn't upgrade it and might be refused by the
+        // keychain as a conflicting signature
+        if self
+            .validator_step(height, round, &public)
+            .filter(|s| s >= &step)
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        let vote = Vote::signed_async(
+            keychain,
+            self.config.chain_id,
+            height,
+            round,
+            block_id,
+            step,
+        )
+        .await?;
+
+        // The vote is persisted before it changes the step and lock of the node
+        self.log(WalEntry::Vote { vote })?;
+
+        self.metadata.upgrade_validator_step(&vote);
+
+        // Own votes are part of the commit certificate
+        self.metadata.record_vote(&vote);
+
+        if let Some(block_id) = block_id.filter(|_| step.is_precommit()) {
+            self.metadata.lock(height, round, block_id);
+        }
+
+        let vote = Message::Event(Event::Broadcast { vote });
+
+        moderator.send(vote, self.timeout).await.ok();
+
+        // Only a block can be committed
+        let block_id = match block_id {
+            Some(b) if step.is_commit() => b,
+            _ => return Ok(()),
+        };
+
+        let event = match self.finalize(height, round, block_id)? {
+            Some(event) => event,
+            None => return Ok(()),
+        };
+
+        // The next height is started even if the commit failed to be dispatched, so the failure is
+        // returned only once the node moved on
+        let committed = self.dispatch(moderator, Message::Event(event)).await;
+
+        let height = self.height();
+        let round = 0;
+
+        let public = keychain
+            .public_async(height)
+            .await
+            .map_err(|_| Error::ResourceNotAvailable)?
+            .ok_or(Error::NotRoundValidator)?;
+
+        // Check if its the next round leader
+        let leader = self.leader(round)?;
+        let is_leader = leader == &public;
+
+        // If its not the leader, just start a new round
+        if !is_leader {
+            // async recursion currently not supported without Box hacks
+            // Better just update state and broadcast vote - otherwise should call upgrade_step
+            // again
+            if self.validator_step(height, round, &public).is_none() {
+                let vote = Vote::signed_async(
+                    keychain,
+                    self.config.chain_id,
+                    height,
+                    round,
+                    None,
+                    Step::NewRound,
+                )
+                .await?;
+
+                self.log(WalEntry::Vote { vote })?;
+                self.metadata.upgrade_validator_step(&vote);
+
+                let vote = Message::Event(Event::Broadcast { vote });
+                moderator.send(vote, self.timeout).await.ok();
+            }
+
+            return committed;
+        }
+
+        self.propose(keychain, moderator).await?;
+
+        committed
+    }
+
+    pub(crate) async fn receive_proposal<K, M>(
+        &mut self,
+        keychain: &K,
+        moderator: &mut M,
+        proposal: Proposal,
+    ) -> Result<(), Error>
+    where
+        K: AsyncKeychain,
+        M: Moderator,
+    {
+        let height = proposal.height();
+        let round = proposal.round();
+        let proposer = proposal.proposer();
+        let block_id = *proposal.block_id();
+
+        let now = moderator.now();
+
+        let expected_height = self.height();
+        let expected_round = self.round(now);
+
+        // Ignore old rounds and heights too far ahead; the sync is driven by the votes
+        if height < expected_height
+            || (height == expected_height && round < expected_round)
+            || height > expected_height.saturating_add(self.config.sync_threshold)
+        {
+            return Ok(());
+        }
+
+        let public = keychain
+            .public_async(height)
+            .await
+            .map_err(|_| Error::ResourceNotAvailable)?
+            .ok_or(Error::NotRoundValidator)?;
+
+        // Ignore messages produced by self
+        if proposer == &public {
+            return Ok(());
+        }
+
+        // Requeue future rounds
+        if height > expected_height || round > expected_round {
+            let proposal = Message::Notification(Notification::Proposal { proposal });
+
+            moderator.requeue(proposal, self.timeout).await;
+
+            return Ok(());
+        }
+
+        #[cfg(feature = "trace")]
+        tracing::trace!(
+            "receiving proposal: height {}, round {}, proposer {:08x}, pol round: {:?}",
+            height,
+            round,
+            proposer,
+            proposal.pol_round()
+        );
+
+        // Only the round leader should propose, and the proof-of-lock must precede the round
+        let is_valid = self.leader(round)? == proposer
+            && proposal.pol_round().filter(|r| r >= &round).is_none()
+            && proposal
+                .validate::<K::Verifier>(self.config.chain_id)
+                .is_ok();
+
+        if !is_valid {
+            #[cfg(feature = "trace")]
+            tracing::trace!(
+                "dropping received invalid proposal - height {}, round {}, proposer {:08x}",
+                height,
+                round,
+                proposer,
+            );
+
+            let bad_proposal = Message::Event(Event::BadProposal { proposal });
+
+            moderator.send(bad_proposal, self.timeout).await.ok();
+
+            return Ok(());
+        }
+
+        // Can discard the proposal if the proposer already moved to a further step
+        if self
+            .validator_step(height, round, proposer)
+            .filter(|s| s > &Step::Propose)
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        let validators = self.metadata.validators_at_height_count(height);
+        if !Consensus::is_bft(validators) {
+            #[cfg(feature = "trace")]
+            tracing::trace!(
+                "proposal rejected, not enough validators for the round: height {}, round {}, proposer {:08x}, validators: {}",
+                height,
+                round,
+                proposer,
+                validators
+            );
+
             return Ok(());
         }
 
-        let vote = Message::Event(Event::Broadcast { vote });
+        let validation = if self.metadata.is_block_authorized(&block_id, height) {
+            Ok(())
+        } else if let Some(validator) = moderator.block_validator() {
+            validator.validate_block(height, &block_id).await
+        } else {
+            #[cfg(feature = "trace")]
+            tracing::trace!(
+                "block not authorized - height {}, round {}, proposer {:08x}",
+                height,
+                round,
+                proposer,
+            );
+
+            let proposal = Message::Notification(Notification::Proposal { proposal });
+
+            // Block isn't authorized yet; should wait
+            moderator.requeue(proposal, self.timeout).await;
+
+            return Ok(());
+        };
 
-        moderator.send(vote, self.timeout).await;
+        self.metadata.upgrade_proposer_step(&proposal);
 
-        if step.is_commit() && self.metadata.commit(height, round) {
-            let event = Message::Event(Event::Commit {
+        if let Err(error) = validation {
+            #[cfg(feature = "trace")]
+            tracing::debug!(
+                "block validation failed - height {}, round {}, proposer {:08x}: {}",
                 height,
                 round,
-                block_id,
-            });
-
-            moderator.send(event, self.timeout).await;
+                proposer,
+                error
+            );
 
-            let height = self.height();
-            let round = 0;
+            let invalid_block = Message::Event(Event::InvalidBlock { proposal, error });
 
-            let public = keychain
-                .public(height)
-                .map_err(|_| Error::ResourceNotAvailable)?
-                .ok_or(Error::NotRoundValidator)?;
+            moderator.send(invalid_block, self.timeout).await.ok();
 
-            // Check if its the next round leader
-            let leader = self.leader(round)?;
-            let is_leader = leader == public.as_ref();
+            return self
+                .upgrade_step(keychain, moderator, height, round, None, Step::Prevote)
+                .await;
+        }
 
-            // If its not the leader, just start a new round
-            if !is_leader {
-                // async recursion currently not supported without Box hacks
-                // Better just update state and broadcast vote - otherwise should call upgrade_step
-                // again
-                let vote =
-                    Vote::signed(keychain, height, round, Bytes32::zeroed(), Step::NewRound)?;
-                let is_upgraded = self.metadata.upgrade_validator_step(&vote);
+        if !self.metadata.is_block_authorized(&block_id, height) {
+            self.log(WalEntry::BlockAuthorized { height, block_id })?;
+            self.metadata.authorize_block(block_id, height);
+        }
 
-                if is_upgraded {
-                    let vote = Message::Event(Event::Broadcast { vote });
-                    moderator.send(vote, self.timeout).await;
-                }
+        // Should upgrade to prevote; the block was authorized either via block notification or
+        // by the block validator
+        let block_id = self.prevote_block(height, round, block_id, proposal.pol_round());
 
-                return Ok(());
-            }
+        self.upgrade_step(keychain, moderator, height, round, block_id, Step::Prevote)
+            .await
+    }
 
-            self.propose(keychain, moderator).await?;
+    /// Block to be prevoted by the node for the given round.
+    ///
+    /// A locked node can only prevote its locked block or a block with a newer proof-of-lock;
+    /// otherwise, it prevotes nil.
+    fn prevote_block(
+        &self,
+        height: Height,
+        round: Round,
+        block_id: Bytes32,
+        pol_round: Option<Round>,
+    ) -> Option<Bytes32> {
+        if self
+            .metadata
+            .is_prevote_allowed(height, round, Some(&block_id), pol_round)
+        {
+            return Some(block_id);
         }
 
-        Ok(())
+        #[cfg(feature = "trace")]
+        tracing::debug!(
+            "prevote restricted by lock for height {} round {}; voting nil",
+            height,
+            round,
+        );
+
+        None
     }
 
     pub(crate) async fn receive_vote<K, M>(
@@ -293,7 +1266,7 @@ impl Reactor {
         vote: Vote,
     ) -> Result<(), Error>
     where
-        K: Keychain,
+        K: AsyncKeychain,
         M: Moderator,
     {
         let height = vote.height();
@@ -301,23 +1274,63 @@ impl Reactor {
         let validator = vote.validator();
         let block_id = vote.block_id();
         let mut proposed_step = vote.step();
-        let public = keychain
-            .public(height)
-            .map_err(|_| Error::ResourceNotAvailable)?
-            .ok_or(Error::NotRoundValidator)?;
 
         let now = moderator.now();
 
         let expected_height = self.height();
         let expected_round = self.round(now);
 
-        // Ignore messages produced by self
-        if validator == public.as_ref() {
-            return Ok(());
+        // The precommits of the last committed height might complete its certificate
+        if height < expected_height {
+            return self.complete_certificate::<K::Verifier>(vote);
         }
 
         // Ignore old steps
-        if height < expected_height || round < expected_round {
+        if height == expected_height && round < expected_round {
+            return Ok(());
+        }
+
+        // Votes too far ahead are not queued since they could exhaust the capacity of the
+        // moderator; the node is behind the network and should catch up via commit certificates
+        if height > expected_height.saturating_add(self.config.sync_threshold) {
+            // The validator set of the vote height is not known yet, so the vote is validated
+            // against the latest known set before announcing the sync target
+            let should_sync = height > self.sync_target
+                && self
+                    .metadata
+                    .validators_at_height(expected_height)
+                    .any(|v| v == validator)
+                && vote.validate::<K::Verifier>(self.config.chain_id).is_ok();
+
+            if should_sync {
+                #[cfg(feature = "trace")]
+                tracing::debug!(
+                    "sync required from height {} to {}",
+                    expected_height,
+                    height
+                );
+
+                self.sync_target = height;
+
+                let sync = Message::Event(Event::SyncRequired {
+                    from: expected_height,
+                    to: height,
+                });
+
+                moderator.send(sync, self.timeout).await.ok();
+            }
+
+            return Ok(());
+        }
+
+        let public = keychain
+            .public_async(height)
+            .await
+            .map_err(|_| Error::ResourceNotAvailable)?
+            .ok_or(Error::NotRoundValidator)?;
+
+        // Ignore messages produced by self
+        if validator == &public {
             return Ok(());
         }
 
@@ -325,8 +1338,6 @@ impl Reactor {
         if height > expected_height || round > expected_round {
             let vote = Message::Notification(Notification::Vote { vote });
 
-            // FIXME maybe limit the height different for requeue? It could be an attack vector
-            // since more queued votes than the capacity would block the reactor
             moderator.requeue(vote, self.timeout).await;
 
             return Ok(());
@@ -341,7 +1352,11 @@ impl Reactor {
             proposed_step
         );
 
-        if self.metadata.validate::<K>(&vote).is_err() {
+        if self
+            .metadata
+            .validate::<K::Verifier>(self.config.chain_id, &vote)
+            .is_err()
+        {
             #[cfg(feature = "trace")]
             tracing::trace!(
                 "dropping received invalid vote - height {}, round {}, author {:08x}, step: {:?}",
@@ -353,7 +1368,7 @@ impl Reactor {
 
             let bad_vote = Message::Event(Event::BadVote { vote });
 
-            moderator.send(bad_vote, self.timeout).await;
+            moderator.send(bad_vote, self.timeout).await.ok();
 
             return Ok(());
         }
@@ -367,7 +1382,38 @@ impl Reactor {
             proposed_step
         );
 
+        // Blocks are proposed via `Notification::Proposal`
+        if proposed_step.is_propose() {
+            let bad_vote = Message::Event(Event::BadVote { vote });
+
+            moderator.send(bad_vote, self.timeout).await.ok();
+
+            return Ok(());
+        }
+
+        // A conflicting vote is evidence of equivocation and must not affect the state
+        if let Some(first) = self.metadata.record_vote(&vote) {
+            #[cfg(feature = "trace")]
+            tracing::warn!(
+                "equivocation detected - height {}, round {}, author {:08x}, step: {:?}",
+                height,
+                round,
+                validator,
+                proposed_step
+            );
+
+            let equivocation = Message::Event(Event::Equivocation {
+                first,
+                second: vote,
+            });
+
+            moderator.send(equivocation, self.timeout).await.ok();
+
+            return Ok(());
+        }
+
         let validators = self.metadata.validators_at_height_count(height);
+        let total_power = self.metadata.total_power(height);
         let is_bft = Consensus::is_bft(validators);
         let validator_step = self.validator_step(height, round, validator);
 
@@ -403,69 +1449,53 @@ impl Reactor {
             _ => (),
         }
 
-        if proposed_step.is_propose() {
-            let leader = self.leader(round)?;
-            let proposer_is_leader = validator == leader;
+        self.metadata.upgrade_validator_step(&vote);
+
+        // Votes of the current node are tracked in the metadata, so they are already included.
+        // The approvals are weighted by the voting power of the validators
+        let approved = self
+            .metadata
+            .evaluate_step_power(height, round, proposed_step, block_id);
 
-            // Only the round leader should propose
-            if !proposer_is_leader {
-                let bad_vote = Message::Event(Event::BadVote { vote });
+        let consensus = Consensus::evaluate(validators, total_power, approved);
 
-                moderator.send(bad_vote, self.timeout).await;
+        // Wait for a block to be decided once 2/3+ of the voting power moved to prevote/precommit
+        for step in [Step::Prevote, Step::Precommit] {
+            let power = self.metadata.evaluate_any_step_power(height, round, step);
 
-                return Ok(());
+            if proposed_step >= step
+                && Consensus::evaluate(validators, total_power, power).is_consensus()
+            {
+                self.schedule_timeout(now, height, round, step);
             }
+        }
 
-            if !self.metadata.is_block_authorized(block_id, height) {
-                #[cfg(feature = "trace")]
-                tracing::trace!(
-                    "block not authorized - height {}, round {}, author {:08x}, state: {:?}",
+        // A proof-of-lock allows the block to be prevoted by nodes locked in previous rounds
+        if let Some(block_id) = block_id.filter(|_| proposed_step >= Step::Prevote) {
+            let prevotes =
+                self.metadata
+                    .evaluate_step_power(height, round, Step::Prevote, Some(block_id));
+
+            if Consensus::evaluate(validators, total_power, prevotes).is_consensus()
+                && self.metadata.is_newer_pol(height, round)
+            {
+                self.log(WalEntry::ProofOfLock {
                     height,
                     round,
-                    validator,
-                    proposed_step
-                );
-
-                let vote = Message::Notification(Notification::Vote { vote });
+                    block_id: *block_id,
+                })?;
 
-                // Block isn't authorized yet; should wait
-                moderator.requeue(vote, self.timeout).await;
-
-                return Ok(());
+                self.metadata.observe_pol(height, round, *block_id);
             }
-
-            #[cfg(feature = "trace")]
-            tracing::trace!(
-                "block authorized - height {}, round {}, author {:08x}, state: {:?}",
-                height,
-                round,
-                validator,
-                proposed_step
-            );
-
-            self.metadata.upgrade_validator_step(&vote);
-
-            // Should upgrade to prevote; vote was authorized via block notification
-            self.upgrade_step(keychain, moderator, height, round, *block_id, Step::Prevote)
-                .await?;
-
-            return Ok(());
         }
 
-        self.metadata.upgrade_validator_step(&vote);
-
-        // Evaluate the count considering the vote of the current node
-        let approved = 1 + self
-            .metadata
-            .evaluate_step_count(height, round, proposed_step);
-
-        let consensus = Consensus::evaluate(validators, approved);
-
         // Upgrade to highest available consensus
         if consensus.is_consensus() {
             while let Some(next_step) = proposed_step.increment() {
-                let approved = 1 + self.metadata.evaluate_step_count(height, round, next_step);
-                let next_consensus = Consensus::evaluate(validators, approved);
+                let approved = self
+                    .metadata
+                    .evaluate_step_power(height, round, next_step, block_id);
+                let next_consensus = Consensus::evaluate(validators, total_power, approved);
 
                 if next_consensus.is_consensus() {
                     proposed_step = next_step;
@@ -485,31 +1515,47 @@ impl Reactor {
             consensus
         );
 
-        let current_step = self.validator_step(height, round, public.as_ref());
+        let current_step = self.validator_step(height, round, &public);
 
         match consensus {
             Consensus::Inconclusive if current_step.is_none() => {
+                self.upgrade_step(keychain, moderator, height, round, None, Step::initial())
+                    .await?;
+            }
+
+            Consensus::Inconclusive => (),
+
+            // 2/3+ nil precommits cannot be committed; the height moves to the next round
+            Consensus::Consensus if block_id.is_none() && proposed_step >= Step::Precommit => {
+                self.advance_round(height, round + 1)?;
+            }
+
+            Consensus::Consensus if proposed_step.is_precommit() || proposed_step.is_commit() => {
                 self.upgrade_step(
                     keychain,
                     moderator,
                     height,
                     round,
-                    *block_id,
-                    Step::initial(),
+                    block_id.copied(),
+                    Step::Commit,
                 )
                 .await?;
             }
 
-            Consensus::Inconclusive => (),
-
-            Consensus::Consensus if proposed_step.is_precommit() || proposed_step.is_commit() => {
-                self.upgrade_step(keychain, moderator, height, round, *block_id, Step::Commit)
-                    .await?;
-            }
-
+            // The propose step is reached only by the proposal of the leader
             Consensus::Consensus => {
-                if let Some(step) = proposed_step.increment() {
-                    self.upgrade_step(keychain, moderator, height, round, *block_id, step)
+                if let Some(step) = proposed_step.increment().filter(|s| !s.is_propose()) {
+                    // Without a proposal, only the proof-of-lock observed by the node can unlock it
+                    let block_id = match block_id {
+                        Some(b) if step == Step::Prevote => {
+                            let pol_round = self.metadata.valid().map(|(r, _)| *r);
+
+                            self.prevote_block(height, round, *b, pol_round)
+                        }
+                        b => b.copied(),
+                    };
+
+                    self.upgrade_step(keychain, moderator, height, round, block_id, step)
                         .await?;
                 }
             }
@@ -539,6 +1585,134 @@ impl Reactor {
         Ok(())
     }
 
+    /// Move the height to a new round, logging the transition.
+    fn advance_round(&mut self, height: Height, round: Round) -> Result<(), Error> {
+        if self.metadata.is_next_round(height, round) {
+            self.log(WalEntry::Round { height, round })?;
+            self.metadata.advance_round(height, round);
+        }
+
+        Ok(())
+    }
+
+    /// Schedule a step timeout for the given round, if not already scheduled.
+    pub(crate) fn schedule_timeout(
+        &mut self,
+        now: OffsetDateTime,
+        height: Height,
+        round: Round,
+        step: Step,
+    ) {
+        let timeout = match self.config.step_timeout(step, round) {
+            Some(t) => t,
+            None => return,
+        };
+
+        self.deadlines
+            .entry((height, round, step))
+            .or_insert_with(|| {
+                #[cfg(feature = "trace")]
+                tracing::trace!(
+                    "scheduling {:?} timeout of {} ms for height {} round {}",
+                    step,
+                    timeout,
+                    height,
+                    round
+                );
+
+                let timeout = time::Duration::milliseconds(timeout as i64);
+
+                Some(now.saturating_add(timeout))
+            });
+    }
+
+    /// Dispatch the expired step timeouts through the moderator.
+    pub(crate) async fn fire_timeouts<M>(&mut self, moderator: &mut M)
+    where
+        M: Moderator,
+    {
+        let height = self.height();
+        let now = moderator.now();
+
+        // Timeouts of committed heights are irrelevant
+        self.deadlines.retain(|(h, _, _), _| height <= *h);
+
+        let expired: Vec<_> = self
+            .deadlines
+            .iter_mut()
+            .filter_map(|(k, deadline)| match deadline {
+                Some(d) if *d <= now => {
+                    deadline.take();
+
+                    Some(*k)
+                }
+                _ => None,
+            })
+            .collect();
+
+        for (height, round, step) in expired {
+            let timeout = Message::Notification(Notification::Timeout {
+                height,
+                round,
+                step,
+            });
+
+            moderator.requeue(timeout, self.timeout).await;
+        }
+    }
+
+    pub(crate) async fn receive_timeout<K, M>(
+        &mut self,
+        keychain: &K,
+        moderator: &mut M,
+        height: Height,
+        round: Round,
+        step: Step,
+    ) -> Result<(), Error>
+    where
+        K: AsyncKeychain,
+        M: Moderator,
+    {
+        let now = moderator.now();
+
+        // Ignore timeouts of steps that are already finished
+        if height != self.height() || round != self.round(now) {
+            return Ok(());
+        }
+
+        #[cfg(feature = "trace")]
+        tracing::debug!("{:?} timeout for height {} round {}", step, height, round);
+
+        let public = keychain
+            .public_async(height)
+            .await
+            .map_err(|_| Error::ResourceNotAvailable)?
+            .ok_or(Error::NotRoundValidator)?;
+
+        let current_step = self.validator_step(height, round, &public);
+
+        match step {
+            // No valid proposal was received in time
+            Step::Propose if current_step.filter(|s| s >= &Step::Prevote).is_none() => {
+                self.upgrade_step(keychain, moderator, height, round, None, Step::Prevote)
+                    .await?;
+            }
+
+            // No block achieved 2/3+ prevotes in time
+            Step::Prevote if current_step == Some(Step::Prevote) => {
+                self.upgrade_step(keychain, moderator, height, round, None, Step::Precommit)
+                    .await?;
+            }
+
+            // No block achieved 2/3+ precommits in time
+            Step::Precommit => self.advance_round(height, round + 1)?,
+
+            _ => (),
+        }
+
+        Ok(())
+    }
+
     pub(crate) async fn receive_event(&mut self, _event: Event) {
         #[cfg(feature = "trace")]
         tracing::warn!("inbound events are not expected; ignored {:?}", _event);
@@ -550,7 +1724,7 @@ impl Reactor {
         moderator: &mut M,
         notification: Notification,
     ) where
-        K: Keychain,
+        K: AsyncKeychain,
         M: Moderator,
     {
         match notification {
@@ -560,7 +1734,38 @@ impl Reactor {
                 height,
                 validity,
                 validator,
-            } => self.add_validator(validator, height, validity),
+            } => {
+                if let Err(_e) = self.schedule_validator(validator, height, validity) {
+                    #[cfg(feature = "trace")]
+                    tracing::error!("error adding validator: {}", _e);
+                }
+            }
+
+            Notification::Stake {
+                height,
+                validity,
+                validator,
+                stake,
+            } => {
+                if let Err(_e) = self.schedule_stake(validator, height, validity, stake) {
+                    #[cfg(feature = "trace")]
+                    tracing::error!("error adding stake: {}", _e);
+                }
+            }
+
+            Notification::RemoveValidator { height, validator } => {
+                if let Err(_e) = self.schedule_removal(validator, height) {
+                    #[cfg(feature = "trace")]
+                    tracing::error!("error removing validator: {}", _e);
+                }
+            }
+
+            Notification::RotateKey { old, new, height } => {
+                if let Err(_e) = self.schedule_rotation(old, new, height) {
+                    #[cfg(feature = "trace")]
+                    tracing::error!("error rotating validator key: {}", _e);
+                }
+            }
 
             Notification::Vote { vote } => {
                 if let Err(_e) = self.receive_vote(keychain, moderator, vote).await {
@@ -569,18 +1774,52 @@ impl Reactor {
                 }
             }
 
+            Notification::Proposal { proposal } => {
+                if let Err(_e) = self.receive_proposal(keychain, moderator, proposal).await {
+                    #[cfg(feature = "trace")]
+                    tracing::error!("error receiving proposal: {}", _e);
+                }
+            }
+
+            Notification::SyncCommit { certificate } => {
+                if let Err(_e) = self.sync_commit::<K, M>(moderator, certificate).await {
+                    #[cfg(feature = "trace")]
+                    tracing::error!("error syncing commit: {}", _e);
+                }
+            }
+
+            Notification::Timeout {
+                height,
+                round,
+                step,
+            } => {
+                if let Err(_e) = self
+                    .receive_timeout(keychain, moderator, height, round, step)
+                    .await
+                {
+                    #[cfg(feature = "trace")]
+                    tracing::error!("error receiving timeout: {}", _e);
+                }
+            }
+
             Notification::BlockAuthorized { height, block_id } => {
                 #[cfg(feature = "trace")]
                 tracing::debug!("block authorized for height {}", height);
 
-                self.metadata.authorize_block(block_id, height)
+                if let Err(_e) = self.authorize_block(height, block_id) {
+                    #[cfg(feature = "trace")]
+                    tracing::error!("error authorizing block: {}", _e);
+                }
             }
 
             Notification::BlockProposeAuthorized { height, block_id } => {
                 #[cfg(feature = "trace")]
                 tracing::debug!("block propose authorized for height {}", height);
 
-                self.metadata.authorize_block_propose(height, block_id)
+                if let Err(_e) = self.authorize_block_propose(height, block_id) {
+                    #[cfg(feature = "trace")]
+                    tracing::error!("error authorizing block propose: {}", _e);
+                }
             }
         }
     }
@@ -591,22 +1830,35 @@ impl Reactor {
         moderator: &mut M,
         request: Request,
     ) where
-        K: Keychain,
+        K: AsyncKeychain,
         M: Moderator,
     {
         let response = match request {
             Request::Commit { id, height, round } => Response::Commit {
                 id,
-                committed: self.commit(moderator, height, round).await,
+                committed: match self.commit(moderator, height, round).await {
+                    Ok(committed) => committed,
+
+                    // The height was committed, even if the application failed to process it
+                    Err(Error::ModeratorFailure) => true,
+
+                    Err(_e) => {
+                        #[cfg(feature = "trace")]
+                        tracing::error!("error committing height {}: {}", height, _e);
+
+                        false
+                    }
+                },
+            },
+
+            Request::CommitCertificate { id, height } => Response::CommitCertificate {
+                id,
+                certificate: self.certificate(height).cloned(),
             },
 
             Request::Identity { id, height } => Response::Identity {
                 id,
-                public: keychain
-                    .public(height)
-                    .ok()
-                    .flatten()
-                    .map(|k| k.into_owned()),
+                public: keychain.public_async(height).await.ok().flatten(),
             },
 
             Request::Initialize {
@@ -616,11 +1868,12 @@ impl Reactor {
             } => Response::Initialize {
                 id,
                 initialized: keychain
-                    .public(start)
+                    .public_async(start)
+                    .await
                     .ok()
                     .flatten()
-                    .map(|public| self.add_validator(public.into_owned(), start, validity))
-                    .is_some(),
+                    .map(|public| self.add_validator(public, start, validity).is_ok())
+                    .unwrap_or(false),
             },
 
             Request::Round { id } => {
@@ -628,9 +1881,9 @@ impl Reactor {
                 let round = self.round(moderator.now());
                 let leader = self.leader(round).copied().unwrap_or_default();
                 let public = keychain
-                    .public(height)
+                    .public_async(height)
+                    .await
                     .unwrap_or_default()
-                    .map(|p| p.into_owned())
                     .unwrap_or_default();
                 let step = self.validator_step(height, round, &public);
 
@@ -646,13 +1899,13 @@ impl Reactor {
 
         let response = Message::Response(response);
 
-        moderator.send(response, self.timeout).await;
+        moderator.send(response, self.timeout).await.ok();
     }
 
     /// Receive a new message, mutating the internal state
     pub async fn receive<K, M>(&mut self, keychain: &K, moderator: &mut M, message: Message)
     where
-        K: Keychain,
+        K: AsyncKeychain,
         M: Moderator,
     {
         #[cfg(feature = "trace")]
@@ -669,7 +1922,7 @@ impl Reactor {
     /// Check the current status of the reactor, producing an event, if applicable
     pub async fn heartbeat<K, M>(&mut self, keychain: &K, moderator: &mut M) -> Result<(), Error>
     where
-        K: Keychain,
+        K: AsyncKeychain,
         M: Moderator,
     {
         let height = self.height();
@@ -679,7 +1932,8 @@ impl Reactor {
 
         // If no public key is available in the keychain, the node is idle
         let public = match keychain
-            .public(height)
+            .public_async(height)
+            .await
             .map_err(|_| Error::ResourceNotAvailable)?
         {
             Some(p) => p,
@@ -687,7 +1941,7 @@ impl Reactor {
                 let idle = Message::Event(Event::Idle);
 
                 // FIXME maybe clean all queues?
-                moderator.send(idle, self.timeout).await;
+                moderator.send(idle, self.timeout).await.ok();
 
                 if let Ok(Some(m)) = moderator.inbound().await {
                     moderator.rebound(m, self.timeout).await.ok();
@@ -697,6 +1951,9 @@ impl Reactor {
             }
         };
 
+        // Expired step timeouts are consumed with the inbound queue
+        self.fire_timeouts(moderator).await;
+
         #[cfg(feature = "trace")]
         tracing::trace!("heartbeat height {} drain queue", height);
 
@@ -715,9 +1972,13 @@ impl Reactor {
             }
         }
 
+        let height = self.height();
         let now = moderator.now();
         let round = self.round(now);
 
+        // Wait for the proposal of the current round
+        self.schedule_timeout(now, height, round, Step::Propose);
+
         #[cfg(feature = "trace")]
         tracing::trace!("heartbeat height {} check propose", height);
 
@@ -726,7 +1987,7 @@ impl Reactor {
         if let Ok(leader) = self.leader(round) {
             // The consumer of the outbound messages should be aware that this reactor will send the
             // request for a new block multiple times - once per heartbeat
-            if leader == public.as_ref() {
+            if leader == &public {
                 #[cfg(feature = "trace")]
                 tracing::trace!("round leader height {} from heartbeat", height);
 
@@ -1,10 +1,16 @@
+use crate::{ChainId, Round, Step};
+
 use time::OffsetDateTime;
 
 use core::time::Duration;
 
 /// Config data for the reactor and consensus behavior
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Config {
+    /// Identifier of the chain, bound to the signature of every vote
+    pub chain_id: ChainId,
+
     /// Blocking resources capacity
     pub capacity: usize,
 
@@ -19,22 +25,72 @@ pub struct Config {
 
     /// Await timeout for blocking resources
     pub timeout: Duration,
+
+    /// Number of commit certificates of recent heights retained by the reactor
+    pub certificates: usize,
+
+    /// Number of recent committed heights for which the leaders of the failed rounds are tracked.
+    ///
+    /// The tracked leaders are provided to the [`LeaderElection`](crate::LeaderElection) strategy.
+    pub leader_history: u64,
+
+    /// Number of heights after the commit of a validator set update before it takes effect.
+    ///
+    /// An update committed at height `h` will change the validator set from `h + delay`, so every
+    /// node applies it at the same epoch boundary.
+    pub validator_set_delay: u64,
+
+    /// Maximum distance of a future height for its votes to be queued by the reactor.
+    ///
+    /// Votes beyond this distance are dropped and a sync is requested instead.
+    pub sync_threshold: u64,
+
+    /// Timeout to wait for a proposal in the first round (ms)
+    pub propose_timeout: u128,
+
+    /// Increment of the propose timeout for every round (ms)
+    pub propose_timeout_delta: u128,
+
+    /// Timeout to wait for 2/3+ prevotes for a block in the first round (ms)
+    pub prevote_timeout: u128,
+
+    /// Increment of the prevote timeout for every round (ms)
+    pub prevote_timeout_delta: u128,
+
+    /// Timeout to wait for 2/3+ precommits for a block in the first round (ms)
+    pub precommit_timeout: u128,
+
+    /// Increment of the precommit timeout for every round (ms)
+    pub precommit_timeout_delta: u128,
 }
 
-impl Default for Config {
-    fn default() -> Self {
+// TODO tweak
+impl Config {
+    /// Create a new config for the provided chain, with the default parameters.
+    ///
+    /// The chain identifier has no default so nodes of distinct chains can't accept the votes
+    /// of each other.
+    pub const fn new(chain_id: ChainId) -> Self {
         Self {
+            chain_id,
             capacity: Self::DEFAULT_CAPACITY,
             consensus: Self::DEFAULT_CONSENSUS,
             genesis: Self::DEFAULT_GENESIS,
             heartbeat: Self::DEFAULT_HEARTBEAT,
             timeout: Self::DEFAULT_TIMEOUT,
+            certificates: Self::DEFAULT_CERTIFICATES,
+            leader_history: Self::DEFAULT_LEADER_HISTORY,
+            validator_set_delay: Self::DEFAULT_VALIDATOR_SET_DELAY,
+            sync_threshold: Self::DEFAULT_SYNC_THRESHOLD,
+            propose_timeout: Self::DEFAULT_PROPOSE_TIMEOUT,
+            propose_timeout_delta: Self::DEFAULT_PROPOSE_TIMEOUT_DELTA,
+            prevote_timeout: Self::DEFAULT_PREVOTE_TIMEOUT,
+            prevote_timeout_delta: Self::DEFAULT_PREVOTE_TIMEOUT_DELTA,
+            precommit_timeout: Self::DEFAULT_PRECOMMIT_TIMEOUT,
+            precommit_timeout_delta: Self::DEFAULT_PRECOMMIT_TIMEOUT_DELTA,
         }
     }
-}
 
-// TODO tweak
-impl Config {
     /// Default capacity
     pub const DEFAULT_CAPACITY: usize = 256;
 
@@ -49,4 +105,48 @@ impl Config {
 
     /// 5s as default timeout
     pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Default number of retained commit certificates
+    pub const DEFAULT_CERTIFICATES: usize = 256;
+
+    /// Track the failed leaders of the last 16 committed heights
+    pub const DEFAULT_LEADER_HISTORY: u64 = 16;
+
+    /// Apply the validator set updates two heights after their commit
+    pub const DEFAULT_VALIDATOR_SET_DELAY: u64 = 2;
+
+    /// Queue votes up to two heights ahead
+    pub const DEFAULT_SYNC_THRESHOLD: u64 = 2;
+
+    /// 3 seconds as default propose timeout
+    pub const DEFAULT_PROPOSE_TIMEOUT: u128 = 3000;
+
+    /// 500 ms as default propose timeout increment per round
+    pub const DEFAULT_PROPOSE_TIMEOUT_DELTA: u128 = 500;
+
+    /// 1 second as default prevote timeout
+    pub const DEFAULT_PREVOTE_TIMEOUT: u128 = 1000;
+
+    /// 500 ms as default prevote timeout increment per round
+    pub const DEFAULT_PREVOTE_TIMEOUT_DELTA: u128 = 500;
+
+    /// 1 second as default precommit timeout
+    pub const DEFAULT_PRECOMMIT_TIMEOUT: u128 = 1000;
+
+    /// 500 ms as default precommit timeout increment per round
+    pub const DEFAULT_PRECOMMIT_TIMEOUT_DELTA: u128 = 500;
+
+    /// Timeout of a step for the given round (ms), if the step is time bound.
+    ///
+    /// The timeouts grow linearly with the round so the network can eventually synchronize.
+    pub const fn step_timeout(&self, step: Step, round: Round) -> Option<u128> {
+        let (timeout, delta) = match step {
+            Step::Propose => (self.propose_timeout, self.propose_timeout_delta),
+            Step::Prevote => (self.prevote_timeout, self.prevote_timeout_delta),
+            Step::Precommit => (self.precommit_timeout, self.precommit_timeout_delta),
+            Step::NewRound | Step::Commit => return None,
+        };
+
+        Some(timeout.saturating_add(delta.saturating_mul(round as u128)))
+    }
 }
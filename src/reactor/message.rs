@@ -1,3 +1,7 @@
+use crate::{Decode, DecodeError, Decoder, Encode};
+
+use alloc::vec::Vec;
+
 mod event;
 mod notification;
 mod request;
@@ -7,7 +11,8 @@ pub use notification::Notification;
 pub use request::{Request, Response};
 
 /// I/O interface with the reactor
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Message {
     /// Event produced by the reactor
     Event(Event),
@@ -36,3 +41,48 @@ impl From<Request> for Message {
         Self::Request(r)
     }
 }
+
+impl Message {
+    const EVENT: u8 = 0x00;
+    const NOTIFICATION: u8 = 0x01;
+    const REQUEST: u8 = 0x02;
+    const RESPONSE: u8 = 0x03;
+}
+
+impl Encode for Message {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Event(e) => {
+                Self::EVENT.encode(buf);
+                e.encode(buf);
+            }
+
+            Self::Notification(n) => {
+                Self::NOTIFICATION.encode(buf);
+                n.encode(buf);
+            }
+
+            Self::Request(r) => {
+                Self::REQUEST.encode(buf);
+                r.encode(buf);
+            }
+
+            Self::Response(r) => {
+                Self::RESPONSE.encode(buf);
+                r.encode(buf);
+            }
+        }
+    }
+}
+
+impl Decode for Message {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        match decoder.decode()? {
+            Self::EVENT => decoder.decode().map(Self::Event),
+            Self::NOTIFICATION => decoder.decode().map(Self::Notification),
+            Self::REQUEST => decoder.decode().map(Self::Request),
+            Self::RESPONSE => decoder.decode().map(Self::Response),
+            tag => Err(DecodeError::InvalidTag { tag }),
+        }
+    }
+}
@@ -1,9 +1,14 @@
-use crate::{Height, Round, Vote};
+use crate::{
+    CommitCertificate, Decode, DecodeError, Decoder, Encode, Error, Height, Proposal, Round, Vote,
+};
 
 use fuel_types::Bytes32;
 
+use alloc::vec::Vec;
+
 /// Event produced by the reactor
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
     /// The reactor is awaiting for a block authorization to propose a new consensus round.
     AwaitingBlock {
@@ -30,6 +35,30 @@ pub enum Event {
         round: Round,
         /// Block identifier.
         block_id: Bytes32,
+        /// Votes that formed the commit quorum; `None` if the node committed without collecting
+        /// 2/3+ of the precommits, as the leader of the block. The certificate of the leader is
+        /// retained by the reactor once the precommits are received.
+        certificate: Option<CommitCertificate>,
+    },
+
+    /// A height was committed without a block, with a forced commit of
+    /// [`Reactor::commit`](crate::Reactor::commit).
+    ForcedCommit {
+        /// Committed block height.
+        height: Height,
+        /// Rounds performed for this height.
+        round: Round,
+    },
+
+    /// Votes for heights far ahead of the node were received, so it is behind the network.
+    ///
+    /// The commit certificates of the heights `[from..to)` should be provided to the reactor via
+    /// [`Notification::SyncCommit`](crate::Notification::SyncCommit).
+    SyncRequired {
+        /// Current height of the node.
+        from: Height,
+        /// Height observed in the network.
+        to: Height,
     },
 
     /// A bad vote was received - should reduce the karma of the author
@@ -37,4 +66,178 @@ pub enum Event {
         /// Tampered vote
         vote: Vote,
     },
+
+    /// A validator signed conflicting votes for the same height, round and step.
+    ///
+    /// Both votes are signed by the validator, so they can be verified as evidence of misbehavior.
+    Equivocation {
+        /// First vote received from the validator
+        first: Vote,
+        /// Conflicting vote received from the validator
+        second: Vote,
+    },
+
+    /// The reactor produced a block proposal and it should be broadcast to the peers.
+    Proposal {
+        /// Proposal produced by the reactor
+        proposal: Proposal,
+    },
+
+    /// A bad proposal was received - should reduce the karma of the author
+    BadProposal {
+        /// Tampered proposal
+        proposal: Proposal,
+    },
+
+    /// The proposed block was rejected by the
+    /// [`BlockValidator`](crate::BlockValidator) of the application, so the node prevoted nil.
+    InvalidBlock {
+        /// Proposal of the rejected block
+        proposal: Proposal,
+        /// Validation error
+        error: Error,
+    },
+}
+
+impl Event {
+    const AWAITING_BLOCK: u8 = 0x00;
+    const IDLE: u8 = 0x01;
+    const BROADCAST: u8 = 0x02;
+    const COMMIT: u8 = 0x03;
+    const SYNC_REQUIRED: u8 = 0x04;
+    const BAD_VOTE: u8 = 0x05;
+    const EQUIVOCATION: u8 = 0x06;
+    const PROPOSAL: u8 = 0x07;
+    const BAD_PROPOSAL: u8 = 0x08;
+    const INVALID_BLOCK: u8 = 0x09;
+    const FORCED_COMMIT: u8 = 0x0a;
+}
+
+impl Encode for Event {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::AwaitingBlock { height } => {
+                Self::AWAITING_BLOCK.encode(buf);
+                height.encode(buf);
+            }
+
+            Self::Idle => Self::IDLE.encode(buf),
+
+            Self::Broadcast { vote } => {
+                Self::BROADCAST.encode(buf);
+                vote.encode(buf);
+            }
+
+            Self::Commit {
+                height,
+                round,
+                block_id,
+                certificate,
+            } => {
+                Self::COMMIT.encode(buf);
+                height.encode(buf);
+                round.encode(buf);
+                block_id.encode(buf);
+                certificate.encode(buf);
+            }
+
+            Self::ForcedCommit { height, round } => {
+                Self::FORCED_COMMIT.encode(buf);
+                height.encode(buf);
+                round.encode(buf);
+            }
+
+            Self::SyncRequired { from, to } => {
+                Self::SYNC_REQUIRED.encode(buf);
+                from.encode(buf);
+                to.encode(buf);
+            }
+
+            Self::BadVote { vote } => {
+                Self::BAD_VOTE.encode(buf);
+                vote.encode(buf);
+            }
+
+            Self::Equivocation { first, second } => {
+                Self::EQUIVOCATION.encode(buf);
+                first.encode(buf);
+                second.encode(buf);
+            }
+
+            Self::Proposal { proposal } => {
+                Self::PROPOSAL.encode(buf);
+                proposal.encode(buf);
+            }
+
+            Self::BadProposal { proposal } => {
+                Self::BAD_PROPOSAL.encode(buf);
+                proposal.encode(buf);
+            }
+
+            Self::InvalidBlock { proposal, error } => {
+                Self::INVALID_BLOCK.encode(buf);
+                proposal.encode(buf);
+                error.encode(buf);
+            }
+        }
+    }
+}
+
+impl Decode for Event {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let event = match decoder.decode()? {
+            Self::AWAITING_BLOCK => Self::AwaitingBlock {
+                height: decoder.decode()?,
+            },
+
+            Self::IDLE => Self::Idle,
+
+            Self::BROADCAST => Self::Broadcast {
+                vote: decoder.decode()?,
+            },
+
+            Self::COMMIT => Self::Commit {
+                height: decoder.decode()?,
+                round: decoder.decode()?,
+                block_id: decoder.decode()?,
+                certificate: decoder.decode()?,
+            },
+
+            Self::FORCED_COMMIT => Self::ForcedCommit {
+                height: decoder.decode()?,
+                round: decoder.decode()?,
+            },
+
+            Self::SYNC_REQUIRED => Self::SyncRequired {
+                from: decoder.decode()?,
+                to: decoder.decode()?,
+            },
+
+            Self::BAD_VOTE => Self::BadVote {
+                vote: decoder.decode()?,
+            },
+
+            Self::EQUIVOCATION => Self::Equivocation {
+                first: decoder.decode()?,
+                second: decoder.decode()?,
+            },
+
+            Self::PROPOSAL => Self::Proposal {
+                proposal: decoder.decode()?,
+            },
+
+            Self::BAD_PROPOSAL => Self::BadProposal {
+                proposal: decoder.decode()?,
+            },
+
+            Self::INVALID_BLOCK => Self::InvalidBlock {
+                proposal: decoder.decode()?,
+                error: decoder.decode()?,
+            },
+
+            tag => return Err(DecodeError::InvalidTag { tag }),
+        };
+
+        Ok(event)
+    }
 }
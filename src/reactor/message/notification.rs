@@ -1,30 +1,78 @@
-use crate::{Height, Vote};
+use crate::{
+    CommitCertificate, Decode, DecodeError, Decoder, Encode, Height, Proposal, Round, Stake, Step,
+    Vote,
+};
 
 use fuel_crypto::PublicKey;
-use fuel_types::Bytes32;
+use fuel_types::{Bytes32, Bytes64};
+
+use alloc::vec::Vec;
 
 /// A notification to be consumed by the reactor
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Notification {
     /// Kill command.
     Kill,
 
-    /// A validator was included
+    /// A validator was included with a unit voting power.
+    ///
+    /// The update takes effect at the epoch boundary
+    /// [`Config::validator_set_delay`](crate::Config::validator_set_delay) heights after its
+    /// commit.
     NewValidator {
-        /// Initial block height.
+        /// Height of the block that included the update.
         height: Height,
         /// Validity period of the validator.
         validity: u64,
         /// Validator identifier.
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
         validator: PublicKey,
     },
 
+    /// A stake was registered for a validator.
+    ///
+    /// The update takes effect at the epoch boundary
+    /// [`Config::validator_set_delay`](crate::Config::validator_set_delay) heights after its
+    /// commit.
+    Stake {
+        /// Height of the block that included the update.
+        height: Height,
+        /// Validity period of the stake.
+        validity: u64,
+        /// Permanent identity of the validator.
+        validator: Bytes64,
+        /// Stake key and voting power.
+        stake: Stake,
+    },
+
     /// A new vote was received
     Vote {
         /// Vote to be processed
         vote: Vote,
     },
 
+    /// A commit certificate for the current height was obtained from the network.
+    ///
+    /// The reactor will verify the certificate against the validator stakes and commit the
+    /// certified block, allowing a lagging node to catch up without the original votes.
+    SyncCommit {
+        /// Certificate of the committed block
+        certificate: CommitCertificate,
+    },
+
+    /// The timeout of a round step expired.
+    ///
+    /// The reactor schedules these notifications through the moderator.
+    Timeout {
+        /// Block height
+        height: Height,
+        /// Height round
+        round: Round,
+        /// Step that timed out
+        step: Step,
+    },
+
     /// A block was cleared for consensus.
     ///
     /// The reactor will expect this event before it can upgrade from the Propose phase.
@@ -43,4 +91,201 @@ pub enum Notification {
         /// Block identifier.
         block_id: Bytes32,
     },
+
+    /// A block proposal was received
+    Proposal {
+        /// Proposal to be processed
+        proposal: Proposal,
+    },
+
+    /// A validator was removed from the set.
+    ///
+    /// The update takes effect at the epoch boundary
+    /// [`Config::validator_set_delay`](crate::Config::validator_set_delay) heights after its
+    /// commit.
+    RemoveValidator {
+        /// Height of the block that included the update.
+        height: Height,
+        /// Key of the removed validator.
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
+        validator: PublicKey,
+    },
+
+    /// The hot key of a validator was replaced.
+    ///
+    /// The update takes effect at the epoch boundary
+    /// [`Config::validator_set_delay`](crate::Config::validator_set_delay) heights after its
+    /// commit. The stakes of the old key are transferred to the new key from the boundary onwards,
+    /// so the votes of these heights must be signed with the new key.
+    RotateKey {
+        /// Replaced key.
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
+        old: PublicKey,
+        /// Key of the validator from the epoch boundary onwards.
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
+        new: PublicKey,
+        /// Height of the block that included the update.
+        height: Height,
+    },
+}
+
+impl Notification {
+    const KILL: u8 = 0x00;
+    const NEW_VALIDATOR: u8 = 0x01;
+    const STAKE: u8 = 0x02;
+    const VOTE: u8 = 0x03;
+    const SYNC_COMMIT: u8 = 0x04;
+    const TIMEOUT: u8 = 0x05;
+    const BLOCK_AUTHORIZED: u8 = 0x06;
+    const BLOCK_PROPOSE_AUTHORIZED: u8 = 0x07;
+    const PROPOSAL: u8 = 0x08;
+    const REMOVE_VALIDATOR: u8 = 0x09;
+    const ROTATE_KEY: u8 = 0x0a;
+}
+
+impl Encode for Notification {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Kill => Self::KILL.encode(buf),
+
+            Self::NewValidator {
+                height,
+                validity,
+                validator,
+            } => {
+                Self::NEW_VALIDATOR.encode(buf);
+                height.encode(buf);
+                validity.encode(buf);
+                validator.encode(buf);
+            }
+
+            Self::Stake {
+                height,
+                validity,
+                validator,
+                stake,
+            } => {
+                Self::STAKE.encode(buf);
+                height.encode(buf);
+                validity.encode(buf);
+                validator.encode(buf);
+                stake.encode(buf);
+            }
+
+            Self::Vote { vote } => {
+                Self::VOTE.encode(buf);
+                vote.encode(buf);
+            }
+
+            Self::SyncCommit { certificate } => {
+                Self::SYNC_COMMIT.encode(buf);
+                certificate.encode(buf);
+            }
+
+            Self::Timeout {
+                height,
+                round,
+                step,
+            } => {
+                Self::TIMEOUT.encode(buf);
+                height.encode(buf);
+                round.encode(buf);
+                step.encode(buf);
+            }
+
+            Self::BlockAuthorized { height, block_id } => {
+                Self::BLOCK_AUTHORIZED.encode(buf);
+                height.encode(buf);
+                block_id.encode(buf);
+            }
+
+            Self::BlockProposeAuthorized { height, block_id } => {
+                Self::BLOCK_PROPOSE_AUTHORIZED.encode(buf);
+                height.encode(buf);
+                block_id.encode(buf);
+            }
+
+            Self::Proposal { proposal } => {
+                Self::PROPOSAL.encode(buf);
+                proposal.encode(buf);
+            }
+
+            Self::RemoveValidator { height, validator } => {
+                Self::REMOVE_VALIDATOR.encode(buf);
+                height.encode(buf);
+                validator.encode(buf);
+            }
+
+            Self::RotateKey { old, new, height } => {
+                Self::ROTATE_KEY.encode(buf);
+                old.encode(buf);
+                new.encode(buf);
+                height.encode(buf);
+            }
+        }
+    }
+}
+
+impl Decode for Notification {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let notification = match decoder.decode()? {
+            Self::KILL => Self::Kill,
+
+            Self::NEW_VALIDATOR => Self::NewValidator {
+                height: decoder.decode()?,
+                validity: decoder.decode()?,
+                validator: decoder.decode()?,
+            },
+
+            Self::STAKE => Self::Stake {
+                height: decoder.decode()?,
+                validity: decoder.decode()?,
+                validator: decoder.decode()?,
+                stake: decoder.decode()?,
+            },
+
+            Self::VOTE => Self::Vote {
+                vote: decoder.decode()?,
+            },
+
+            Self::SYNC_COMMIT => Self::SyncCommit {
+                certificate: decoder.decode()?,
+            },
+
+            Self::TIMEOUT => Self::Timeout {
+                height: decoder.decode()?,
+                round: decoder.decode()?,
+                step: decoder.decode()?,
+            },
+
+            Self::BLOCK_AUTHORIZED => Self::BlockAuthorized {
+                height: decoder.decode()?,
+                block_id: decoder.decode()?,
+            },
+
+            Self::BLOCK_PROPOSE_AUTHORIZED => Self::BlockProposeAuthorized {
+                height: decoder.decode()?,
+                block_id: decoder.decode()?,
+            },
+
+            Self::PROPOSAL => Self::Proposal {
+                proposal: decoder.decode()?,
+            },
+
+            Self::REMOVE_VALIDATOR => Self::RemoveValidator {
+                height: decoder.decode()?,
+                validator: decoder.decode()?,
+            },
+
+            Self::ROTATE_KEY => Self::RotateKey {
+                old: decoder.decode()?,
+                new: decoder.decode()?,
+                height: decoder.decode()?,
+            },
+
+            tag => return Err(DecodeError::InvalidTag { tag }),
+        };
+
+        Ok(notification)
+    }
 }
@@ -1,9 +1,12 @@
-use crate::{Height, Round, Step};
+use crate::{CommitCertificate, Decode, DecodeError, Decoder, Encode, Height, Round, Step};
 
 use fuel_crypto::PublicKey;
 
+use alloc::vec::Vec;
+
 /// A request to be responded by the reactor
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Request {
     /// The reactor should attempt to commit the provided block height
     Commit {
@@ -15,6 +18,14 @@ pub enum Request {
         round: Round,
     },
 
+    /// Query the commit certificate of a committed height
+    CommitCertificate {
+        /// Id of the request used to track its response
+        id: u64,
+        /// Committed block height
+        height: Height,
+    },
+
     /// Query the identity of the node for the provided height
     Identity {
         /// Id of the request used to track its response
@@ -45,6 +56,7 @@ impl Request {
     pub const fn id(&self) -> u64 {
         match self {
             Self::Commit { id, .. } => *id,
+            Self::CommitCertificate { id, .. } => *id,
             Self::Identity { id, .. } => *id,
             Self::Initialize { id, .. } => *id,
             Self::Round { id, .. } => *id,
@@ -52,8 +64,94 @@ impl Request {
     }
 }
 
+impl Request {
+    const COMMIT: u8 = 0x00;
+    const COMMIT_CERTIFICATE: u8 = 0x01;
+    const IDENTITY: u8 = 0x02;
+    const INITIALIZE: u8 = 0x03;
+    const ROUND: u8 = 0x04;
+}
+
+impl Encode for Request {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Commit { id, height, round } => {
+                Self::COMMIT.encode(buf);
+                id.encode(buf);
+                height.encode(buf);
+                round.encode(buf);
+            }
+
+            Self::CommitCertificate { id, height } => {
+                Self::COMMIT_CERTIFICATE.encode(buf);
+                id.encode(buf);
+                height.encode(buf);
+            }
+
+            Self::Identity { id, height } => {
+                Self::IDENTITY.encode(buf);
+                id.encode(buf);
+                height.encode(buf);
+            }
+
+            Self::Initialize {
+                id,
+                start,
+                validity,
+            } => {
+                Self::INITIALIZE.encode(buf);
+                id.encode(buf);
+                start.encode(buf);
+                validity.encode(buf);
+            }
+
+            Self::Round { id } => {
+                Self::ROUND.encode(buf);
+                id.encode(buf);
+            }
+        }
+    }
+}
+
+impl Decode for Request {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let request = match decoder.decode()? {
+            Self::COMMIT => Self::Commit {
+                id: decoder.decode()?,
+                height: decoder.decode()?,
+                round: decoder.decode()?,
+            },
+
+            Self::COMMIT_CERTIFICATE => Self::CommitCertificate {
+                id: decoder.decode()?,
+                height: decoder.decode()?,
+            },
+
+            Self::IDENTITY => Self::Identity {
+                id: decoder.decode()?,
+                height: decoder.decode()?,
+            },
+
+            Self::INITIALIZE => Self::Initialize {
+                id: decoder.decode()?,
+                start: decoder.decode()?,
+                validity: decoder.decode()?,
+            },
+
+            Self::ROUND => Self::Round {
+                id: decoder.decode()?,
+            },
+
+            tag => return Err(DecodeError::InvalidTag { tag }),
+        };
+
+        Ok(request)
+    }
+}
+
 /// Response from the reactor as result of a request
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Response {
     /// The reactor acknowledged a commit request
     Commit {
@@ -63,11 +161,23 @@ pub enum Response {
         committed: bool,
     },
 
+    /// Query the commit certificate of a committed height
+    CommitCertificate {
+        /// Id of the request used to track its response
+        id: u64,
+        /// Certificate of the height, if committed and retained by the reactor.
+        certificate: Option<CommitCertificate>,
+    },
+
     /// Query the identity of the node for the provided height
     Identity {
         /// Id of the request used to track its response
         id: u64,
         /// Public identity of the node for the provided height, if present.
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::serde_impl::option_public_key")
+        )]
         public: Option<PublicKey>,
     },
 
@@ -88,6 +198,7 @@ pub enum Response {
         /// Height round
         round: Round,
         /// Public key of the leader
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
         leader: PublicKey,
         /// Current step of the node for the round.
         step: Option<Step>,
@@ -99,9 +210,101 @@ impl Response {
     pub const fn id(&self) -> u64 {
         match self {
             Self::Commit { id, .. } => *id,
+            Self::CommitCertificate { id, .. } => *id,
             Self::Identity { id, .. } => *id,
             Self::Initialize { id, .. } => *id,
             Self::Round { id, .. } => *id,
         }
     }
 }
+
+impl Response {
+    const COMMIT: u8 = 0x00;
+    const COMMIT_CERTIFICATE: u8 = 0x01;
+    const IDENTITY: u8 = 0x02;
+    const INITIALIZE: u8 = 0x03;
+    const ROUND: u8 = 0x04;
+}
+
+impl Encode for Response {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Commit { id, committed } => {
+                Self::COMMIT.encode(buf);
+                id.encode(buf);
+                committed.encode(buf);
+            }
+
+            Self::CommitCertificate { id, certificate } => {
+                Self::COMMIT_CERTIFICATE.encode(buf);
+                id.encode(buf);
+                certificate.encode(buf);
+            }
+
+            Self::Identity { id, public } => {
+                Self::IDENTITY.encode(buf);
+                id.encode(buf);
+                public.encode(buf);
+            }
+
+            Self::Initialize { id, initialized } => {
+                Self::INITIALIZE.encode(buf);
+                id.encode(buf);
+                initialized.encode(buf);
+            }
+
+            Self::Round {
+                id,
+                height,
+                round,
+                leader,
+                step,
+            } => {
+                Self::ROUND.encode(buf);
+                id.encode(buf);
+                height.encode(buf);
+                round.encode(buf);
+                leader.encode(buf);
+                step.encode(buf);
+            }
+        }
+    }
+}
+
+impl Decode for Response {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let response = match decoder.decode()? {
+            Self::COMMIT => Self::Commit {
+                id: decoder.decode()?,
+                committed: decoder.decode()?,
+            },
+
+            Self::COMMIT_CERTIFICATE => Self::CommitCertificate {
+                id: decoder.decode()?,
+                certificate: decoder.decode()?,
+            },
+
+            Self::IDENTITY => Self::Identity {
+                id: decoder.decode()?,
+                public: decoder.decode()?,
+            },
+
+            Self::INITIALIZE => Self::Initialize {
+                id: decoder.decode()?,
+                initialized: decoder.decode()?,
+            },
+
+            Self::ROUND => Self::Round {
+                id: decoder.decode()?,
+                height: decoder.decode()?,
+                round: decoder.decode()?,
+                leader: decoder.decode()?,
+                step: decoder.decode()?,
+            },
+
+            tag => return Err(DecodeError::InvalidTag { tag }),
+        };
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,103 @@
+//! Serialization of the cryptographic primitives that don't implement `serde`.
+//!
+//! Keys and signatures are represented as their underlying [`Bytes64`].
+
+use fuel_crypto::{PublicKey, Signature};
+use fuel_types::Bytes64;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+fn to_public_key(bytes: Bytes64) -> PublicKey {
+    let mut public = PublicKey::default();
+
+    public.as_mut().copy_from_slice(bytes.as_ref());
+
+    public
+}
+
+pub(crate) mod public_key {
+    use super::*;
+
+    pub fn serialize<S>(public: &PublicKey, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Bytes64::from(<[u8; PublicKey::LEN]>::from(*public)).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<PublicKey, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Bytes64::deserialize(deserializer).map(to_public_key)
+    }
+}
+
+pub(crate) mod option_public_key {
+    use super::*;
+
+    pub fn serialize<S>(public: &Option<PublicKey>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        public
+            .map(|public| Bytes64::from(<[u8; PublicKey::LEN]>::from(public)))
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<PublicKey>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<Bytes64>::deserialize(deserializer).map(|bytes| bytes.map(to_public_key))
+    }
+}
+
+/// Maps keyed by public keys are represented as sequences, since the keys aren't strings.
+pub(crate) mod public_key_map {
+    use super::*;
+
+    pub fn serialize<S, V>(map: &BTreeMap<PublicKey, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        V: Serialize,
+    {
+        serializer.collect_seq(
+            map.iter()
+                .map(|(key, value)| (Bytes64::from(<[u8; PublicKey::LEN]>::from(*key)), value)),
+        )
+    }
+
+    pub fn deserialize<'de, D, V>(deserializer: D) -> Result<BTreeMap<PublicKey, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+    {
+        let entries: Vec<(Bytes64, V)> = Vec::deserialize(deserializer)?;
+
+        Ok(entries
+            .into_iter()
+            .map(|(key, value)| (to_public_key(key), value))
+            .collect())
+    }
+}
+
+pub(crate) mod signature {
+    use super::*;
+
+    pub fn serialize<S>(signature: &Signature, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Bytes64::from(*signature).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Signature, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Bytes64::deserialize(deserializer).map(Signature::from)
+    }
+}
@@ -1,27 +1,50 @@
-use crate::{Error, Height};
+use crate::{Decode, DecodeError, Decoder, Encode, Error, Height};
 
 use fuel_crypto::PublicKey;
 use fuel_types::Bytes64;
 use hashbrown::HashMap;
 
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
 use core::ops::{Range, RangeBounds};
 
 mod height_stakes;
 
 use height_stakes::HeightStakes;
 
+#[cfg(any(feature = "file-keychain", feature = "memory"))]
+pub(crate) use height_stakes::normalize_range;
+
 /// Registered stake for a validator
 ///
 /// The used key might not reflect the canonical validators set and will be used only to verify the
 /// signatures with the chosen protocol.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Stake {
     /// Hot key for a height range
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
     pub key: PublicKey,
     /// Staked value
     pub value: u64,
 }
 
+impl Encode for Stake {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.key.encode(buf);
+        self.value.encode(buf);
+    }
+}
+
+impl Decode for Stake {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        Ok(Self {
+            key: decoder.decode()?,
+            value: decoder.decode()?,
+        })
+    }
+}
+
 /// A stake pool, mapping a validator identity to a set of height stakes.
 ///
 /// The validator identity is agnostic to this library and the only requirement is it fits in
@@ -49,6 +72,17 @@ impl ValidatorStakes {
             .add_stake_range(bounds, stake)
     }
 
+    /// Check if a stake can be added for a validator, without changing the stakes.
+    pub fn check_stake<B>(&self, validator: &Bytes64, bounds: B, stake: Stake) -> Result<(), Error>
+    where
+        B: RangeBounds<Height>,
+    {
+        self.validators
+            .get(validator)
+            .map(|staked| staked.check_stake_range(bounds, stake))
+            .unwrap_or(Ok(()))
+    }
+
     /// Remove all stake entries for the given validator, if present
     pub fn clear(&mut self, validator: &Bytes64) {
         if let Some(staked) = self.validators.get_mut(validator) {
@@ -56,6 +90,30 @@ impl ValidatorStakes {
         }
     }
 
+    /// Restrict the stake entries of the given validator to the heights before the provided one.
+    pub fn truncate(&mut self, validator: &Bytes64, height: Height) {
+        if let Some(staked) = self.validators.get_mut(validator) {
+            staked.truncate(height)
+        }
+    }
+
+    /// Restrict the stake entries of the provided key to the heights before the provided one.
+    pub fn truncate_key(&mut self, key: &PublicKey, height: Height) {
+        self.validators
+            .values_mut()
+            .for_each(|staked| staked.truncate_key(key, height));
+    }
+
+    /// Transfer the stake entries of a key to a new key from the provided height onwards.
+    ///
+    /// The entries that are valid before the height will retain the old key for the previous
+    /// heights.
+    pub fn rotate_key(&mut self, old: &PublicKey, new: PublicKey, height: Height) {
+        self.validators
+            .values_mut()
+            .for_each(|staked| staked.rotate_key(old, new, height));
+    }
+
     /// Return a stake for a given height
     pub fn fetch(&self, validator: &Bytes64, height: Height) -> Option<&Stake> {
         self.validators
@@ -70,11 +128,40 @@ impl ValidatorStakes {
             .for_each(|staked| staked.purge_key(key));
     }
 
+    /// Remove all stake entries that are not valid after the provided height.
+    pub fn prune(&mut self, height: Height) {
+        self.validators.retain(|_, staked| {
+            staked.prune(height);
+
+            !staked.is_empty()
+        });
+    }
+
     /// Return the total staked value for a given height.
     pub fn total_staked(&self, height: Height) -> u64 {
         self.iter()
-            .filter_map(|(_, range, stake)| range.contains(&height).then(|| stake.value))
-            .sum()
+            .filter(|(_, range, _)| range.contains(&height))
+            .fold(0u64, |total, (_, _, stake)| {
+                total.saturating_add(stake.value)
+            })
+    }
+
+    /// Return the voting power of a stake key for a given height.
+    pub fn voting_power(&self, height: Height, key: &PublicKey) -> u64 {
+        self.iter()
+            .filter(|(_, range, stake)| range.contains(&height) && &stake.key == key)
+            .fold(0u64, |power, (_, _, stake)| {
+                power.saturating_add(stake.value)
+            })
+    }
+
+    /// Sorted stake keys with voting power for a given height.
+    pub fn keys_at_height(&self, height: Height) -> impl Iterator<Item = &PublicKey> {
+        self.iter()
+            .filter(|(_, range, stake)| range.contains(&height) && stake.value > 0)
+            .map(|(_, _, stake)| &stake.key)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
     }
 
     /// Iter the validator, ranges and stakes
@@ -101,3 +188,29 @@ impl ValidatorStakes {
             })
     }
 }
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ValidatorStakes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // The stakes are stored in hash maps, so they must be sorted
+        let mut stakes: Vec<_> = self.iter().collect();
+        stakes.sort_by_key(|(validator, range, _)| (*validator, range.start));
+
+        serializer.collect_seq(stakes)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ValidatorStakes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let stakes: Vec<(Bytes64, Range<Height>, Stake)> = Vec::deserialize(deserializer)?;
+
+        Self::try_from_iter(stakes).map_err(serde::de::Error::custom)
+    }
+}
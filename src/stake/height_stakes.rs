@@ -4,7 +4,6 @@ use crate::{Error, Height};
 use fuel_crypto::PublicKey;
 use hashbrown::HashMap;
 
-use alloc::borrow::ToOwned;
 use core::cmp;
 use core::ops::{Bound, Range, RangeBounds};
 
@@ -26,17 +25,10 @@ impl HeightStakes {
     {
         let bounds = normalize_range(bounds);
 
-        // Check if an equivalent stake intersects the arguments - if yes, extend it
-        if let Some(range) = self
-            .keys
-            .iter()
-            .filter(|(_, &staked)| staked == stake)
-            .find_map(|(range, _)| {
-                (range.contains(&bounds.start) || range.contains(&bounds.end)).then(|| range)
-            })
-        {
-            let range = range.to_owned();
+        self.check_range(&bounds, stake)?;
 
+        // Check if an equivalent stake intersects the arguments - if yes, extend it
+        if let Some(range) = self.equivalent_range(&bounds, stake).cloned() {
             let start = cmp::min(range.start, bounds.start);
             let end = cmp::max(range.end, bounds.end);
             let bounds = Range { start, end };
@@ -52,22 +44,48 @@ impl HeightStakes {
             return Ok(());
         }
 
+        self.keys.insert(bounds, stake);
+
+        Ok(())
+    }
+
+    /// Check if the stake can be added for the provided range, without changing the entries.
+    pub(super) fn check_stake_range<B>(&self, bounds: B, stake: Stake) -> Result<(), Error>
+    where
+        B: RangeBounds<Height>,
+    {
+        self.check_range(&normalize_range(bounds), stake)
+    }
+
+    fn check_range(&self, bounds: &Range<Height>, stake: Stake) -> Result<(), Error> {
+        if self.equivalent_range(bounds, stake).is_some() {
+            return Ok(());
+        }
+
         // Extend is not possible because either the key or value differs - intersection is forbidden
         // from this point
-        if let Some(range) = self
+        match self
             .keys
             .keys()
             .find(|range| range.contains(&bounds.start) || range.contains(&bounds.end))
         {
-            return Err(Error::DuplicatedStake {
+            Some(range) => Err(Error::DuplicatedStake {
                 height: range.start,
                 valid_before: range.end,
-            });
-        }
+            }),
 
-        self.keys.insert(bounds, stake);
+            None => Ok(()),
+        }
+    }
 
-        Ok(())
+    /// Range of an equivalent stake that intersects the provided bounds, if any.
+    fn equivalent_range(&self, bounds: &Range<Height>, stake: Stake) -> Option<&Range<Height>> {
+        self.keys
+            .iter()
+            .filter(|(_, &staked)| staked == stake)
+            .find_map(|(range, _)| {
+                (range.contains(&bounds.start) || range.contains(&bounds.end)).then_some(range)
+            })
     }
 
     /// Remove all stake entries
@@ -75,11 +93,73 @@ impl HeightStakes {
         self.keys.clear();
     }
 
+    /// Restrict all stake entries to the heights before the provided one, removing the entries that
+    /// start from it.
+    pub(super) fn truncate(&mut self, height: Height) {
+        self.keys = self
+            .keys
+            .drain()
+            .filter(|(range, _)| range.start < height)
+            .map(|(range, stake)| (range.start..cmp::min(range.end, height), stake))
+            .collect();
+    }
+
+    /// Restrict the entries of the provided key to the heights before the provided one, removing
+    /// the entries that start from it.
+    pub(super) fn truncate_key(&mut self, key: &PublicKey, height: Height) {
+        self.keys = self
+            .keys
+            .drain()
+            .filter(|(range, stake)| &stake.key != key || range.start < height)
+            .map(|(range, stake)| match &stake.key == key {
+                true => (range.start..cmp::min(range.end, height), stake),
+                false => (range, stake),
+            })
+            .collect();
+    }
+
+    /// Replace the key of the entries that are valid from the provided height onwards, splitting
+    /// the entries that are also valid before it.
+    pub(super) fn rotate_key(&mut self, old: &PublicKey, new: PublicKey, height: Height) {
+        self.keys = self
+            .keys
+            .drain()
+            .flat_map(|(range, stake)| {
+                if &stake.key != old || range.end <= height {
+                    return [Some((range, stake)), None];
+                }
+
+                let rotated = Stake { key: new, ..stake };
+
+                if range.start < height {
+                    [
+                        Some((range.start..height, stake)),
+                        Some((height..range.end, rotated)),
+                    ]
+                } else {
+                    [Some((range, rotated)), None]
+                }
+            })
+            .flatten()
+            .collect();
+    }
+
     /// Return a stake for a given height
     pub(super) fn fetch(&self, height: Height) -> Option<&Stake> {
         self.keys
             .iter()
-            .find_map(|(range, stake)| range.contains(&height).then(|| stake))
+            .find_map(|(range, stake)| range.contains(&height).then_some(stake))
+    }
+
+    /// Remove all entries that are not valid after the provided height
+    pub(super) fn prune(&mut self, height: Height) {
+        self.keys
+            .retain(|range, _| height.saturating_add(1) < range.end);
+    }
+
+    /// Check if there are no stake entries
+    pub(super) fn is_empty(&self) -> bool {
+        self.keys.is_empty()
     }
 
     /// Remove all entries with the provided key
@@ -93,7 +173,8 @@ impl HeightStakes {
     }
 }
 
-fn normalize_range<R>(bounds: R) -> Range<Height>
+/// Convert range bounds into an exclusive range, where an unbounded end is [`Height::MAX`].
+pub(crate) fn normalize_range<R>(bounds: R) -> Range<Height>
 where
     R: RangeBounds<Height>,
 {
@@ -152,11 +233,11 @@ fn stake_keys_intersect_and_merge() {
 
     let mut keys = HeightStakes::default();
     keys.add_stake_range(0..2, ax).expect("no intersect");
-    keys.add_stake_range(1..3, ay).err().expect("intersect");
+    keys.add_stake_range(1..3, ay).expect_err("intersect");
 
     let mut keys = HeightStakes::default();
     keys.add_stake_range(1..3, ax).expect("no intersect");
-    keys.add_stake_range(0..2, ay).err().expect("intersect");
+    keys.add_stake_range(0..2, ay).expect_err("intersect");
 
     let mut keys = HeightStakes::default();
     keys.add_stake_range(0..2, ax).expect("no intersect");
@@ -1,5 +1,6 @@
 /// Step of the consensus protocol.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum Step {
     /// A round just started without a proposal from a leader.
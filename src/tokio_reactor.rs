@@ -38,10 +38,9 @@ impl TokioReactor {
     /// Send a request to the reactor
     pub async fn request(&mut self, request: Request) -> Result<Response, Error> {
         let id = request.id();
-        let request = Message::Request(request);
 
         self.sender
-            .send_timeout(request, self.timeout)
+            .send_timeout(Message::Request(request), self.timeout)
             .await
             .map_err(|_| Error::ResourceNotAvailable)?;
 
@@ -65,9 +64,8 @@ impl TokioReactor {
                     if let Err(_e) = self.outbound.send(m).await {
                         #[cfg(feature = "trace")]
                         tracing::error!(
-                            "message {:?} discarded; outbound resource exhausted: {}",
-                            m,
-                            _e
+                            "message {:?} discarded; outbound resource exhausted",
+                            _e.0
                         );
 
                         return Err(Error::ResourceNotAvailable);
@@ -0,0 +1,170 @@
+use crate::{Decode, DecodeError, Decoder, Encode, Height, ValidatorStakes};
+
+use fuel_crypto::{Hasher, PublicKey};
+use fuel_types::Bytes32;
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// Validators of a height with their voting power.
+///
+/// The validators are sorted by key and have a positive voting power, so the set has a single
+/// representation and its [`ValidatorSet::hash`] is the same for every node that observed the same
+/// stakes.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ValidatorSet {
+    validators: Vec<(PublicKey, u64)>,
+}
+
+impl ValidatorSet {
+    /// Domain separation tag of the set hash.
+    pub const DOMAIN_TAG: &'static [u8] = b"fuel-bft/validator-set/v1";
+
+    /// Create a new set from `(key, voting power)` pairs.
+    ///
+    /// The voting power of repeated keys is accumulated, and the keys without voting power are
+    /// discarded.
+    pub fn new<I>(validators: I) -> Self
+    where
+        I: IntoIterator<Item = (PublicKey, u64)>,
+    {
+        let validators = validators.into_iter().fold(
+            BTreeMap::<PublicKey, u64>::new(),
+            |mut set, (key, power)| {
+                let total = set.entry(key).or_default();
+
+                *total = total.saturating_add(power);
+                set
+            },
+        );
+
+        let validators = validators
+            .into_iter()
+            .filter(|(_, power)| *power > 0)
+            .collect();
+
+        Self { validators }
+    }
+
+    /// Set of the staked keys of a height.
+    pub fn from_stakes(stakes: &ValidatorStakes, height: Height) -> Self {
+        Self::new(
+            stakes
+                .iter()
+                .filter(|(_, range, _)| range.contains(&height))
+                .map(|(_, _, stake)| (stake.key, stake.value)),
+        )
+    }
+
+    /// Commitment to the validators and their voting power.
+    pub fn hash(&self) -> Bytes32 {
+        self.validators
+            .iter()
+            .fold(
+                Hasher::default()
+                    .chain(Self::DOMAIN_TAG)
+                    .chain((self.validators.len() as u64).to_be_bytes()),
+                |hasher, (key, power)| hasher.chain(key).chain(power.to_be_bytes()),
+            )
+            .digest()
+    }
+
+    /// Voting power of a key, or zero if it isn't a validator of the set.
+    pub fn voting_power(&self, key: &PublicKey) -> u64 {
+        self.validators
+            .binary_search_by(|(k, _)| k.cmp(key))
+            .map(|idx| self.validators[idx].1)
+            .unwrap_or_default()
+    }
+
+    /// Check if the key is a validator of the set.
+    pub fn contains(&self, key: &PublicKey) -> bool {
+        self.voting_power(key) > 0
+    }
+
+    /// Sum of the voting power of the set.
+    pub fn total_power(&self) -> u64 {
+        self.validators
+            .iter()
+            .fold(0u64, |total, (_, power)| total.saturating_add(*power))
+    }
+
+    /// Number of validators.
+    pub fn len(&self) -> usize {
+        self.validators.len()
+    }
+
+    /// Check if the set has no validators.
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// Sorted keys of the validators.
+    pub fn keys(&self) -> impl Iterator<Item = &PublicKey> {
+        self.validators.iter().map(|(key, _)| key)
+    }
+
+    /// Sorted validators with their voting power.
+    pub fn as_slice(&self) -> &[(PublicKey, u64)] {
+        self.validators.as_slice()
+    }
+}
+
+impl Encode for ValidatorSet {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.validators.encode(buf);
+    }
+}
+
+impl Decode for ValidatorSet {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let validators: Vec<(PublicKey, u64)> = decoder.decode()?;
+
+        // The validators are sorted by distinct keys with voting power
+        let is_canonical = validators.iter().all(|(_, power)| *power > 0)
+            && validators.windows(2).all(|w| w[0].0 < w[1].0);
+
+        if !is_canonical {
+            return Err(DecodeError::NonCanonical);
+        }
+
+        Ok(Self { validators })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ValidatorSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use fuel_types::Bytes64;
+
+        serializer.collect_seq(
+            self.validators
+                .iter()
+                .map(|(key, power)| (Bytes64::from(<[u8; PublicKey::LEN]>::from(*key)), *power)),
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ValidatorSet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use fuel_types::Bytes64;
+
+        let validators: Vec<(Bytes64, u64)> = Vec::deserialize(deserializer)?;
+        let validators = validators.into_iter().map(|(key, power)| {
+            let mut public = PublicKey::default();
+
+            public.as_mut().copy_from_slice(key.as_ref());
+
+            (public, power)
+        });
+
+        Ok(Self::new(validators))
+    }
+}
@@ -1,29 +1,43 @@
-use crate::{Error, Height, Keychain, Round, Step};
+use crate::{
+    AsyncKeychain, ChainId, Decode, DecodeError, Decoder, Encode, Error, Height, Keychain, Round,
+    Step,
+};
 
 use fuel_crypto::{Hasher, PublicKey, SecretKey, Signature};
 use fuel_types::Bytes32;
 
+use alloc::vec::Vec;
+
 /// A vote from a validator.
 ///
-/// These votes are consumed to produce state change in the reactor.
+/// These votes are consumed to produce state change in the reactor. A vote without a block id is a
+/// nil vote.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vote {
-    block_id: Bytes32,
+    block_id: Option<Bytes32>,
     height: Height,
     round: Round,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::signature"))]
     signature: Signature,
     step: Step,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
     validator: PublicKey,
 }
 
 impl Vote {
+    /// Domain separation tag of the vote digest, including the version of the signed encoding.
+    ///
+    /// Guarantees a vote signature cannot be replayed as a signature of any other message.
+    pub const DOMAIN_TAG: &'static [u8] = b"fuel-bft/vote/v1";
+
     /// Create a new vote from a given signature
     pub const fn new(
         validator: PublicKey,
         signature: Signature,
         height: Height,
         round: Round,
-        block_id: Bytes32,
+        block_id: Option<Bytes32>,
         step: Step,
     ) -> Self {
         Self {
@@ -36,21 +50,63 @@ impl Vote {
         }
     }
 
-    fn _digest(h: Hasher, height: Height, round: Round, block_id: &Bytes32, step: Step) -> Hasher {
-        h.chain(height.to_be_bytes())
-            .chain(round.to_be_bytes())
-            .chain(block_id)
-            .chain(&[step as u8])
+    /// Canonical encoding of the signed fields of a vote.
+    fn encode_body(
+        height: Height,
+        round: Round,
+        block_id: Option<&Bytes32>,
+        step: Step,
+        buf: &mut Vec<u8>,
+    ) {
+        height.encode(buf);
+        round.encode(buf);
+
+        // The tag guarantees a nil vote is distinguishable from any block
+        block_id.encode(buf);
+        step.encode(buf);
     }
 
-    /// Compute the digest of the vote. Will be used by the signature
-    pub fn digest(&self, h: Hasher) -> Hasher {
-        Self::_digest(h, self.height, self.round, &self.block_id, self.step)
+    pub(crate) fn _digest(
+        h: Hasher,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Option<&Bytes32>,
+        step: Step,
+    ) -> Hasher {
+        let mut body = Vec::new();
+
+        Self::encode_body(height, round, block_id, step, &mut body);
+
+        h.chain(Self::DOMAIN_TAG)
+            .chain(chain_id.to_be_bytes())
+            .chain(body)
     }
 
-    /// Block Id of the step
-    pub const fn block_id(&self) -> &Bytes32 {
-        &self.block_id
+    /// Compute the digest of the vote for a given chain. Will be used by the signature
+    ///
+    /// The digest is computed over the domain tag, the chain id and the canonical encoding of the
+    /// signed fields, that is also the prefix of the vote encoding. A vote is then valid only for
+    /// the chain it was signed for.
+    pub fn digest(&self, chain_id: ChainId, h: Hasher) -> Hasher {
+        Self::_digest(
+            h,
+            chain_id,
+            self.height,
+            self.round,
+            self.block_id(),
+            self.step,
+        )
+    }
+
+    /// Block Id of the step, or `None` for a nil vote
+    pub const fn block_id(&self) -> Option<&Bytes32> {
+        self.block_id.as_ref()
+    }
+
+    /// Check if the vote is nil
+    pub const fn is_nil(&self) -> bool {
+        self.block_id.is_none()
     }
 
     /// Target block height.
@@ -81,18 +137,18 @@ impl Vote {
     /// Produce a guaranteed correctness signed vote
     pub fn signed<K>(
         keychain: &K,
+        chain_id: ChainId,
         height: Height,
         round: Round,
-        block_id: Bytes32,
+        block_id: Option<Bytes32>,
         step: Step,
     ) -> Result<Self, Error>
     where
         K: Keychain,
     {
-        let digest = Self::_digest(Hasher::default(), height, round, &block_id, step);
         let signature = keychain
-            .sign(height, digest)
-            .map_err(|_| Error::ResourceNotAvailable)?;
+            .sign_vote(chain_id, height, round, block_id.as_ref(), step)
+            .map_err(Into::into)?;
 
         let validator = keychain
             .public(height)
@@ -105,31 +161,93 @@ impl Vote {
         Ok(vote)
     }
 
+    /// Produce a guaranteed correctness signed vote with an asynchronous keychain
+    pub async fn signed_async<K>(
+        keychain: &K,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Option<Bytes32>,
+        step: Step,
+    ) -> Result<Self, Error>
+    where
+        K: AsyncKeychain,
+    {
+        let signature = keychain
+            .sign_vote_async(chain_id, height, round, block_id, step)
+            .await
+            .map_err(Into::into)?;
+
+        let validator = keychain
+            .public_async(height)
+            .await
+            .map_err(|_| Error::ResourceNotAvailable)?
+            .ok_or(Error::NotRoundValidator)?;
+
+        let vote = Self::new(validator, signature, height, round, block_id, step);
+
+        Ok(vote)
+    }
+
     /// Produce a guaranteed correctness signed vote
     pub fn signed_with_key<K>(
         secret: &SecretKey,
+        chain_id: ChainId,
         height: Height,
         round: Round,
-        block_id: Bytes32,
+        block_id: Option<Bytes32>,
         step: Step,
     ) -> Self
     where
         K: Keychain,
     {
-        let digest = Self::_digest(Hasher::default(), height, round, &block_id, step);
+        let digest = Self::_digest(
+            Hasher::default(),
+            chain_id,
+            height,
+            round,
+            block_id.as_ref(),
+            step,
+        );
         let validator = K::public_with_key(secret);
         let signature = K::sign_with_key(secret, digest);
 
         Self::new(validator, signature, height, round, block_id, step)
     }
 
-    /// Validate the signature of the vote
-    pub fn validate<K>(&self) -> Result<(), Error>
+    /// Validate the signature of the vote for a given chain
+    ///
+    /// A vote signed for a different chain will be rejected as an invalid signature.
+    pub fn validate<K>(&self, chain_id: ChainId) -> Result<(), Error>
     where
         K: Keychain,
     {
-        let digest = self.digest(Hasher::default());
+        let digest = self.digest(chain_id, Hasher::default());
 
         K::verify(self.signature, &self.validator, digest).map_err(|_| Error::InvalidSignature)
     }
 }
+
+impl Encode for Vote {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        Self::encode_body(self.height, self.round, self.block_id(), self.step, buf);
+
+        self.validator.encode(buf);
+        self.signature.encode(buf);
+    }
+}
+
+impl Decode for Vote {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let height = decoder.decode()?;
+        let round = decoder.decode()?;
+        let block_id = decoder.decode()?;
+        let step = decoder.decode()?;
+        let validator = decoder.decode()?;
+        let signature = decoder.decode()?;
+
+        Ok(Self::new(
+            validator, signature, height, round, block_id, step,
+        ))
+    }
+}
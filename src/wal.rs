@@ -0,0 +1,406 @@
+use crate::{
+    CommitCertificate, Decode, DecodeError, Decoder, Encode, Height, Proposal, Round, Stake, Vote,
+};
+
+use fuel_crypto::PublicKey;
+use fuel_types::{Bytes32, Bytes64};
+
+use alloc::vec::Vec;
+use core::fmt;
+
+#[cfg(feature = "std")]
+mod file;
+mod memory;
+
+#[cfg(feature = "std")]
+pub use file::FileWal;
+pub use memory::MemoryWal;
+
+/// State transition of the reactor, persisted before its effects are broadcast.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WalEntry {
+    /// A validator was included with a unit voting power
+    Validator {
+        /// Initial block height.
+        height: Height,
+        /// Validity period of the validator.
+        validity: u64,
+        /// Validator identifier.
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
+        validator: PublicKey,
+    },
+
+    /// A stake was registered for a validator
+    Stake {
+        /// Initial block height.
+        height: Height,
+        /// Validity period of the stake.
+        validity: u64,
+        /// Permanent identity of the validator.
+        validator: Bytes64,
+        /// Stake key and voting power.
+        stake: Stake,
+    },
+
+    /// A block was cleared for consensus.
+    BlockAuthorized {
+        /// Block height
+        height: Height,
+        /// Block identifier.
+        block_id: Bytes32,
+    },
+
+    /// A block was cleared to be proposed by the node.
+    BlockProposeAuthorized {
+        /// Block height
+        height: Height,
+        /// Block identifier.
+        block_id: Bytes32,
+    },
+
+    /// A vote was signed by the node.
+    Vote {
+        /// Signed vote
+        vote: Vote,
+    },
+
+    /// 2/3+ of the voting power prevoted a block.
+    ProofOfLock {
+        /// Block height
+        height: Height,
+        /// Round of the prevotes
+        round: Round,
+        /// Block identifier.
+        block_id: Bytes32,
+    },
+
+    /// The height moved to a new round without a commit.
+    Round {
+        /// Block height
+        height: Height,
+        /// New round of the height
+        round: Round,
+    },
+
+    /// A block was committed.
+    Commit {
+        /// Total number of rounds performed by the chain, including the committed height.
+        rounds: u64,
+        /// Votes that formed the commit quorum.
+        certificate: CommitCertificate,
+    },
+
+    /// A block proposal was signed by the node.
+    Proposal {
+        /// Signed proposal
+        proposal: Proposal,
+    },
+
+    /// A validator was removed from the set.
+    RemoveValidator {
+        /// First block height without the validator.
+        height: Height,
+        /// Validator identifier.
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
+        validator: PublicKey,
+    },
+
+    /// The hot key of a validator was replaced.
+    RotateKey {
+        /// Replaced key.
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
+        old: PublicKey,
+        /// Key of the validator from the initial height onwards.
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::public_key"))]
+        new: PublicKey,
+        /// Initial block height of the new key.
+        from_height: Height,
+    },
+
+    /// The consensus state was captured after a commit.
+    ///
+    /// The previous entries of the log are superseded by the snapshot.
+    Snapshot {
+        /// Snapshot produced by [`Reactor::snapshot`](crate::Reactor::snapshot).
+        snapshot: Vec<u8>,
+    },
+
+    /// The quorum of a block committed by the node as its leader was completed by the precommits
+    /// received afterwards.
+    Certificate {
+        /// Votes that formed the commit quorum.
+        certificate: CommitCertificate,
+    },
+}
+
+impl WalEntry {
+    const VALIDATOR: u8 = 0x00;
+    const STAKE: u8 = 0x01;
+    const BLOCK_AUTHORIZED: u8 = 0x02;
+    const BLOCK_PROPOSE_AUTHORIZED: u8 = 0x03;
+    const VOTE: u8 = 0x04;
+    const PROOF_OF_LOCK: u8 = 0x05;
+    const ROUND: u8 = 0x06;
+    const COMMIT: u8 = 0x07;
+    const PROPOSAL: u8 = 0x08;
+    const REMOVE_VALIDATOR: u8 = 0x09;
+    const ROTATE_KEY: u8 = 0x0a;
+    const SNAPSHOT: u8 = 0x0b;
+    const CERTIFICATE: u8 = 0x0c;
+
+    /// Check if the entry is irrelevant to recover the state after the provided committed height.
+    ///
+    /// Only the most recent commit is required to restore the committed height.
+    pub fn is_expired(&self, committed: Height) -> bool {
+        match self {
+            Self::Validator {
+                height, validity, ..
+            }
+            | Self::Stake {
+                height, validity, ..
+            } => height.saturating_add(*validity) <= committed,
+
+            Self::BlockAuthorized { height, .. }
+            | Self::BlockProposeAuthorized { height, .. }
+            | Self::ProofOfLock { height, .. }
+            | Self::Round { height, .. } => *height <= committed,
+
+            Self::Vote { vote } => vote.height() <= committed,
+
+            Self::Proposal { proposal } => proposal.height() <= committed,
+
+            Self::Commit { certificate, .. } | Self::Certificate { certificate } => {
+                certificate.height() < committed
+            }
+
+            // The stakes they modify might be valid for an arbitrary period, so they are discarded
+            // only once they are captured by a subsequent snapshot
+            Self::RemoveValidator { .. } | Self::RotateKey { .. } | Self::Snapshot { .. } => false,
+        }
+    }
+
+    /// Discard the entries superseded by the latest snapshot of the log, and the entries expired
+    /// after the provided committed height.
+    ///
+    /// Return `true` if any entry was discarded.
+    pub fn prune(entries: &mut Vec<Self>, committed: Height) -> bool {
+        let len = entries.len();
+
+        if let Some(snapshot) = entries
+            .iter()
+            .rposition(|e| matches!(e, Self::Snapshot { .. }))
+        {
+            entries.drain(..snapshot);
+        }
+
+        entries.retain(|e| !e.is_expired(committed));
+
+        entries.len() < len
+    }
+}
+
+impl Encode for WalEntry {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Validator {
+                height,
+                validity,
+                validator,
+            } => {
+                Self::VALIDATOR.encode(buf);
+                height.encode(buf);
+                validity.encode(buf);
+                validator.encode(buf);
+            }
+
+            Self::Stake {
+                height,
+                validity,
+                validator,
+                stake,
+            } => {
+                Self::STAKE.encode(buf);
+                height.encode(buf);
+                validity.encode(buf);
+                validator.encode(buf);
+                stake.encode(buf);
+            }
+
+            Self::BlockAuthorized { height, block_id } => {
+                Self::BLOCK_AUTHORIZED.encode(buf);
+                height.encode(buf);
+                block_id.encode(buf);
+            }
+
+            Self::BlockProposeAuthorized { height, block_id } => {
+                Self::BLOCK_PROPOSE_AUTHORIZED.encode(buf);
+                height.encode(buf);
+                block_id.encode(buf);
+            }
+
+            Self::Vote { vote } => {
+                Self::VOTE.encode(buf);
+                vote.encode(buf);
+            }
+
+            Self::ProofOfLock {
+                height,
+                round,
+                block_id,
+            } => {
+                Self::PROOF_OF_LOCK.encode(buf);
+                height.encode(buf);
+                round.encode(buf);
+                block_id.encode(buf);
+            }
+
+            Self::Round { height, round } => {
+                Self::ROUND.encode(buf);
+                height.encode(buf);
+                round.encode(buf);
+            }
+
+            Self::Commit {
+                rounds,
+                certificate,
+            } => {
+                Self::COMMIT.encode(buf);
+                rounds.encode(buf);
+                certificate.encode(buf);
+            }
+
+            Self::Proposal { proposal } => {
+                Self::PROPOSAL.encode(buf);
+                proposal.encode(buf);
+            }
+
+            Self::RemoveValidator { height, validator } => {
+                Self::REMOVE_VALIDATOR.encode(buf);
+                height.encode(buf);
+                validator.encode(buf);
+            }
+
+            Self::RotateKey {
+                old,
+                new,
+                from_height,
+            } => {
+                Self::ROTATE_KEY.encode(buf);
+                old.encode(buf);
+                new.encode(buf);
+                from_height.encode(buf);
+            }
+
+            Self::Snapshot { snapshot } => {
+                Self::SNAPSHOT.encode(buf);
+                snapshot.encode(buf);
+            }
+
+            Self::Certificate { certificate } => {
+                Self::CERTIFICATE.encode(buf);
+                certificate.encode(buf);
+            }
+        }
+    }
+}
+
+impl Decode for WalEntry {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, DecodeError> {
+        let entry = match decoder.decode()? {
+            Self::VALIDATOR => Self::Validator {
+                height: decoder.decode()?,
+                validity: decoder.decode()?,
+                validator: decoder.decode()?,
+            },
+
+            Self::STAKE => Self::Stake {
+                height: decoder.decode()?,
+                validity: decoder.decode()?,
+                validator: decoder.decode()?,
+                stake: decoder.decode()?,
+            },
+
+            Self::BLOCK_AUTHORIZED => Self::BlockAuthorized {
+                height: decoder.decode()?,
+                block_id: decoder.decode()?,
+            },
+
+            Self::BLOCK_PROPOSE_AUTHORIZED => Self::BlockProposeAuthorized {
+                height: decoder.decode()?,
+                block_id: decoder.decode()?,
+            },
+
+            Self::VOTE => Self::Vote {
+                vote: decoder.decode()?,
+            },
+
+            Self::PROOF_OF_LOCK => Self::ProofOfLock {
+                height: decoder.decode()?,
+                round: decoder.decode()?,
+                block_id: decoder.decode()?,
+            },
+
+            Self::ROUND => Self::Round {
+                height: decoder.decode()?,
+                round: decoder.decode()?,
+            },
+
+            Self::COMMIT => Self::Commit {
+                rounds: decoder.decode()?,
+                certificate: decoder.decode()?,
+            },
+
+            Self::PROPOSAL => Self::Proposal {
+                proposal: decoder.decode()?,
+            },
+
+            Self::REMOVE_VALIDATOR => Self::RemoveValidator {
+                height: decoder.decode()?,
+                validator: decoder.decode()?,
+            },
+
+            Self::ROTATE_KEY => Self::RotateKey {
+                old: decoder.decode()?,
+                new: decoder.decode()?,
+                from_height: decoder.decode()?,
+            },
+
+            Self::SNAPSHOT => Self::Snapshot {
+                snapshot: decoder.decode()?,
+            },
+
+            Self::CERTIFICATE => Self::Certificate {
+                certificate: decoder.decode()?,
+            },
+
+            tag => return Err(DecodeError::InvalidTag { tag }),
+        };
+
+        Ok(entry)
+    }
+}
+
+/// Write-ahead log of the reactor state transitions.
+///
+/// The reactor appends every own vote and state transition before it is broadcast, so a restarted
+/// node can replay the log via [`Reactor::recover`](crate::Reactor::recover) and will not sign
+/// conflicting votes.
+pub trait Wal {
+    /// Concrete error of the log.
+    type Error: fmt::Display;
+
+    /// Append an entry to the log.
+    ///
+    /// The entry must be persisted when the function returns.
+    fn append(&mut self, entry: &WalEntry) -> Result<(), Self::Error>;
+
+    /// Entries of the log, in the order they were appended.
+    fn entries(&self) -> Result<Vec<WalEntry>, Self::Error>;
+
+    /// Discard the entries that are superseded by a snapshot or expired after a committed height,
+    /// as in [`WalEntry::prune`].
+    ///
+    /// The log might defer the compaction, so the replayed entries can include expired ones.
+    fn prune(&mut self, committed: Height) -> Result<(), Self::Error>;
+}
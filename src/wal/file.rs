@@ -0,0 +1,214 @@
+use crate::{Decode, Encode, Height, Wal, WalEntry};
+
+use fuel_crypto::Hasher;
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// File-backed implementation of a write-ahead log.
+///
+/// Every entry is appended in its canonical encoding as a length-prefixed frame followed by a
+/// checksum, and synchronized to the disk before the append returns. A torn frame at the end of
+/// the file, produced by a crash during a write, is truncated when the log is opened; an invalid
+/// frame followed by valid entries is reported as corrupted data instead.
+///
+/// Pruning rewrites the whole file, so the log is compacted only after a snapshot is appended, or
+/// once it grew past the compaction threshold since the last compaction; otherwise, the expired
+/// entries are retained and replayed.
+#[derive(Debug)]
+pub struct FileWal {
+    path: PathBuf,
+    file: File,
+    len: u64,
+    compacted: u64,
+    compaction_threshold: u64,
+    snapshot: bool,
+}
+
+impl FileWal {
+    const CHECKSUM_LEN: usize = 4;
+
+    /// Growth of the log, in bytes, that triggers a compaction without a snapshot.
+    pub const DEFAULT_COMPACTION_THRESHOLD: u64 = 1 << 20;
+
+    /// Open the log at the given path, creating the file if it doesn't exist.
+    ///
+    /// A torn frame at the end of the file is truncated so new entries are appended after the
+    /// last valid frame. If an invalid frame is followed by valid entries, the file is left
+    /// untouched and an [`io::ErrorKind::InvalidData`] error is returned.
+    pub fn open<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        let (_, valid) = Self::read(&path)?;
+
+        if valid < file.metadata()?.len() {
+            file.set_len(valid)?;
+            file.sync_all()?;
+        }
+
+        Ok(Self {
+            path,
+            file,
+            len: valid,
+            compacted: valid,
+            compaction_threshold: Self::DEFAULT_COMPACTION_THRESHOLD,
+            snapshot: false,
+        })
+    }
+
+    /// Set the growth of the log, in bytes, that triggers a compaction without a snapshot.
+    pub const fn with_compaction_threshold(mut self, threshold: u64) -> Self {
+        self.compaction_threshold = threshold;
+        self
+    }
+
+    /// Path of the log file.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    fn checksum(payload: &[u8]) -> [u8; Self::CHECKSUM_LEN] {
+        let digest = Hasher::hash(payload);
+        let mut checksum = [0u8; Self::CHECKSUM_LEN];
+
+        checksum.copy_from_slice(&digest[..Self::CHECKSUM_LEN]);
+
+        checksum
+    }
+
+    fn frame(entry: &WalEntry, buf: &mut Vec<u8>) {
+        let payload = entry.to_bytes();
+
+        buf.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        buf.extend_from_slice(&payload);
+        buf.extend_from_slice(&Self::checksum(&payload));
+    }
+
+    /// Payload of the frame at the start of the bytes, and the length of the frame.
+    ///
+    /// Return `None` if the frame is incomplete or its checksum doesn't match the payload.
+    fn next_frame(bytes: &[u8]) -> Option<(&[u8], usize)> {
+        let len = bytes
+            .get(..4)
+            .map(|len| u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize)?;
+
+        let end = 4usize
+            .saturating_add(len)
+            .saturating_add(Self::CHECKSUM_LEN);
+
+        let (payload, checksum) = bytes.get(4..end)?.split_at(len);
+
+        (checksum == Self::checksum(payload)).then_some((payload, end))
+    }
+
+    /// Check if the bytes starting with an invalid frame are the tail of an interrupted append.
+    ///
+    /// A corrupted length prefix might point past the end of the file; the bytes are a torn tail
+    /// only if no valid entry is found after the start of the invalid frame.
+    fn is_torn(bytes: &[u8]) -> bool {
+        (1..bytes.len()).all(|offset| {
+            !matches!(
+                Self::next_frame(&bytes[offset..]),
+                Some((payload, _)) if WalEntry::from_bytes(payload).is_ok()
+            )
+        })
+    }
+
+    /// Entries of the log file, and the length of its valid frames.
+    fn read(path: &Path) -> io::Result<(Vec<WalEntry>, u64)> {
+        let mut bytes = Vec::new();
+
+        File::open(path)?.read_to_end(&mut bytes)?;
+
+        let mut entries = Vec::new();
+        let mut cursor = bytes.as_slice();
+
+        while !cursor.is_empty() {
+            let (payload, end) = match Self::next_frame(cursor) {
+                Some(frame) => frame,
+
+                // A crash during an append can only tear the last frame
+                None if Self::is_torn(cursor) => break,
+
+                None => return Err(invalid_data("corrupted entry")),
+            };
+
+            let entry = WalEntry::from_bytes(payload).map_err(|_| invalid_data("invalid entry"))?;
+
+            entries.push(entry);
+            cursor = &cursor[end..];
+        }
+
+        let valid = bytes.len().saturating_sub(cursor.len()) as u64;
+
+        Ok((entries, valid))
+    }
+}
+
+impl Wal for FileWal {
+    type Error = io::Error;
+
+    fn append(&mut self, entry: &WalEntry) -> Result<(), Self::Error> {
+        let mut frame = Vec::new();
+
+        Self::frame(entry, &mut frame);
+
+        self.file.write_all(&frame)?;
+        self.file.sync_data()?;
+
+        self.len = self.len.saturating_add(frame.len() as u64);
+        self.snapshot |= matches!(entry, WalEntry::Snapshot { .. });
+
+        Ok(())
+    }
+
+    fn entries(&self) -> Result<Vec<WalEntry>, Self::Error> {
+        Self::read(&self.path).map(|(entries, _)| entries)
+    }
+
+    fn prune(&mut self, committed: Height) -> Result<(), Self::Error> {
+        let grown = self.len.saturating_sub(self.compacted);
+        if !self.snapshot && grown < self.compaction_threshold {
+            return Ok(());
+        }
+
+        let mut entries = self.entries()?;
+
+        self.snapshot = false;
+        self.compacted = self.len;
+
+        // The log is rewritten only if an entry was discarded
+        if !WalEntry::prune(&mut entries, committed) {
+            return Ok(());
+        }
+
+        let mut bytes = Vec::new();
+
+        entries.iter().for_each(|e| Self::frame(e, &mut bytes));
+
+        // The log is replaced atomically so a crash will preserve either of the versions
+        let tmp = self.path.with_extension("tmp");
+
+        let mut file = File::create(&tmp)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+
+        fs::rename(&tmp, &self.path)?;
+
+        self.file = OpenOptions::new().append(true).open(&self.path)?;
+
+        self.len = bytes.len() as u64;
+        self.compacted = self.len;
+
+        Ok(())
+    }
+}
+
+fn invalid_data(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,32 @@
+use crate::{Height, Wal, WalEntry};
+
+use alloc::vec::Vec;
+use core::convert::Infallible;
+
+/// In-memory implementation of a write-ahead log.
+///
+/// The entries are lost with the process, so this is useful only for tests and ephemeral nodes.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemoryWal {
+    entries: Vec<WalEntry>,
+}
+
+impl Wal for MemoryWal {
+    type Error = Infallible;
+
+    fn append(&mut self, entry: &WalEntry) -> Result<(), Self::Error> {
+        self.entries.push(entry.clone());
+
+        Ok(())
+    }
+
+    fn entries(&self) -> Result<Vec<WalEntry>, Self::Error> {
+        Ok(self.entries.clone())
+    }
+
+    fn prune(&mut self, committed: Height) -> Result<(), Self::Error> {
+        WalEntry::prune(&mut self.entries, committed);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use async_trait::async_trait;
+use fuel_crypto::{Hasher, SecretKey};
+use fuel_types::Bytes32;
+
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Callback {
+    PrepareProposal(Height),
+    ProcessProposal(Height, Bytes32),
+    FinalizeBlock(Height, Bytes32),
+    Commit(Height),
+}
+
+#[derive(Default)]
+struct RecordingApplication {
+    callbacks: Vec<Callback>,
+    rejected: Vec<Bytes32>,
+    failing: bool,
+}
+
+impl RecordingApplication {
+    fn block(height: Height) -> Bytes32 {
+        Hasher::hash(height.to_be_bytes())
+    }
+}
+
+#[async_trait]
+impl Application for RecordingApplication {
+    async fn prepare_proposal(&mut self, height: Height) -> Result<Bytes32, Error> {
+        self.callbacks.push(Callback::PrepareProposal(height));
+
+        Ok(Self::block(height))
+    }
+
+    async fn process_proposal(&mut self, height: Height, block_id: &Bytes32) -> Result<(), Error> {
+        self.callbacks
+            .push(Callback::ProcessProposal(height, *block_id));
+
+        if self.rejected.contains(block_id) {
+            return Err(Error::BlockValidation);
+        }
+
+        Ok(())
+    }
+
+    async fn finalize_block(
+        &mut self,
+        height: Height,
+        block_id: &Bytes32,
+        certificate: Option<&CommitCertificate>,
+    ) -> Result<(), Error> {
+        if let Some(certificate) = certificate {
+            assert_eq!(height, certificate.height());
+            assert_eq!(block_id, certificate.block_id());
+        }
+
+        self.callbacks
+            .push(Callback::FinalizeBlock(height, *block_id));
+
+        if self.failing {
+            return Err(Error::ResourceNotAvailable);
+        }
+
+        Ok(())
+    }
+
+    async fn commit(&mut self, height: Height) -> Result<(), Error> {
+        self.callbacks.push(Callback::Commit(height));
+
+        if self.failing {
+            return Err(Error::ResourceNotAvailable);
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn application_drives_block_lifecycle() {
+    let passwords = ["validator_0", "validator_1", "validator_2", "validator_3"];
+    let secrets: Vec<SecretKey> = passwords.iter().map(MemoryKeychain::secret).collect();
+
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+    secrets.iter().for_each(|s| {
+        reactor
+            .add_validator(s.public_key(), 0, 10)
+            .expect("failed to add validator");
+    });
+
+    // The node is the leader of the first height
+    let leader = *reactor.leader(0).expect("failed to define leader");
+    let node = secrets
+        .iter()
+        .position(|s| s.public_key() == leader)
+        .expect("the leader is a validator");
+
+    let mut keychain = MemoryKeychain::default();
+    keychain.insert(.., passwords[node]);
+
+    let mut moderator =
+        ApplicationModerator::new(QueueModerator::default(), RecordingApplication::default());
+
+    // The block is requested from the application, and then proposed and committed
+    reactor
+        .heartbeat(&keychain, &mut moderator)
+        .await
+        .expect("heartbeat failed");
+    reactor
+        .heartbeat(&keychain, &mut moderator)
+        .await
+        .expect("heartbeat failed");
+
+    let block_id = RecordingApplication::block(0);
+
+    assert_eq!(1, reactor.height());
+    assert_eq!(
+        &[
+            Callback::PrepareProposal(0),
+            Callback::FinalizeBlock(0, block_id),
+            Callback::Commit(0),
+        ],
+        moderator.application().callbacks.as_slice()
+    );
+
+    // The messages are still dispatched to the underlying moderator
+    assert!(moderator.moderator().outbound.iter().any(|m| matches!(
+        m,
+        Message::Event(Event::Commit { height: 0, block_id: b, .. }) if b == &block_id
+    )));
+
+    // A block rejected by the application is prevoted nil
+    let proposer = *reactor.leader(0).expect("failed to define leader");
+    let proposer = secrets
+        .iter()
+        .find(|s| s.public_key() == proposer)
+        .expect("the leader is a validator");
+    assert_ne!(leader, proposer.public_key());
+
+    let rejected = Hasher::hash("rejected block");
+    moderator.application_mut().rejected.push(rejected);
+
+    let proposal =
+        Proposal::signed_with_key::<MemoryKeychain>(proposer, CHAIN_ID, 1, 0, rejected, None, 0);
+
+    reactor
+        .receive(
+            &keychain,
+            &mut moderator,
+            Message::Notification(Notification::Proposal { proposal }),
+        )
+        .await;
+
+    assert_eq!(
+        Some(&Callback::ProcessProposal(1, rejected)),
+        moderator.application().callbacks.last()
+    );
+
+    let (moderator, _) = moderator.into_inner();
+    let events: Vec<&Event> = moderator
+        .outbound
+        .iter()
+        .filter_map(|m| match m {
+            Message::Event(e) => Some(e),
+            _ => None,
+        })
+        .collect();
+
+    assert!(events.iter().any(|e| matches!(
+        e,
+        Event::InvalidBlock { proposal: p, error: Error::BlockValidation } if p == &proposal
+    )));
+    assert!(events.iter().any(|e| matches!(
+        e,
+        Event::Broadcast { vote } if vote.height() == 1 && vote.step() == Step::Prevote && vote.is_nil()
+    )));
+}
+
+#[tokio::test]
+async fn application_failure_preserves_commit_event() {
+    let mut moderator =
+        ApplicationModerator::new(QueueModerator::default(), RecordingApplication::default());
+
+    moderator.application_mut().failing = true;
+
+    let block_id = RecordingApplication::block(0);
+    let commit = Message::Event(Event::Commit {
+        height: 0,
+        round: 0,
+        block_id,
+        certificate: None,
+    });
+
+    moderator
+        .outbound(commit.clone(), Duration::from_secs(1))
+        .await
+        .expect_err("the application failure should be returned");
+
+    assert_eq!(
+        &[Callback::FinalizeBlock(0, block_id)],
+        moderator.application().callbacks.as_slice()
+    );
+    assert_eq!(&[commit], moderator.moderator().outbound.as_slice());
+}
+
+#[tokio::test]
+async fn application_skips_forced_commit_block() {
+    let passwords = ["validator_0", "validator_1", "validator_2", "validator_3"];
+
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+    passwords.iter().for_each(|p| {
+        reactor
+            .add_validator(MemoryKeychain::secret(p).public_key(), 0, 10)
+            .expect("failed to add validator");
+    });
+
+    let mut moderator =
+        ApplicationModerator::new(QueueModerator::default(), RecordingApplication::default());
+
+    assert_eq!(Ok(true), reactor.commit(&mut moderator, 0, 0).await);
+
+    assert_eq!(
+        &[Callback::Commit(0)],
+        moderator.application().callbacks.as_slice()
+    );
+    assert!(moderator.moderator().outbound.iter().any(|m| matches!(
+        m,
+        Message::Event(Event::ForcedCommit {
+            height: 0,
+            round: 0
+        })
+    )));
+}
+
+#[tokio::test]
+async fn application_failure_is_returned_to_reactor() {
+    let passwords = ["validator_0", "validator_1", "validator_2", "validator_3"];
+
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+    passwords.iter().for_each(|p| {
+        reactor
+            .add_validator(MemoryKeychain::secret(p).public_key(), 0, 10)
+            .expect("failed to add validator");
+    });
+
+    let mut moderator =
+        ApplicationModerator::new(QueueModerator::default(), RecordingApplication::default());
+
+    moderator.application_mut().failing = true;
+
+    // The height is committed, but the failure of the application is reported
+    assert_eq!(
+        Err(Error::ModeratorFailure),
+        reactor.commit(&mut moderator, 0, 0).await
+    );
+    assert_eq!(1, reactor.height());
+    assert_eq!(
+        &[Callback::Commit(0)],
+        moderator.application().callbacks.as_slice()
+    );
+}
@@ -0,0 +1,204 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use async_trait::async_trait;
+use fuel_crypto::{Hasher, PublicKey, SecretKey, Signature};
+use fuel_types::Bytes32;
+use tokio::sync::Notify;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Signer that waits for an approval before every signature, as a remote signer would wait for
+/// its response.
+struct ApprovalSigner {
+    keychain: MemoryKeychain,
+    approval: Notify,
+    requests: AtomicUsize,
+}
+
+impl ApprovalSigner {
+    fn new(keychain: MemoryKeychain) -> Self {
+        Self {
+            keychain,
+            approval: Notify::new(),
+            requests: AtomicUsize::new(0),
+        }
+    }
+
+    async fn approve(&self) {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        self.approval.notified().await;
+    }
+}
+
+#[async_trait]
+impl AsyncKeychain for ApprovalSigner {
+    type Error = Error;
+    type Verifier = MemoryKeychain;
+
+    async fn public_async(&self, height: Height) -> Result<Option<PublicKey>, Self::Error> {
+        let public = Keychain::public(&self.keychain, height)?.map(|p| p.into_owned());
+
+        Ok(public)
+    }
+
+    async fn sign_vote_async(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Option<Bytes32>,
+        step: Step,
+    ) -> Result<Signature, Self::Error> {
+        self.approve().await;
+
+        let signature = Keychain::sign_vote(
+            &self.keychain,
+            chain_id,
+            height,
+            round,
+            block_id.as_ref(),
+            step,
+        )?;
+
+        Ok(signature)
+    }
+
+    async fn sign_proposal_async(
+        &self,
+        chain_id: ChainId,
+        height: Height,
+        round: Round,
+        block_id: Bytes32,
+        pol_round: Option<Round>,
+        timestamp: u64,
+    ) -> Result<Signature, Self::Error> {
+        self.approve().await;
+
+        let signature = Keychain::sign_proposal(
+            &self.keychain,
+            chain_id,
+            height,
+            round,
+            &block_id,
+            pol_round,
+            timestamp,
+        )?;
+
+        Ok(signature)
+    }
+}
+
+#[tokio::test]
+async fn sync_keychain_is_async_keychain() {
+    let mut keychain = MemoryKeychain::default();
+    keychain.insert(.., "validator");
+
+    let block_id = Some(Hasher::hash("block"));
+
+    let vote = Vote::signed(&keychain, CHAIN_ID, 2, 1, block_id, Step::Precommit)
+        .expect("failed to sign vote");
+
+    let async_vote = Vote::signed_async(&keychain, CHAIN_ID, 2, 1, block_id, Step::Precommit)
+        .await
+        .expect("failed to sign vote");
+
+    assert_eq!(vote.validator(), async_vote.validator());
+    async_vote
+        .validate::<MemoryKeychain>(CHAIN_ID)
+        .expect("invalid signature");
+
+    assert!(keychain
+        .is_validator_for_async(2)
+        .await
+        .expect("failed to fetch key"));
+
+    // The errors of the signature are reported as consensus errors
+    let keychain = MemoryKeychain::default();
+
+    let err = Proposal::signed_async(&keychain, CHAIN_ID, 2, 1, Hasher::hash("block"), None, 0)
+        .await
+        .expect_err("no key for the height");
+
+    assert_eq!(Error::ResourceNotAvailable, err);
+}
+
+#[tokio::test]
+async fn reactor_awaits_async_keychain() {
+    let passwords = ["validator_0", "validator_1", "validator_2", "validator_3"];
+    let secrets: Vec<SecretKey> = passwords.iter().map(MemoryKeychain::secret).collect();
+
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+    secrets.iter().for_each(|s| {
+        reactor
+            .add_validator(s.public_key(), 0, 10)
+            .expect("failed to add validator");
+    });
+
+    // The node is the leader of the first height
+    let leader = *reactor.leader(0).expect("failed to define leader");
+    let node = secrets
+        .iter()
+        .position(|s| s.public_key() == leader)
+        .expect("the leader is a validator");
+
+    let mut keychain = MemoryKeychain::default();
+    keychain.insert(.., passwords[node]);
+
+    let signer = Arc::new(ApprovalSigner::new(keychain));
+    let block_id = Hasher::hash("block");
+
+    let task = {
+        let signer = Arc::clone(&signer);
+
+        tokio::spawn(async move {
+            let mut moderator = QueueModerator::default();
+
+            reactor
+                .receive(
+                    signer.as_ref(),
+                    &mut moderator,
+                    Message::Notification(Notification::BlockProposeAuthorized {
+                        height: 0,
+                        block_id,
+                    }),
+                )
+                .await;
+
+            reactor
+                .heartbeat(signer.as_ref(), &mut moderator)
+                .await
+                .expect("heartbeat failed");
+
+            (reactor, moderator)
+        })
+    };
+
+    // The reactor task is suspended while the proposal waits for the signer
+    while signer.requests.load(Ordering::SeqCst) < 1 {
+        tokio::task::yield_now().await;
+    }
+
+    assert!(!task.is_finished());
+    signer.approval.notify_one();
+
+    // Then the commit of the own block
+    while signer.requests.load(Ordering::SeqCst) < 2 {
+        tokio::task::yield_now().await;
+    }
+
+    assert!(!task.is_finished());
+    signer.approval.notify_one();
+
+    let (reactor, moderator) = task.await.expect("reactor task failed");
+
+    assert_eq!(1, reactor.height());
+    assert_eq!(2, signer.requests.load(Ordering::SeqCst));
+    assert!(moderator.outbound.iter().any(|m| matches!(
+        m,
+        Message::Event(Event::Commit { height: 0, block_id: b, .. }) if b == &block_id
+    )));
+}
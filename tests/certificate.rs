@@ -0,0 +1,100 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use fuel_crypto::{Hasher, SecretKey};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn verify() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secrets: Vec<SecretKey> = (0..4).map(|_| SecretKey::random(rng)).collect();
+
+    let stakes = ValidatorStakes::try_from_iter(secrets.iter().map(|s| {
+        let key = s.public_key();
+
+        (identity(&key), 0..10, Stake { key, value: 1 })
+    }))
+    .expect("failed to create stakes");
+
+    let height = 2;
+    let round = 1;
+    let block_id = Hasher::hash("some block");
+    let validator_set = ValidatorSet::from_stakes(&stakes, height).hash();
+
+    let vote = |secret: &SecretKey, step| {
+        Vote::signed_with_key::<MemoryKeychain>(
+            secret,
+            CHAIN_ID,
+            height,
+            round,
+            Some(block_id),
+            step,
+        )
+    };
+
+    let quorum = vec![
+        vote(&secrets[0], Step::Precommit),
+        vote(&secrets[1], Step::Commit),
+        vote(&secrets[2], Step::Precommit),
+    ];
+
+    CommitCertificate::new(height, round, block_id, validator_set, quorum.clone())
+        .verify::<MemoryKeychain>(CHAIN_ID, &stakes)
+        .expect("failed to verify certificate");
+
+    // Not enough voting power
+    let err = CommitCertificate::new(height, round, block_id, validator_set, quorum[..2].to_vec())
+        .verify::<MemoryKeychain>(CHAIN_ID, &stakes)
+        .expect_err("two out of four validators shouldn't be a quorum");
+    assert_eq!(Error::NotEnoughVotingPower, err);
+
+    // Duplicated signer
+    let mut duplicated = quorum[..2].to_vec();
+    duplicated.push(vote(&secrets[0], Step::Commit));
+    let err = CommitCertificate::new(height, round, block_id, validator_set, duplicated)
+        .verify::<MemoryKeychain>(CHAIN_ID, &stakes)
+        .expect_err("duplicated signers shouldn't be a quorum");
+    assert_eq!(Error::DuplicatedVote, err);
+
+    // Prevotes are not commit votes
+    let mut prevote = quorum[..2].to_vec();
+    prevote.push(vote(&secrets[3], Step::Prevote));
+    let err = CommitCertificate::new(height, round, block_id, validator_set, prevote)
+        .verify::<MemoryKeychain>(CHAIN_ID, &stakes)
+        .expect_err("prevotes shouldn't be part of a certificate");
+    assert_eq!(Error::VoteInconsistent, err);
+
+    // Votes from outside of the validator set
+    let mut outsider = quorum[..2].to_vec();
+    outsider.push(vote(&SecretKey::random(rng), Step::Precommit));
+    let err = CommitCertificate::new(height, round, block_id, validator_set, outsider)
+        .verify::<MemoryKeychain>(CHAIN_ID, &stakes)
+        .expect_err("outsiders shouldn't be part of a certificate");
+    assert_eq!(Error::ValidatorNotFound, err);
+
+    // Tampered signature
+    let mut tampered = quorum[..2].to_vec();
+    let v = vote(&secrets[3], Step::Precommit);
+    tampered.push(Vote::new(
+        *v.validator(),
+        *quorum[0].signature(),
+        height,
+        round,
+        Some(block_id),
+        Step::Precommit,
+    ));
+    let err = CommitCertificate::new(height, round, block_id, validator_set, tampered)
+        .verify::<MemoryKeychain>(CHAIN_ID, &stakes)
+        .expect_err("tampered signatures shouldn't be part of a certificate");
+    assert_eq!(Error::InvalidSignature, err);
+
+    // Certified for a different validator set
+    let other = ValidatorSet::from_stakes(&stakes, 10).hash();
+    let err = CommitCertificate::new(height, round, block_id, other, quorum.clone())
+        .verify::<MemoryKeychain>(CHAIN_ID, &stakes)
+        .expect_err("a certificate of a different set shouldn't be valid");
+    assert_eq!(Error::ValidatorSetMismatch, err);
+}
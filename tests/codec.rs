@@ -0,0 +1,336 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use fuel_crypto::{Hasher, PublicKey, SecretKey};
+use fuel_types::{Bytes32, Bytes64};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn vote_digest_is_encoding_prefix() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+    let block_id = Hasher::hash("some block");
+
+    for block_id in [Some(block_id), None] {
+        let vote = Vote::signed_with_key::<MemoryKeychain>(
+            &secret,
+            CHAIN_ID,
+            3,
+            2,
+            block_id,
+            Step::Prevote,
+        );
+
+        let mut body = Vec::new();
+        vote.encode(&mut body);
+        body.truncate(body.len() - PublicKey::LEN - Bytes64::LEN);
+
+        let expected = Hasher::default()
+            .chain(Vote::DOMAIN_TAG)
+            .chain(CHAIN_ID.to_be_bytes())
+            .chain(&body)
+            .digest();
+
+        assert_eq!(expected, vote.digest(CHAIN_ID, Hasher::default()).digest());
+
+        round_trip(vote);
+    }
+}
+
+#[test]
+fn messages_round_trip() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secrets: Vec<SecretKey> = (0..3).map(|_| SecretKey::random(rng)).collect();
+
+    let key = secrets[0].public_key();
+    let block_id = Hasher::hash("some block");
+    let vote =
+        Vote::signed_with_key::<MemoryKeychain>(&secrets[0], CHAIN_ID, 2, 1, None, Step::Precommit);
+    let other = Vote::signed_with_key::<MemoryKeychain>(
+        &secrets[0],
+        CHAIN_ID,
+        2,
+        1,
+        Some(block_id),
+        Step::Precommit,
+    );
+    let proposal = Proposal::signed_with_key::<MemoryKeychain>(
+        &secrets[0],
+        CHAIN_ID,
+        2,
+        1,
+        block_id,
+        Some(0),
+        1_000,
+    );
+    let certificate = certificate(
+        &secrets,
+        2,
+        1,
+        Hasher::hash("some block"),
+        Hasher::hash("validator set"),
+    );
+
+    let events = vec![
+        Event::AwaitingBlock { height: 2 },
+        Event::Idle,
+        Event::Broadcast { vote },
+        Event::Commit {
+            height: 2,
+            round: 1,
+            block_id,
+            certificate: Some(certificate.clone()),
+        },
+        Event::Commit {
+            height: 2,
+            round: 1,
+            block_id,
+            certificate: None,
+        },
+        Event::ForcedCommit {
+            height: 2,
+            round: 1,
+        },
+        Event::SyncRequired { from: 2, to: 5 },
+        Event::BadVote { vote },
+        Event::Equivocation {
+            first: vote,
+            second: other,
+        },
+        Event::Proposal { proposal },
+        Event::BadProposal { proposal },
+        Event::InvalidBlock {
+            proposal,
+            error: Error::BlockValidation,
+        },
+        Event::InvalidBlock {
+            proposal,
+            error: Error::HeightMismatch {
+                expected: 2,
+                found: 3,
+            },
+        },
+        Event::InvalidBlock {
+            proposal,
+            error: Error::DoubleSign {
+                height: 2,
+                round: 1,
+                step: Step::Prevote,
+            },
+        },
+        Event::InvalidBlock {
+            proposal,
+            error: Error::ModeratorFailure,
+        },
+    ];
+
+    let notifications = vec![
+        Notification::Kill,
+        Notification::NewValidator {
+            height: 2,
+            validity: 10,
+            validator: key,
+        },
+        Notification::Stake {
+            height: 2,
+            validity: 10,
+            validator: Bytes64::from([0xfa; Bytes64::LEN]),
+            stake: Stake { key, value: 5 },
+        },
+        Notification::Vote { vote },
+        Notification::SyncCommit {
+            certificate: certificate.clone(),
+        },
+        Notification::Timeout {
+            height: 2,
+            round: 1,
+            step: Step::Prevote,
+        },
+        Notification::BlockAuthorized {
+            height: 2,
+            block_id,
+        },
+        Notification::BlockProposeAuthorized {
+            height: 2,
+            block_id,
+        },
+        Notification::Proposal { proposal },
+        Notification::RemoveValidator {
+            height: 2,
+            validator: key,
+        },
+        Notification::RotateKey {
+            old: key,
+            new: secrets[1].public_key(),
+            height: 4,
+        },
+    ];
+
+    let requests = vec![
+        Request::Commit {
+            id: 1,
+            height: 2,
+            round: 1,
+        },
+        Request::CommitCertificate { id: 2, height: 2 },
+        Request::Identity { id: 3, height: 2 },
+        Request::Initialize {
+            id: 4,
+            start: 2,
+            validity: 10,
+        },
+        Request::Round { id: 5 },
+    ];
+
+    let responses = vec![
+        Response::Commit {
+            id: 1,
+            committed: true,
+        },
+        Response::CommitCertificate {
+            id: 2,
+            certificate: Some(certificate),
+        },
+        Response::CommitCertificate {
+            id: 2,
+            certificate: None,
+        },
+        Response::Identity {
+            id: 3,
+            public: Some(key),
+        },
+        Response::Identity {
+            id: 3,
+            public: None,
+        },
+        Response::Initialize {
+            id: 4,
+            initialized: false,
+        },
+        Response::Round {
+            id: 5,
+            height: 2,
+            round: 1,
+            leader: key,
+            step: Some(Step::Commit),
+        },
+        Response::Round {
+            id: 5,
+            height: 2,
+            round: 1,
+            leader: key,
+            step: None,
+        },
+    ];
+
+    events
+        .into_iter()
+        .map(Message::from)
+        .chain(notifications.into_iter().map(Message::from))
+        .chain(requests.into_iter().map(Message::from))
+        .chain(responses.into_iter().map(Message::Response))
+        .for_each(round_trip);
+}
+
+#[test]
+fn decode_rejects_malformed_input() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secrets: Vec<SecretKey> = (0..3).map(|_| SecretKey::random(rng)).collect();
+
+    let vote =
+        Vote::signed_with_key::<MemoryKeychain>(&secrets[0], CHAIN_ID, 2, 1, None, Step::Precommit);
+    let bytes = Message::from(Notification::Vote { vote }).to_bytes();
+
+    // Truncated
+    assert_eq!(
+        Err(DecodeError::UnexpectedEnd),
+        Message::from_bytes(&bytes[..bytes.len() - 1])
+    );
+
+    // Trailing bytes
+    let mut trailing = bytes.clone();
+    trailing.push(0x00);
+    assert_eq!(
+        Err(DecodeError::TrailingBytes),
+        Message::from_bytes(&trailing)
+    );
+
+    // Unknown version
+    let mut version = bytes.clone();
+    version[0] = 0xff;
+    assert_eq!(
+        Err(DecodeError::UnsupportedVersion { version: 0xff }),
+        Message::from_bytes(&version)
+    );
+
+    // Unknown message tag
+    let mut tag = bytes.clone();
+    tag[1] = 0x04;
+    assert_eq!(
+        Err(DecodeError::InvalidTag { tag: 0x04 }),
+        Message::from_bytes(&tag)
+    );
+
+    // Unknown step
+    let mut step = bytes;
+    step[1 + 1 + 1 + 8 + 8 + 1] = 0x05;
+    assert_eq!(
+        Err(DecodeError::InvalidTag { tag: 0x05 }),
+        Message::from_bytes(&step)
+    );
+
+    // Booleans have a single encoding
+    let mut flag = Response::Commit {
+        id: 1,
+        committed: true,
+    }
+    .to_bytes();
+    *flag.last_mut().unwrap() = 0x02;
+    assert_eq!(
+        Err(DecodeError::InvalidTag { tag: 0x02 }),
+        Response::from_bytes(&flag)
+    );
+
+    // Length exceeding the input
+    let certificate = certificate(
+        &secrets,
+        2,
+        1,
+        Hasher::hash("some block"),
+        Hasher::hash("validator set"),
+    );
+    let mut len = certificate.to_bytes();
+    len[1 + 8 + 8 + Bytes32::LEN + Bytes32::LEN..][..4].copy_from_slice(&u32::MAX.to_be_bytes());
+    assert_eq!(
+        Err(DecodeError::InvalidLength { len: u32::MAX }),
+        CommitCertificate::from_bytes(&len)
+    );
+
+    // Votes must be sorted by validator
+    let mut votes = certificate.votes().to_vec();
+    votes.swap(0, 1);
+
+    let mut unsorted = vec![CODEC_VERSION];
+    certificate.height().encode(&mut unsorted);
+    certificate.round().encode(&mut unsorted);
+    certificate.block_id().encode(&mut unsorted);
+    certificate.validator_set().encode(&mut unsorted);
+    votes.encode(&mut unsorted);
+    assert_eq!(
+        Err(DecodeError::NonCanonical),
+        CommitCertificate::from_bytes(&unsorted)
+    );
+
+    // Decoding errors map to the library errors
+    assert_eq!(
+        Error::UnsupportedVersion { version: 0xff },
+        Error::from(DecodeError::UnsupportedVersion { version: 0xff })
+    );
+    assert_eq!(
+        Error::InvalidEncoding,
+        Error::from(DecodeError::NonCanonical)
+    );
+}
@@ -0,0 +1,153 @@
+#![allow(dead_code)]
+
+use fuel_bft::*;
+
+use async_trait::async_trait;
+use fuel_crypto::{Hasher, PublicKey, SecretKey};
+use fuel_types::{Bytes32, Bytes64};
+use time::OffsetDateTime;
+
+use std::borrow::Borrow;
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// Chain identifier of the test networks.
+pub const CHAIN_ID: ChainId = 0x0ba1;
+
+/// Permanent identity of a validator derived from its key.
+pub fn identity(key: &PublicKey) -> Bytes64 {
+    Bytes64::from(<[u8; PublicKey::LEN]>::from(*key))
+}
+
+/// Assert the value is preserved by its binary encoding.
+pub fn round_trip<T>(value: T)
+where
+    T: Encode + Decode + Debug + PartialEq,
+{
+    let bytes = value.to_bytes();
+    let decoded = T::from_bytes(&bytes).expect("failed to decode value");
+
+    assert_eq!(value, decoded);
+    assert_eq!(bytes, decoded.to_bytes());
+}
+
+/// Assert the value is preserved by its serde representation.
+#[cfg(feature = "serde")]
+pub fn json_round_trip<T>(value: T)
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + Debug + PartialEq,
+{
+    let json = serde_json::to_string(&value).expect("failed to serialize value");
+    let decoded: T = serde_json::from_str(&json).expect("failed to deserialize value");
+
+    assert_eq!(value, decoded);
+}
+
+/// Certificate composed by the precommits of the signers for a block.
+pub fn certificate<K>(
+    signers: &[K],
+    height: Height,
+    round: Round,
+    block_id: Bytes32,
+    validator_set: Bytes32,
+) -> CommitCertificate
+where
+    K: Borrow<SecretKey>,
+{
+    let votes = signers.iter().map(|s| {
+        Vote::signed_with_key::<MemoryKeychain>(
+            s.borrow(),
+            CHAIN_ID,
+            height,
+            round,
+            Some(block_id),
+            Step::Precommit,
+        )
+    });
+
+    CommitCertificate::new(height, round, block_id, validator_set, votes)
+}
+
+/// Certificate composed by the precommits of the signers for the current height of the reactor,
+/// bound to its validator set.
+pub fn height_certificate<W, L, K>(
+    reactor: &Reactor<W, L>,
+    signers: &[K],
+    round: Round,
+) -> CommitCertificate
+where
+    W: Wal,
+    L: LeaderElection,
+    K: Borrow<SecretKey>,
+{
+    let height = reactor.height();
+    let block_id = Hasher::hash(height.to_be_bytes());
+    let validator_set = reactor.validator_set(height).hash();
+
+    certificate(signers, height, round, block_id, validator_set)
+}
+
+/// Moderator that queues the inbound and outbound messages in memory.
+#[derive(Default)]
+pub struct QueueModerator {
+    pub inbound: Vec<Message>,
+    pub outbound: Vec<Message>,
+}
+
+#[async_trait]
+impl Moderator for QueueModerator {
+    type Error = Error;
+
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::UNIX_EPOCH
+    }
+
+    async fn inbound(&mut self) -> Result<Option<Message>, Self::Error> {
+        self.inbound_blocking()
+    }
+
+    fn inbound_blocking(&mut self) -> Result<Option<Message>, Self::Error> {
+        Ok(self.inbound.pop())
+    }
+
+    async fn outbound(&mut self, message: Message, _timeout: Duration) -> Result<(), Self::Error> {
+        self.outbound.push(message);
+
+        Ok(())
+    }
+
+    async fn rebound(&mut self, message: Message, _timeout: Duration) -> Result<(), Self::Error> {
+        self.inbound.push(message);
+
+        Ok(())
+    }
+}
+
+/// Moderator that discards every message.
+#[derive(Default)]
+pub struct NullModerator;
+
+#[async_trait]
+impl Moderator for NullModerator {
+    type Error = Error;
+
+    fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::UNIX_EPOCH
+    }
+
+    async fn inbound(&mut self) -> Result<Option<Message>, Self::Error> {
+        Ok(None)
+    }
+
+    fn inbound_blocking(&mut self) -> Result<Option<Message>, Self::Error> {
+        Ok(None)
+    }
+
+    async fn outbound(&mut self, _message: Message, _timeout: Duration) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn rebound(&mut self, _message: Message, _timeout: Duration) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
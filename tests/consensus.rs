@@ -1,3 +1,6 @@
+mod common;
+
+use common::*;
 use fuel_bft::*;
 
 use fuel_crypto::PublicKey;
@@ -29,7 +32,7 @@ async fn consensus() {
         })
         .collect();
 
-    let config = Config::default();
+    let config = Config::new(CHAIN_ID);
     let password = "some-harder-password";
     let mut reactor = TokioReactor::spawn(config, password);
 
@@ -123,12 +126,11 @@ async fn consensus() {
             // Block authorized, expecting propose and commit
             while let Some(m) = reactor.next_async().await {
                 match m {
-                    Message::Event(Event::Broadcast { vote })
-                        if vote.height() == current_height
-                            && vote.round() == round
-                            && vote.step() == Step::Propose
-                            && vote.validator() == &public
-                            && vote.block_id() == &block_id =>
+                    Message::Event(Event::Proposal { proposal })
+                        if proposal.height() == current_height
+                            && proposal.round() == round
+                            && proposal.proposer() == &public
+                            && proposal.block_id() == &block_id =>
                     {
                         propose_found = true;
                     }
@@ -137,7 +139,7 @@ async fn consensus() {
                             && vote.round() == round
                             && vote.step() == Step::Commit
                             && vote.validator() == &public
-                            && vote.block_id() == &block_id =>
+                            && vote.block_id() == Some(&block_id) =>
                     {
                         commit_found = true;
                     }
@@ -145,6 +147,7 @@ async fn consensus() {
                         height,
                         round,
                         block_id,
+                        ..
                     }) if height == current_height
                         && round == current_round
                         && block_id == current_block_id =>
@@ -161,25 +164,15 @@ async fn consensus() {
         } else {
             let keychain = validators
                 .iter()
-                .find_map(|(k, p)| (p == &leader).then(|| k))
+                .find_map(|(k, p)| (p == &leader).then_some(k))
                 .expect("failed to fetch validator keychain");
 
-            let propose = Vote::signed(keychain, current_height, round, block_id, Step::Propose)
-                .expect("failed to create vote");
-
-            let proposer_commit =
-                Vote::signed(keychain, current_height, round, block_id, Step::Propose)
-                    .expect("failed to create vote");
-
-            reactor
-                .notify(Notification::Vote { vote: propose })
-                .await
-                .expect("failed to notify reactor");
+            let proposal =
+                Proposal::signed(keychain, CHAIN_ID, current_height, round, block_id, None, 0)
+                    .expect("failed to create proposal");
 
             reactor
-                .notify(Notification::Vote {
-                    vote: proposer_commit,
-                })
+                .notify(Notification::Proposal { proposal })
                 .await
                 .expect("failed to notify reactor");
 
@@ -230,11 +223,18 @@ async fn consensus() {
             // This is BFT consensus for 4 validators
             let keychain = validators
                 .iter()
-                .find_map(|(k, p)| (p != &leader).then(|| k))
+                .find_map(|(k, p)| (p != &leader).then_some(k))
                 .expect("failed to fetch validator keychain");
 
-            let prevote = Vote::signed(keychain, current_height, round, block_id, Step::Prevote)
-                .expect("failed to create vote");
+            let prevote = Vote::signed(
+                keychain,
+                CHAIN_ID,
+                current_height,
+                round,
+                Some(block_id),
+                Step::Prevote,
+            )
+            .expect("failed to create vote");
 
             reactor
                 .notify(Notification::Vote { vote: prevote })
@@ -258,9 +258,15 @@ async fn consensus() {
             };
 
             // One precommit vote should be enough to commit BFT
-            let precommit =
-                Vote::signed(keychain, current_height, round, block_id, Step::Precommit)
-                    .expect("failed to create vote");
+            let precommit = Vote::signed(
+                keychain,
+                CHAIN_ID,
+                current_height,
+                round,
+                Some(block_id),
+                Step::Precommit,
+            )
+            .expect("failed to create vote");
 
             reactor
                 .notify(Notification::Vote { vote: precommit })
@@ -275,7 +281,7 @@ async fn consensus() {
                             && vote.round() == current_round
                             && vote.step() == Step::Commit
                             && vote.validator() == &public
-                            && vote.block_id() == &current_block_id =>
+                            && vote.block_id() == Some(&current_block_id) =>
                     {
                         commit_found = true;
                     }
@@ -283,6 +289,7 @@ async fn consensus() {
                         height,
                         round,
                         block_id,
+                        ..
                     }) if height == current_height
                         && round == current_round
                         && block_id == current_block_id =>
@@ -305,10 +312,7 @@ async fn consensus() {
 
             match response {
                 Response::Round { height, round, .. }
-                    if height == current_height.wrapping_add(1) && round == 0 =>
-                {
-                    ()
-                }
+                    if height == current_height.wrapping_add(1) && round == 0 => {}
                 _ => panic!("unexpected round"),
             };
         }
@@ -0,0 +1,191 @@
+use fuel_bft::*;
+
+use fuel_crypto::{Hasher, PublicKey};
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const PARAMS: KdfParams = KdfParams {
+    log_n: 4,
+    r: 8,
+    p: 1,
+};
+
+fn keychain_path(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("fuel-bft-{}-{}.keychain", name, std::process::id()));
+
+    fs::remove_file(&path).ok();
+
+    path
+}
+
+fn public(keychain: &FileKeychain, height: Height) -> Option<PublicKey> {
+    keychain
+        .public(height)
+        .expect("failed to fetch public key")
+        .map(|public| public.into_owned())
+}
+
+#[test]
+fn file_keychain_persists_keys() {
+    let path = keychain_path("persist");
+
+    let a = MemoryKeychain::secret("a");
+    let b = MemoryKeychain::secret("b");
+
+    let mut keychain =
+        FileKeychain::create_with_params(&path, "passphrase", PARAMS).expect("failed to create");
+
+    keychain.insert(2..5, a).expect("failed to insert");
+    keychain.insert(5.., b).expect("failed to insert");
+
+    FileKeychain::create_with_params(&path, "passphrase", PARAMS)
+        .expect_err("an existing keychain is not overwritten");
+
+    let reopened = FileKeychain::open(&path, "passphrase").expect("failed to open");
+
+    assert_eq!(PARAMS, *reopened.params());
+    assert_eq!(
+        vec![2..5, 5..Height::MAX],
+        reopened.ranges().collect::<Vec<_>>()
+    );
+
+    assert_eq!(None, public(&reopened, 1));
+    assert_eq!(Some(a.public_key()), public(&reopened, 2));
+    assert_eq!(Some(a.public_key()), public(&reopened, 4));
+    assert_eq!(Some(b.public_key()), public(&reopened, 5));
+    assert_eq!(Some(b.public_key()), public(&reopened, Height::MAX - 1));
+
+    // Signatures are produced by the key of the height
+    let digest = Hasher::default().chain(b"vote");
+    let signature = reopened.sign(4, digest.clone()).expect("failed to sign");
+
+    FileKeychain::verify(signature, &a.public_key(), digest.clone()).expect("invalid signature");
+    FileKeychain::verify(signature, &b.public_key(), digest).expect_err("wrong key");
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn file_keychain_rejects_wrong_passphrase() {
+    let path = keychain_path("passphrase");
+
+    // The passphrase is verified even if the keychain is empty
+    FileKeychain::create_with_params(&path, "passphrase", PARAMS).expect("failed to create");
+
+    let e = FileKeychain::open(&path, "wrong").expect_err("wrong passphrase");
+    assert_eq!(io::ErrorKind::InvalidData, e.kind());
+
+    let mut keychain = FileKeychain::open(&path, "passphrase").expect("failed to open");
+    keychain
+        .insert(.., MemoryKeychain::secret("a"))
+        .expect("failed to insert");
+
+    let e = FileKeychain::open(&path, "wrong").expect_err("wrong passphrase");
+    assert_eq!(io::ErrorKind::InvalidData, e.kind());
+
+    // A tampered ciphertext is rejected
+    let mut bytes = fs::read(&path).expect("failed to read keychain");
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0x01;
+    fs::write(&path, bytes).expect("failed to write keychain");
+
+    let e = FileKeychain::open(&path, "passphrase").expect_err("tampered keychain");
+    assert_eq!(io::ErrorKind::InvalidData, e.kind());
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn file_keychain_rejects_unbounded_params() {
+    let path = keychain_path("params");
+
+    for params in [
+        KdfParams {
+            log_n: KdfParams::MAX.log_n + 1,
+            ..PARAMS
+        },
+        KdfParams {
+            r: KdfParams::MAX.r + 1,
+            ..PARAMS
+        },
+        KdfParams {
+            p: KdfParams::MAX.p + 1,
+            ..PARAMS
+        },
+    ] {
+        let e = FileKeychain::create_with_params(&path, "passphrase", params)
+            .expect_err("unbounded parameters");
+        assert_eq!(io::ErrorKind::InvalidData, e.kind());
+        assert!(!path.exists());
+    }
+
+    FileKeychain::create_with_params(&path, "passphrase", PARAMS).expect("failed to create");
+
+    // The parameters of the file are bounded before the key is derived; the version precedes the
+    // cost of the derivation
+    let mut bytes = fs::read(&path).expect("failed to read keychain");
+    bytes[1] = KdfParams::MAX.log_n + 1;
+    fs::write(&path, bytes).expect("failed to write keychain");
+
+    let e = FileKeychain::open(&path, "passphrase").expect_err("unbounded parameters");
+    assert_eq!(io::ErrorKind::InvalidData, e.kind());
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn file_keychain_retires_and_reloads_keys() {
+    let path = keychain_path("retire");
+
+    let a = MemoryKeychain::secret("a");
+    let b = MemoryKeychain::secret("b");
+
+    let mut keychain =
+        FileKeychain::create_with_params(&path, "passphrase", PARAMS).expect("failed to create");
+
+    keychain.insert(0..10, a).expect("failed to insert");
+
+    // Another handle of the same file, as used by the running reactor
+    let mut running = FileKeychain::open(&path, "passphrase").expect("failed to open");
+
+    // Retiring the middle of a range splits it
+    keychain.retire(4..6).expect("failed to retire");
+    keychain.insert(8.., b).expect("failed to insert");
+
+    assert_eq!(
+        vec![0..4, 6..8, 8..Height::MAX],
+        keychain.ranges().collect::<Vec<_>>()
+    );
+
+    assert_eq!(Some(a.public_key()), public(&running, 5));
+
+    running.reload().expect("failed to reload");
+
+    assert_eq!(Some(a.public_key()), public(&running, 3));
+    assert_eq!(None, public(&running, 4));
+    assert_eq!(None, public(&running, 5));
+    assert_eq!(Some(a.public_key()), public(&running, 6));
+    assert_eq!(Some(b.public_key()), public(&running, 8));
+    assert!(!running.is_validator_for(5).expect("failed to fetch key"));
+
+    // The retirement is persisted
+    let reopened = FileKeychain::open(&path, "passphrase").expect("failed to open");
+
+    assert_eq!(
+        running.ranges().collect::<Vec<_>>(),
+        reopened.ranges().collect::<Vec<_>>()
+    );
+
+    // A keychain replaced with another passphrase can't be reloaded
+    fs::remove_file(&path).expect("failed to remove keychain");
+    FileKeychain::create_with_params(&path, "other", PARAMS).expect("failed to create");
+
+    let e = running.reload().expect_err("different encryption key");
+    assert_eq!(io::ErrorKind::InvalidData, e.kind());
+    assert_eq!(Some(a.public_key()), public(&running, 3));
+
+    fs::remove_file(&path).ok();
+}
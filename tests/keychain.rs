@@ -0,0 +1,124 @@
+use fuel_bft::*;
+
+use fuel_crypto::{Hasher, PublicKey};
+
+fn public(keychain: &MemoryKeychain, height: Height) -> Option<PublicKey> {
+    keychain
+        .public(height)
+        .expect("failed to fetch public key")
+        .map(|public| public.into_owned())
+}
+
+#[test]
+fn memory_keychain_ranges_boundaries() {
+    let a = MemoryKeychain::secret("a").public_key();
+    let b = MemoryKeychain::secret("b").public_key();
+    let c = MemoryKeychain::secret("c").public_key();
+
+    let mut keychain = MemoryKeychain::default();
+
+    keychain.insert(2..5, "a");
+    keychain.insert(5..=7, "b");
+
+    assert_eq!(None, public(&keychain, 0));
+    assert_eq!(None, public(&keychain, 1));
+    assert_eq!(Some(a), public(&keychain, 2));
+    assert_eq!(Some(a), public(&keychain, 4));
+    assert_eq!(Some(b), public(&keychain, 5));
+    assert_eq!(Some(b), public(&keychain, 7));
+    assert_eq!(None, public(&keychain, 8));
+    assert!(!keychain
+        .is_validator_for(8)
+        .expect("failed to fetch public key"));
+
+    // Unbounded ranges
+    keychain.insert(..2, "c");
+    keychain.insert(10.., "c");
+
+    assert_eq!(Some(c), public(&keychain, Height::MIN));
+    assert_eq!(Some(c), public(&keychain, 1));
+    assert_eq!(Some(a), public(&keychain, 2));
+    assert_eq!(None, public(&keychain, 9));
+    assert_eq!(Some(c), public(&keychain, 10));
+    assert_eq!(Some(c), public(&keychain, Height::MAX - 1));
+
+    // Empty ranges are ignored
+    let count = keychain.iter().count();
+
+    keychain.insert(6..6, "a");
+    assert_eq!(count, keychain.iter().count());
+    assert_eq!(Some(b), public(&keychain, 6));
+}
+
+#[test]
+fn memory_keychain_splits_intersecting_ranges() {
+    let a = MemoryKeychain::secret("a").public_key();
+    let b = MemoryKeychain::secret("b").public_key();
+    let c = MemoryKeychain::secret("c").public_key();
+
+    // A scheduled rotation replaces the key from the provided height
+    let mut keychain = MemoryKeychain::default();
+
+    keychain.insert(.., "a");
+    keychain.insert(10.., "b");
+
+    assert_eq!(Some(a), public(&keychain, 9));
+    assert_eq!(Some(b), public(&keychain, 10));
+    assert_eq!(
+        vec![0..10, 10..Height::MAX],
+        keychain.iter().map(|(r, _)| r).collect::<Vec<_>>()
+    );
+
+    // A range inside of another splits it in two
+    keychain.insert(4..6, "c");
+
+    assert_eq!(Some(a), public(&keychain, 3));
+    assert_eq!(Some(c), public(&keychain, 4));
+    assert_eq!(Some(c), public(&keychain, 5));
+    assert_eq!(Some(a), public(&keychain, 6));
+    assert_eq!(
+        vec![0..4, 4..6, 6..10, 10..Height::MAX],
+        keychain.iter().map(|(r, _)| r).collect::<Vec<_>>()
+    );
+
+    // A range across several others replaces the covered ones
+    keychain.insert(5..=12, "b");
+
+    assert_eq!(Some(c), public(&keychain, 4));
+    assert_eq!(Some(b), public(&keychain, 5));
+    assert_eq!(Some(b), public(&keychain, 12));
+    assert_eq!(Some(b), public(&keychain, 13));
+    assert_eq!(
+        vec![0..4, 4..5, 5..13, 13..Height::MAX],
+        keychain.iter().map(|(r, _)| r).collect::<Vec<_>>()
+    );
+
+    // Reinserting a key for the whole range leaves a single entry
+    keychain.insert(.., "c");
+
+    assert_eq!(1, keychain.iter().count());
+    assert_eq!(Some(c), public(&keychain, 0));
+    assert_eq!(Some(c), public(&keychain, 13));
+}
+
+#[test]
+fn memory_keychain_signs_with_key_of_height() {
+    let mut keychain = MemoryKeychain::default();
+
+    keychain.insert(..3, "a");
+    keychain.insert(3.., "b");
+
+    let digest = Hasher::default().chain("some message");
+
+    [(2, "a"), (3, "b")]
+        .into_iter()
+        .for_each(|(height, password)| {
+            let signature = keychain
+                .sign(height, digest.clone())
+                .expect("failed to sign");
+            let public = MemoryKeychain::secret(password).public_key();
+
+            MemoryKeychain::verify(signature, &public, digest.clone())
+                .expect("the signature should be produced by the key of the height");
+        });
+}
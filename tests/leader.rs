@@ -0,0 +1,440 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use fuel_crypto::{PublicKey, SecretKey};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn sorted_keys(n: usize) -> Vec<PublicKey> {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let mut keys: Vec<PublicKey> = (0..n)
+        .map(|_| SecretKey::random(rng).public_key())
+        .collect();
+
+    keys.sort();
+    keys
+}
+
+fn sequence<'a>(
+    priority: &mut ProposerPriority,
+    names: &'a [(PublicKey, &'a str)],
+    rounds: usize,
+) -> Vec<&'a str> {
+    (0..rounds)
+        .map(|_| {
+            let proposer = priority.increment(1).expect("the set isn't empty");
+
+            names
+                .iter()
+                .find_map(|(key, name)| (key == &proposer).then_some(*name))
+                .expect("the proposer is a validator")
+        })
+        .collect()
+}
+
+#[test]
+fn proposer_priority_reference_sequence() {
+    // Reference sequence of the Tendermint proposer selection, with the names sorted as the
+    // addresses of the validators
+    let keys = sorted_keys(3);
+    let names = [(keys[0], "bar"), (keys[1], "baz"), (keys[2], "foo")];
+
+    let mut priority = ProposerPriority::default();
+    priority.update([(keys[0], 300), (keys[1], 330), (keys[2], 1000)]);
+
+    let expected = "foo baz foo bar foo foo baz foo bar foo foo baz foo foo bar foo baz foo foo bar \
+                    foo foo baz foo bar foo foo baz foo bar foo foo baz foo foo bar foo baz foo foo \
+                    bar foo baz foo foo bar foo baz foo foo bar foo baz foo foo foo baz bar foo foo \
+                    foo baz foo bar foo foo baz foo bar foo foo baz foo bar foo foo baz foo bar foo \
+                    foo baz foo foo bar foo baz foo foo bar foo baz foo foo bar foo baz foo foo";
+
+    assert_eq!(
+        expected,
+        sequence(&mut priority, &names, 99).join(" ").as_str()
+    );
+}
+
+#[test]
+fn proposer_priority_is_proportional_to_power() {
+    let keys = sorted_keys(3);
+    let names = [(keys[0], "a"), (keys[1], "b"), (keys[2], "c")];
+
+    let mut priority = ProposerPriority::default();
+    priority.update([(keys[0], 1), (keys[1], 2), (keys[2], 3)]);
+
+    // The sequence is periodic over the total voting power
+    assert_eq!(
+        vec!["c", "b", "a", "c", "b", "c", "c", "b", "a", "c", "b", "c"],
+        sequence(&mut priority, &names, 12)
+    );
+    assert!(names
+        .iter()
+        .all(|(key, _)| priority.priority(key) == Some(0)));
+
+    // The proposer lookup doesn't mutate the priorities
+    assert_eq!(Some(keys[1]), priority.proposer(1));
+    assert_eq!(Some(keys[0]), priority.proposer(2));
+    assert_eq!(Some(keys[2]), priority.proposer(0));
+}
+
+#[test]
+fn proposer_priority_equal_power_is_round_robin() {
+    let keys = sorted_keys(4);
+
+    let mut priority = ProposerPriority::default();
+    priority.update(keys.iter().map(|k| (*k, 5)));
+
+    let proposers: Vec<PublicKey> = (0..8)
+        .map(|_| priority.increment(1).expect("the set isn't empty"))
+        .collect();
+
+    let expected: Vec<PublicKey> = keys.iter().chain(keys.iter()).copied().collect();
+
+    assert_eq!(expected, proposers);
+}
+
+#[test]
+fn proposer_priority_penalizes_new_validators() {
+    let keys = sorted_keys(4);
+
+    let mut priority = ProposerPriority::default();
+    priority.update(keys[..3].iter().map(|k| (*k, 10)));
+    priority.increment(2);
+
+    // A new validator with the highest power cannot propose right after joining the set
+    priority.update(
+        keys.iter()
+            .map(|k| (*k, if k == &keys[3] { 20 } else { 10 })),
+    );
+    assert!(priority.priority(&keys[3]) < priority.priority(&keys[0]));
+    assert_ne!(Some(keys[3]), priority.increment(1));
+
+    // The priorities are centered, so the new validator will eventually propose
+    let proposers: Vec<PublicKey> = (0..5).filter_map(|_| priority.increment(1)).collect();
+    assert!(proposers.contains(&keys[3]));
+
+    // Removed validators and validators without voting power are not part of the set
+    priority.update([(keys[0], 10), (keys[1], 0)]);
+    assert_eq!(10, priority.total_power());
+    assert_eq!(None, priority.priority(&keys[1]));
+    assert_eq!(Some(keys[0]), priority.increment(3));
+
+    priority.update([]);
+    assert_eq!(None, priority.increment(1));
+}
+
+#[test]
+fn round_robin_election_ignores_voting_power() {
+    let keys = sorted_keys(3);
+    let validators = [(keys[0], 1), (keys[1], 2), (keys[2], 3)];
+    let priority = ProposerPriority::default();
+
+    // The rotation starts from the rounds performed by the chain
+    let leaders: Vec<PublicKey> = (0..6)
+        .map(|round| {
+            let election = Election::new(0, round, 4, &validators, &priority);
+
+            RoundRobin.leader(&election).expect("the set isn't empty")
+        })
+        .collect();
+
+    let expected = vec![keys[1], keys[2], keys[0], keys[1], keys[2], keys[0]];
+    assert_eq!(expected, leaders);
+
+    let election = Election::new(0, 0, 0, &[], &priority);
+    assert_eq!(None, RoundRobin.leader(&election));
+}
+
+#[test]
+fn weighted_round_robin_election_follows_proposer_priority() {
+    let keys = sorted_keys(3);
+    let validators = [(keys[0], 1), (keys[1], 2), (keys[2], 3)];
+    let priority = ProposerPriority::default();
+
+    let mut reference = ProposerPriority::default();
+    reference.update(validators);
+
+    for round in 0..12 {
+        let election = Election::new(0, round, 0, &validators, &priority);
+
+        assert_eq!(
+            reference.proposer(round),
+            WeightedRoundRobin.leader(&election)
+        );
+    }
+}
+
+/// Round-robin election over the validators in reverse order.
+struct ReverseRoundRobin;
+
+impl LeaderElection for ReverseRoundRobin {
+    fn leader(&self, election: &Election<'_>) -> Option<PublicKey> {
+        let validators = election.validators();
+        let index = election.committed_rounds().wrapping_add(election.round()) as usize;
+
+        validators
+            .iter()
+            .rev()
+            .nth(index.checked_rem(validators.len())?)
+            .map(|(key, _)| *key)
+    }
+}
+
+#[test]
+fn reactor_elects_leader_with_strategy() {
+    let keys = sorted_keys(4);
+
+    let mut round_robin = Reactor::new(Config::new(CHAIN_ID));
+    let mut reverse = Reactor::with_election(
+        Config::new(CHAIN_ID),
+        MemoryWal::default(),
+        ReverseRoundRobin,
+    );
+
+    keys.iter().for_each(|k| {
+        round_robin
+            .add_validator(*k, 0, 10)
+            .expect("failed to add validator");
+        reverse
+            .add_validator(*k, 0, 10)
+            .expect("failed to add validator");
+    });
+
+    let schedule = |f: &dyn Fn(Round) -> PublicKey| (0..32).map(f).collect::<Vec<_>>();
+
+    let round_robin = schedule(&|r| *round_robin.leader(r).expect("failed to elect leader"));
+    let reverse = schedule(&|r| *reverse.leader(r).expect("failed to elect leader"));
+
+    let expected: Vec<PublicKey> = keys.iter().cycle().take(32).copied().collect();
+    assert_eq!(expected, round_robin);
+
+    let expected: Vec<PublicKey> = keys.iter().rev().cycle().take(32).copied().collect();
+    assert_eq!(expected, reverse);
+}
+
+#[test]
+fn reactor_restores_with_strategy() {
+    let keys = sorted_keys(4);
+
+    let mut reactor = Reactor::with_election(
+        Config::new(CHAIN_ID),
+        MemoryWal::default(),
+        ReverseRoundRobin,
+    );
+
+    keys.iter().for_each(|k| {
+        reactor
+            .add_validator(*k, 0, 10)
+            .expect("failed to add validator");
+    });
+
+    let recovered = Reactor::recover_with_election(
+        Config::new(CHAIN_ID),
+        reactor.wal().clone(),
+        ReverseRoundRobin,
+    )
+    .expect("failed to recover");
+
+    let restored = Reactor::restore_with_election(
+        Config::new(CHAIN_ID),
+        MemoryWal::default(),
+        ReverseRoundRobin,
+        &reactor.snapshot(),
+    )
+    .expect("failed to restore");
+
+    for round in 0..8 {
+        let leader = reactor.leader(round).expect("failed to elect leader");
+
+        assert_eq!(
+            leader,
+            recovered.leader(round).expect("failed to elect leader")
+        );
+        assert_eq!(
+            leader,
+            restored.leader(round).expect("failed to elect leader")
+        );
+    }
+}
+
+#[test]
+fn reputation_election_skips_missed_leaders() {
+    let keys = sorted_keys(4);
+    let validators: Vec<(PublicKey, u64)> = keys.iter().map(|k| (*k, 1)).collect();
+    let priority = ProposerPriority::default();
+
+    let schedule = |strategy: &dyn LeaderElection, missed: &[PublicKey]| {
+        (0..8)
+            .map(|round| {
+                let election = Election::new(5, round, 0, &validators, &priority)
+                    .with_missed_proposals(missed);
+
+                strategy.leader(&election).expect("failed to elect leader")
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Without failures, the underlying strategy is preserved
+    let round_robin: Vec<PublicKey> = keys.iter().cycle().take(8).copied().collect();
+
+    assert_eq!(
+        round_robin,
+        schedule(&Reputation::<RoundRobin>::default(), &[])
+    );
+
+    // The validators that missed proposals are skipped
+    let missed = [keys[0], keys[2], keys[2]];
+    let expected: Vec<PublicKey> = [keys[1], keys[3]].iter().cycle().take(8).copied().collect();
+
+    assert_eq!(
+        expected,
+        schedule(&Reputation::<RoundRobin>::default(), &missed)
+    );
+
+    // The threshold tolerates a number of failures
+    let expected: Vec<PublicKey> = [keys[0], keys[1], keys[3]]
+        .iter()
+        .cycle()
+        .take(8)
+        .copied()
+        .collect();
+
+    assert_eq!(expected, schedule(&Reputation::new(RoundRobin, 2), &missed));
+    assert_eq!(
+        round_robin,
+        schedule(&Reputation::new(RoundRobin, 0), &missed)
+    );
+
+    // The full set is used if every validator would be skipped
+    assert_eq!(
+        round_robin,
+        schedule(&Reputation::<RoundRobin>::default(), &keys)
+    );
+}
+
+#[tokio::test]
+async fn reactor_skips_leaders_of_failed_rounds() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secrets: Vec<SecretKey> = (0..4).map(|_| SecretKey::random(rng)).collect();
+
+    let config = Config {
+        leader_history: 2,
+        ..Config::new(CHAIN_ID)
+    };
+
+    let mut reactor: Reactor<MemoryWal, Reputation> =
+        Reactor::with_wal(config, MemoryWal::default());
+
+    secrets.iter().for_each(|s| {
+        reactor
+            .add_validator(s.public_key(), 0, 10)
+            .expect("failed to add validator");
+    });
+
+    let schedule = |reactor: &Reactor<MemoryWal, Reputation>| {
+        (0..8)
+            .map(|r| *reactor.leader(r).expect("failed to elect leader"))
+            .collect::<Vec<_>>()
+    };
+
+    // The leader of the first round failed to propose, so the height was committed in round 1
+    let offline = *reactor.leader(0).expect("failed to elect leader");
+
+    let commit = height_certificate(&reactor, &secrets, 1);
+
+    reactor
+        .sync_commit::<MemoryKeychain, _>(&mut NullModerator, commit)
+        .await
+        .expect("failed to commit height");
+
+    assert_eq!(Some(&vec![offline]), reactor.missed_proposals().get(&0));
+    assert!(!schedule(&reactor).contains(&offline));
+
+    // All the nodes agree on the schedule, including the restored ones
+    let restored: Reactor<MemoryWal, Reputation> =
+        Reactor::restore_with_wal(config, MemoryWal::default(), &reactor.snapshot())
+            .expect("failed to restore snapshot");
+
+    assert_eq!(schedule(&reactor), schedule(&restored));
+
+    // The failure is forgotten once it leaves the history
+    let commit = height_certificate(&reactor, &secrets, 0);
+
+    reactor
+        .sync_commit::<MemoryKeychain, _>(&mut NullModerator, commit)
+        .await
+        .expect("failed to commit height");
+
+    assert!(!schedule(&reactor).contains(&offline));
+
+    let commit = height_certificate(&reactor, &secrets, 0);
+
+    reactor
+        .sync_commit::<MemoryKeychain, _>(&mut NullModerator, commit)
+        .await
+        .expect("failed to commit height");
+
+    assert!(schedule(&reactor).contains(&offline));
+}
+
+#[tokio::test]
+async fn missed_leaders_are_derived_from_certificate() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secrets: Vec<SecretKey> = (0..4).map(|_| SecretKey::random(rng)).collect();
+
+    let config = Config {
+        leader_history: 2,
+        ..Config::new(CHAIN_ID)
+    };
+
+    let reactor = || {
+        let mut reactor: Reactor<MemoryWal, Reputation> =
+            Reactor::with_wal(config, MemoryWal::default());
+
+        secrets.iter().for_each(|s| {
+            reactor
+                .add_validator(s.public_key(), 0, 10)
+                .expect("failed to add validator");
+        });
+
+        reactor
+    };
+
+    // A forced commit has no proposal, so its round is local to the node
+    let mut forced = reactor();
+
+    assert_eq!(Ok(true), forced.commit(&mut NullModerator, 0, 1).await);
+    assert!(forced.missed_proposals().is_empty());
+
+    // The replay of a synced certificate records the same failures
+    let mut synced = reactor();
+    let commit = height_certificate(&synced, &secrets, 1);
+
+    synced
+        .sync_commit::<MemoryKeychain, _>(&mut NullModerator, commit)
+        .await
+        .expect("failed to commit height");
+
+    let recovered: Reactor<MemoryWal, Reputation> =
+        Reactor::recover(config, synced.wal().clone()).expect("failed to recover");
+
+    assert_eq!(1, synced.missed_proposals().len());
+    assert_eq!(synced.missed_proposals(), recovered.missed_proposals());
+
+    // Every failed round is recorded, even if its leader failed a previous one
+    let mut delayed = reactor();
+    let leaders: Vec<PublicKey> = (0..6)
+        .map(|r| *delayed.leader(r).expect("failed to elect leader"))
+        .collect();
+
+    let commit = height_certificate(&delayed, &secrets, 6);
+
+    delayed
+        .sync_commit::<MemoryKeychain, _>(&mut NullModerator, commit)
+        .await
+        .expect("failed to commit height");
+
+    assert_eq!(Some(&leaders), delayed.missed_proposals().get(&0));
+}
@@ -0,0 +1,216 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use fuel_crypto::{Hasher, PublicKey, SecretKey};
+use fuel_types::Bytes32;
+
+const NODE: &str = "node";
+const VALIDATORS: [&str; 3] = ["validator_1", "validator_2", "validator_3"];
+
+fn keychain() -> MemoryKeychain {
+    let mut keychain = MemoryKeychain::default();
+
+    keychain.insert(.., NODE);
+
+    keychain
+}
+
+/// Log of a node that precommitted `locked` in round 1, with a proof-of-lock for `valid` in the
+/// provided round.
+fn locked_wal(locked: Bytes32, valid: Bytes32, valid_round: Round) -> MemoryWal {
+    let node = MemoryKeychain::secret(NODE);
+    let mut wal = MemoryWal::default();
+
+    let entries = [NODE]
+        .into_iter()
+        .chain(VALIDATORS)
+        .map(|password| WalEntry::Validator {
+            height: 0,
+            validity: 10,
+            validator: MemoryKeychain::secret(password).public_key(),
+        })
+        .chain([
+            WalEntry::BlockAuthorized {
+                height: 0,
+                block_id: valid,
+            },
+            WalEntry::ProofOfLock {
+                height: 0,
+                round: valid_round,
+                block_id: valid,
+            },
+            WalEntry::Vote {
+                vote: Vote::signed_with_key::<MemoryKeychain>(
+                    &node,
+                    CHAIN_ID,
+                    0,
+                    1,
+                    Some(locked),
+                    Step::Precommit,
+                ),
+            },
+        ]);
+
+    entries.for_each(|e| wal.append(&e).expect("failed to append entry"));
+
+    wal
+}
+
+/// Move the node to the first round after `after` that is led by another validator, returning
+/// the round and the secret of its leader.
+fn leader_round(wal: &mut MemoryWal, after: Round) -> (Round, SecretKey) {
+    let reactor: Reactor =
+        Reactor::recover(Config::new(CHAIN_ID), wal.clone()).expect("failed to recover");
+    let node = MemoryKeychain::secret(NODE).public_key();
+
+    let (round, leader) = (after + 1..)
+        .map(|round| (round, *reactor.leader(round).expect("no leader for round")))
+        .find(|(_, leader)| leader != &node)
+        .expect("the node leads every round");
+
+    wal.append(&WalEntry::Round { height: 0, round })
+        .expect("failed to append entry");
+
+    let secret = VALIDATORS
+        .iter()
+        .map(MemoryKeychain::secret)
+        .find(|s| s.public_key() == leader)
+        .expect("leader is not a validator");
+
+    (round, secret)
+}
+
+/// Messages sent by the node after receiving the proposal of `block_id`.
+async fn receive_proposal(
+    reactor: &mut Reactor,
+    secret: &SecretKey,
+    round: Round,
+    block_id: Bytes32,
+    pol_round: Round,
+) -> Vec<Message> {
+    let keychain = keychain();
+    let mut moderator = QueueModerator::default();
+
+    let proposal = Proposal::signed_with_key::<MemoryKeychain>(
+        secret,
+        CHAIN_ID,
+        0,
+        round,
+        block_id,
+        Some(pol_round),
+        0,
+    );
+
+    reactor
+        .receive(
+            &keychain,
+            &mut moderator,
+            Notification::Proposal { proposal }.into(),
+        )
+        .await;
+
+    moderator.outbound
+}
+
+/// Prevote of the node after receiving the proposal of `block_id`.
+async fn prevote(
+    reactor: &mut Reactor,
+    secret: &SecretKey,
+    round: Round,
+    block_id: Bytes32,
+    pol_round: Round,
+) -> Option<Bytes32> {
+    let node: PublicKey = MemoryKeychain::secret(NODE).public_key();
+
+    let vote = receive_proposal(reactor, secret, round, block_id, pol_round)
+        .await
+        .into_iter()
+        .find_map(|m| match m {
+            Message::Event(Event::Broadcast { vote })
+                if vote.step() == Step::Prevote && vote.validator() == &node =>
+            {
+                Some(vote)
+            }
+            _ => None,
+        })
+        .expect("the node didn't prevote");
+
+    assert_eq!(round, vote.round());
+
+    vote.block_id().copied()
+}
+
+#[tokio::test]
+async fn unlock_accepts_pol_of_locked_round() {
+    let locked = Hasher::hash("locked block");
+    let valid = Hasher::hash("valid block");
+
+    let mut wal = locked_wal(locked, valid, 1);
+    let (round, secret) = leader_round(&mut wal, 1);
+
+    let mut reactor: Reactor =
+        Reactor::recover(Config::new(CHAIN_ID), wal).expect("failed to recover");
+
+    assert_eq!(Some((1, &locked)), reactor.locked());
+    assert_eq!(Some((1, &valid)), reactor.valid());
+
+    let prevote = prevote(&mut reactor, &secret, round, valid, 1).await;
+
+    assert_eq!(Some(valid), prevote);
+}
+
+#[tokio::test]
+async fn unlock_rejects_pol_of_current_round() {
+    let locked = Hasher::hash("locked block");
+    let valid = Hasher::hash("valid block");
+
+    let mut wal = locked_wal(locked, valid, 0);
+    let (round, secret) = leader_round(&mut wal, 1);
+
+    // The proof-of-lock is observed in the round of the proposal
+    wal.append(&WalEntry::ProofOfLock {
+        height: 0,
+        round,
+        block_id: valid,
+    })
+    .expect("failed to append entry");
+
+    let mut reactor: Reactor =
+        Reactor::recover(Config::new(CHAIN_ID), wal).expect("failed to recover");
+
+    assert_eq!(Some((1, &locked)), reactor.locked());
+    assert_eq!(Some((round, &valid)), reactor.valid());
+
+    let outbound = receive_proposal(&mut reactor, &secret, round, valid, round).await;
+
+    assert!(outbound
+        .iter()
+        .any(|m| matches!(m, Message::Event(Event::BadProposal { .. }))));
+
+    assert!(!outbound
+        .iter()
+        .any(|m| matches!(m, Message::Event(Event::Broadcast { .. }))));
+}
+
+#[tokio::test]
+async fn unlock_rejects_pol_round_without_quorum() {
+    let locked = Hasher::hash("locked block");
+    let valid = Hasher::hash("valid block");
+
+    let mut wal = locked_wal(locked, valid, 2);
+    let (round, secret) = leader_round(&mut wal, 2);
+
+    let mut reactor: Reactor =
+        Reactor::recover(Config::new(CHAIN_ID), wal).expect("failed to recover");
+
+    assert_eq!(Some((1, &locked)), reactor.locked());
+    assert_eq!(Some((2, &valid)), reactor.valid());
+
+    // The proposal claims a proof-of-lock in the locked round, but the 2/3+ prevotes for the block
+    // were observed in a later round
+    let prevote = prevote(&mut reactor, &secret, round, valid, 1).await;
+
+    assert_eq!(None, prevote);
+}
@@ -0,0 +1,272 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use fuel_crypto::{Hasher, SecretKey};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn proposal_signature_binds_fields() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+    let block_id = Hasher::hash("some block");
+
+    let proposal = Proposal::signed_with_key::<MemoryKeychain>(
+        &secret,
+        CHAIN_ID,
+        1,
+        2,
+        block_id,
+        Some(1),
+        1_000,
+    );
+
+    assert_eq!(&secret.public_key(), proposal.proposer());
+    assert_eq!(Some(1), proposal.pol_round());
+    assert_eq!(1_000, proposal.timestamp());
+
+    proposal
+        .validate::<MemoryKeychain>(CHAIN_ID)
+        .expect("failed to validate proposal");
+
+    // A proposal signed for a chain is not valid for any other chain
+    assert_eq!(
+        Err(Error::InvalidSignature),
+        proposal.validate::<MemoryKeychain>(CHAIN_ID + 1)
+    );
+
+    // The proof-of-lock round and the timestamp are part of the signed digest
+    let forged = [
+        Proposal::new(
+            *proposal.proposer(),
+            *proposal.signature(),
+            1,
+            2,
+            block_id,
+            None,
+            1_000,
+        ),
+        Proposal::new(
+            *proposal.proposer(),
+            *proposal.signature(),
+            1,
+            2,
+            block_id,
+            Some(1),
+            1_001,
+        ),
+    ];
+
+    for p in forged {
+        assert_eq!(
+            Err(Error::InvalidSignature),
+            p.validate::<MemoryKeychain>(CHAIN_ID)
+        );
+    }
+}
+
+#[test]
+fn proposal_differs_from_vote_digest() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+    let block_id = Hasher::hash("some block");
+
+    let proposal =
+        Proposal::signed_with_key::<MemoryKeychain>(&secret, CHAIN_ID, 1, 2, block_id, None, 0);
+
+    // A proposal signature cannot be replayed as a vote
+    let vote = Vote::new(
+        *proposal.proposer(),
+        *proposal.signature(),
+        1,
+        2,
+        Some(block_id),
+        Step::Propose,
+    );
+
+    assert_eq!(
+        Err(Error::InvalidSignature),
+        vote.validate::<MemoryKeychain>(CHAIN_ID)
+    );
+}
+
+#[tokio::test]
+async fn leader_proposes_once_after_new_round_quorum() {
+    const PASSWORDS: [&str; 4] = ["validator_0", "validator_1", "validator_2", "validator_3"];
+
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+
+    PASSWORDS.iter().for_each(|p| {
+        reactor
+            .add_validator(MemoryKeychain::secret(p).public_key(), 0, 10)
+            .expect("failed to add validator");
+    });
+
+    let leader = *reactor.leader(0).expect("failed to elect leader");
+    let (leader_password, others): (Vec<&str>, Vec<&str>) = PASSWORDS
+        .iter()
+        .partition(|p| MemoryKeychain::secret(p).public_key() == leader);
+
+    let mut keychain = MemoryKeychain::default();
+    keychain.insert(.., leader_password[0]);
+
+    let mut moderator = QueueModerator::default();
+
+    // The leader reaches a new round quorum before the block is authorized
+    for p in others {
+        let vote = Vote::signed_with_key::<MemoryKeychain>(
+            &MemoryKeychain::secret(p),
+            CHAIN_ID,
+            0,
+            0,
+            None,
+            Step::NewRound,
+        );
+
+        reactor
+            .receive(
+                &keychain,
+                &mut moderator,
+                Notification::Vote { vote }.into(),
+            )
+            .await;
+    }
+
+    assert_eq!(Some(Step::NewRound), reactor.validator_step(0, 0, &leader));
+
+    let authorized = Notification::BlockProposeAuthorized {
+        height: 0,
+        block_id: Hasher::hash("some block"),
+    };
+
+    reactor
+        .receive(&keychain, &mut moderator, authorized.into())
+        .await;
+
+    // Every heartbeat of the leader checks the proposal of the round
+    for _ in 0..2 {
+        reactor
+            .heartbeat(&keychain, &mut moderator)
+            .await
+            .expect("failed to run heartbeat");
+    }
+
+    let proposals = moderator
+        .outbound
+        .iter()
+        .filter(|m| matches!(m, Message::Event(Event::Proposal { .. })))
+        .count();
+
+    let propose_votes = moderator.outbound.iter().any(
+        |m| matches!(m, Message::Event(Event::Broadcast { vote }) if vote.step().is_propose()),
+    );
+
+    assert_eq!(1, proposals);
+    assert!(!propose_votes);
+}
+
+#[tokio::test]
+async fn leader_certifies_own_commit_once_precommits_arrive() {
+    const PASSWORDS: [&str; 4] = ["validator_0", "validator_1", "validator_2", "validator_3"];
+
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+
+    PASSWORDS.iter().for_each(|p| {
+        reactor
+            .add_validator(MemoryKeychain::secret(p).public_key(), 0, 10)
+            .expect("failed to add validator");
+    });
+
+    let leader = *reactor.leader(0).expect("failed to elect leader");
+    let (leader_password, others): (Vec<&str>, Vec<&str>) = PASSWORDS
+        .iter()
+        .partition(|p| MemoryKeychain::secret(p).public_key() == leader);
+
+    let mut keychain = MemoryKeychain::default();
+    keychain.insert(.., leader_password[0]);
+
+    let mut moderator = QueueModerator::default();
+    let block_id = Hasher::hash("some block");
+
+    let authorized = Notification::BlockProposeAuthorized {
+        height: 0,
+        block_id,
+    };
+
+    reactor
+        .receive(&keychain, &mut moderator, authorized.into())
+        .await;
+
+    reactor
+        .heartbeat(&keychain, &mut moderator)
+        .await
+        .expect("failed to run heartbeat");
+
+    // The leader commits its own block without a quorum
+    assert_eq!(1, reactor.height());
+    assert!(reactor.certificate(0).is_none());
+
+    let precommits: Vec<Vote> = others
+        .iter()
+        .map(|p| {
+            Vote::signed_with_key::<MemoryKeychain>(
+                &MemoryKeychain::secret(p),
+                CHAIN_ID,
+                0,
+                0,
+                Some(block_id),
+                Step::Precommit,
+            )
+        })
+        .collect();
+
+    // A precommit of another block doesn't count for the certificate
+    let conflicting = Vote::signed_with_key::<MemoryKeychain>(
+        &MemoryKeychain::secret(others[0]),
+        CHAIN_ID,
+        0,
+        0,
+        Some(Hasher::hash("other block")),
+        Step::Precommit,
+    );
+
+    for vote in [conflicting, precommits[0]] {
+        reactor
+            .receive(
+                &keychain,
+                &mut moderator,
+                Notification::Vote { vote }.into(),
+            )
+            .await;
+    }
+
+    assert!(reactor.certificate(0).is_none());
+
+    let vote = precommits[1];
+
+    reactor
+        .receive(
+            &keychain,
+            &mut moderator,
+            Notification::Vote { vote }.into(),
+        )
+        .await;
+
+    let certificate = reactor
+        .certificate(0)
+        .expect("the precommits should certify the commit")
+        .clone();
+
+    assert_eq!(3, certificate.votes().len());
+    certificate
+        .verify::<MemoryKeychain>(CHAIN_ID, reactor.stakes())
+        .expect("failed to verify certificate");
+
+    // The completed certificate is recovered from the log
+    let recovered: Reactor = Reactor::recover(Config::new(CHAIN_ID), reactor.wal().clone())
+        .expect("failed to recover reactor");
+
+    assert_eq!(Some(&certificate), recovered.certificate(0));
+}
@@ -0,0 +1,360 @@
+use fuel_bft::*;
+
+use fuel_crypto::Hasher;
+use fuel_types::Bytes32;
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const CHAIN_ID: ChainId = 7;
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn signer_keychain() -> MemoryKeychain {
+    let mut keychain = MemoryKeychain::default();
+
+    keychain.insert(1..10, "signer");
+
+    keychain
+}
+
+async fn check_remote<S>(remote: &RemoteKeychain<S>)
+where
+    S: Read + Write + Send + 'static,
+{
+    let expected = MemoryKeychain::secret("signer").public_key();
+
+    let public = remote
+        .public_async(3)
+        .await
+        .expect("failed to fetch public key");
+
+    assert_eq!(Some(expected), public);
+    assert!(!remote
+        .is_validator_for_async(10)
+        .await
+        .expect("failed to fetch key"));
+
+    let block_id = Bytes32::from([0x11; 32]);
+
+    let vote = Vote::signed_async(remote, CHAIN_ID, 3, 1, Some(block_id), Step::Prevote)
+        .await
+        .expect("failed to sign vote");
+
+    assert_eq!(&expected, vote.validator());
+    vote.validate::<MemoryKeychain>(CHAIN_ID)
+        .expect("invalid vote signature");
+    vote.validate::<MemoryKeychain>(CHAIN_ID + 1)
+        .expect_err("vote signed for another chain");
+
+    let nil = Vote::signed_async(remote, CHAIN_ID, 3, 1, None, Step::Precommit)
+        .await
+        .expect("failed to sign nil vote");
+
+    nil.validate::<MemoryKeychain>(CHAIN_ID)
+        .expect("invalid vote signature");
+
+    let proposal = Proposal::signed_async(remote, CHAIN_ID, 3, 0, block_id, None, 1000)
+        .await
+        .expect("failed to sign proposal");
+
+    assert_eq!(&expected, proposal.proposer());
+    proposal
+        .validate::<MemoryKeychain>(CHAIN_ID)
+        .expect("invalid proposal signature");
+
+    // No key for the height
+    Vote::signed_async(remote, CHAIN_ID, 10, 0, None, Step::Prevote)
+        .await
+        .expect_err("no key for the height");
+
+    assert!(remote.is_connected());
+}
+
+#[tokio::test]
+async fn remote_signer_tcp_loopback() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+    let addr = listener.local_addr().expect("failed to fetch address");
+
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("failed to accept");
+
+        SignerServer::new(signer_keychain()).serve(stream)
+    });
+
+    let remote = RemoteKeychain::connect_tcp(addr, TIMEOUT)
+        .await
+        .expect("failed to connect");
+
+    check_remote(&remote).await;
+
+    drop(remote);
+
+    server
+        .join()
+        .expect("server panicked")
+        .expect("server failed");
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn remote_signer_unix_loopback() {
+    use std::os::unix::net::UnixListener;
+
+    let path = std::env::temp_dir().join(format!("fuel-bft-signer-{}.sock", std::process::id()));
+    std::fs::remove_file(&path).ok();
+
+    let listener = UnixListener::bind(&path).expect("failed to bind");
+
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("failed to accept");
+
+        SignerServer::new(signer_keychain()).serve(stream)
+    });
+
+    let remote = RemoteKeychain::connect_unix(&path, TIMEOUT)
+        .await
+        .expect("failed to connect");
+
+    check_remote(&remote).await;
+
+    drop(remote);
+
+    server
+        .join()
+        .expect("server panicked")
+        .expect("server failed");
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn remote_signer_connection_failure() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+    let addr = listener.local_addr().expect("failed to fetch address");
+
+    // The signer closes the connection without answering
+    let server = thread::spawn(move || {
+        listener.accept().expect("failed to accept");
+    });
+
+    let remote = RemoteKeychain::connect_tcp(addr, TIMEOUT)
+        .await
+        .expect("failed to connect");
+
+    server.join().expect("server panicked");
+
+    let e = remote.public_async(3).await.expect_err("connection closed");
+
+    assert_eq!(fuel_crypto::Error::KeystoreNotAvailable, e);
+    assert!(!remote.is_connected());
+}
+
+#[tokio::test]
+async fn remote_signer_timeout() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+    let addr = listener.local_addr().expect("failed to fetch address");
+
+    // The signer accepts the connection, but never answers
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("failed to accept");
+
+        thread::sleep(Duration::from_secs(2));
+
+        drop(stream);
+    });
+
+    let remote = RemoteKeychain::connect_tcp(addr, Duration::from_millis(100))
+        .await
+        .expect("failed to connect");
+
+    let start = Instant::now();
+    let e = remote
+        .public_async(3)
+        .await
+        .expect_err("signer didn't answer");
+
+    assert_eq!(fuel_crypto::Error::KeystoreNotAvailable, e);
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert!(!remote.is_connected());
+
+    server.join().expect("server panicked");
+}
+
+#[tokio::test]
+async fn remote_signer_doesnt_block_runtime() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+    let addr = listener.local_addr().expect("failed to fetch address");
+
+    // The signer answers after a delay
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("failed to accept");
+
+        thread::sleep(Duration::from_millis(500));
+
+        SignerServer::new(signer_keychain()).serve(stream)
+    });
+
+    let remote = RemoteKeychain::connect_tcp(addr, TIMEOUT)
+        .await
+        .expect("failed to connect");
+
+    // The single thread of the runtime progresses other tasks while the request is pending
+    let (public, ticked) = tokio::join!(remote.public_async(3), async {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        Instant::now()
+    });
+
+    let answered = Instant::now();
+
+    assert!(public.expect("failed to fetch public key").is_some());
+    assert!(answered.duration_since(ticked) > Duration::from_millis(100));
+
+    drop(remote);
+
+    server
+        .join()
+        .expect("server panicked")
+        .expect("server failed");
+}
+
+#[tokio::test]
+async fn remote_signer_reconnects() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+    let addr = listener.local_addr().expect("failed to fetch address");
+
+    // The first connection is closed without answering; the following ones are served
+    let server = thread::spawn(move || {
+        drop(listener.accept().expect("failed to accept"));
+
+        for _ in 0..2 {
+            let (stream, _) = listener.accept().expect("failed to accept");
+
+            SignerServer::new(signer_keychain())
+                .serve(stream)
+                .expect("server failed");
+        }
+    });
+
+    let remote = RemoteKeychain::connect_tcp(addr, TIMEOUT)
+        .await
+        .expect("failed to connect");
+
+    remote.public_async(3).await.expect_err("connection closed");
+    assert!(!remote.is_connected());
+
+    // The next request connects again
+    check_remote(&remote).await;
+
+    // The connection can be replaced explicitly
+    remote.reconnect().await.expect("failed to reconnect");
+    check_remote(&remote).await;
+
+    drop(remote);
+
+    server.join().expect("server panicked");
+
+    // A keychain without a connector can only be reconnected with a new stream
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+    let addr = listener.local_addr().expect("failed to fetch address");
+
+    let remote = RemoteKeychain::new(TcpStream::connect(addr).expect("failed to connect"));
+
+    assert_eq!(
+        std::io::ErrorKind::NotConnected,
+        remote.reconnect().await.expect_err("no connector").kind()
+    );
+
+    remote
+        .reconnect_with(TcpStream::connect(addr).expect("failed to connect"))
+        .await
+        .expect("failed to reconnect");
+    assert!(remote.is_connected());
+}
+
+#[test]
+fn remote_signer_requires_runtime() {
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+    let addr = listener.local_addr().expect("failed to fetch address");
+
+    let remote = RemoteKeychain::new(TcpStream::connect(addr).expect("failed to connect"));
+
+    // The request fails instead of panicking if it isn't polled by a tokio runtime
+    let mut request = pin!(remote.request(SignerRequest::PublicKey { height: 3 }));
+
+    match request
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(Err(e)) => assert_eq!(std::io::ErrorKind::Unsupported, e.kind()),
+        _ => panic!("the request shouldn't run outside of a runtime"),
+    }
+}
+
+#[test]
+fn remote_signer_protocol_codec() {
+    let signature = <MemoryKeychain as Keychain>::sign_with_key(
+        &MemoryKeychain::secret("signer"),
+        Hasher::default().chain(b"message"),
+    );
+
+    let requests = [
+        SignerRequest::PublicKey { height: 3 },
+        SignerRequest::SignVote {
+            chain_id: CHAIN_ID,
+            height: 3,
+            round: 1,
+            block_id: None,
+            step: Step::Precommit,
+        },
+        SignerRequest::SignProposal {
+            chain_id: CHAIN_ID,
+            height: 3,
+            round: 1,
+            block_id: Bytes32::from([0x11; 32]),
+            pol_round: Some(0),
+            timestamp: 1000,
+        },
+    ];
+
+    requests.iter().for_each(|request| {
+        let decoded = SignerRequest::from_bytes(&request.to_bytes()).expect("failed to decode");
+
+        assert_eq!(request, &decoded);
+    });
+
+    let responses = [
+        SignerResponse::PublicKey {
+            key: Some(MemoryKeychain::secret("signer").public_key()),
+        },
+        SignerResponse::PublicKey { key: None },
+        SignerResponse::Signature { signature },
+        SignerResponse::KeyNotFound,
+        SignerResponse::Rejected,
+    ];
+
+    responses.iter().for_each(|response| {
+        let decoded = SignerResponse::from_bytes(&response.to_bytes()).expect("failed to decode");
+
+        assert_eq!(response, &decoded);
+    });
+
+    // The reference server answers the requests with its keychain
+    let server = SignerServer::new(signer_keychain());
+
+    assert_eq!(
+        SignerResponse::KeyNotFound,
+        server.handle(&SignerRequest::PublicKey { height: 0 })
+    );
+
+    match server.handle(&requests[1]) {
+        SignerResponse::Signature { .. } => (),
+        response => panic!("unexpected response {:?}", response),
+    }
+}
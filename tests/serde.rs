@@ -0,0 +1,345 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use fuel_crypto::{Hasher, SecretKey};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn types_round_trip() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secrets: Vec<SecretKey> = (0..3).map(|_| SecretKey::random(rng)).collect();
+
+    let key = secrets[0].public_key();
+    let block_id = Hasher::hash("some block");
+    let vote =
+        Vote::signed_with_key::<MemoryKeychain>(&secrets[0], CHAIN_ID, 2, 1, None, Step::Precommit);
+    let other = Vote::signed_with_key::<MemoryKeychain>(
+        &secrets[0],
+        CHAIN_ID,
+        2,
+        1,
+        Some(block_id),
+        Step::Precommit,
+    );
+
+    let proposal = Proposal::signed_with_key::<MemoryKeychain>(
+        &secrets[0],
+        CHAIN_ID,
+        2,
+        1,
+        block_id,
+        Some(0),
+        1_000,
+    );
+    let votes = secrets.iter().map(|s| {
+        Vote::signed_with_key::<MemoryKeychain>(s, CHAIN_ID, 2, 1, Some(block_id), Step::Precommit)
+    });
+    let certificate = CommitCertificate::new(2, 1, block_id, Hasher::hash("validator set"), votes);
+
+    json_round_trip(vote);
+    json_round_trip(other);
+    json_round_trip(proposal);
+    json_round_trip(certificate.clone());
+    json_round_trip(Config::new(CHAIN_ID));
+    json_round_trip(Stake { key, value: 5 });
+    json_round_trip(ValidatorSet::new(
+        secrets.iter().map(|s| (s.public_key(), 2)),
+    ));
+
+    // The priorities are negative once the validators propose
+    let mut priority = ProposerPriority::default();
+    priority.update(
+        secrets
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.public_key(), i as u64 + 1)),
+    );
+    priority.increment(4);
+
+    assert!(priority.iter().any(|(_, _, p)| p < 0));
+    json_round_trip(priority);
+    json_round_trip(Error::DuplicatedStake {
+        height: 2,
+        valid_before: 5,
+    });
+    json_round_trip(Error::DoubleSign {
+        height: 2,
+        round: 1,
+        step: Step::Precommit,
+    });
+    json_round_trip(DecodeError::InvalidTag { tag: 0x05 });
+
+    [
+        Step::NewRound,
+        Step::Propose,
+        Step::Prevote,
+        Step::Precommit,
+        Step::Commit,
+    ]
+    .into_iter()
+    .for_each(json_round_trip);
+
+    [
+        WalEntry::Validator {
+            height: 2,
+            validity: 10,
+            validator: key,
+        },
+        WalEntry::Vote { vote },
+        WalEntry::Proposal { proposal },
+        WalEntry::RemoveValidator {
+            height: 4,
+            validator: key,
+        },
+        WalEntry::RotateKey {
+            old: key,
+            new: secrets[1].public_key(),
+            from_height: 4,
+        },
+        WalEntry::Commit {
+            rounds: 3,
+            certificate: certificate.clone(),
+        },
+        WalEntry::Snapshot {
+            snapshot: Reactor::new(Config::new(CHAIN_ID)).snapshot(),
+        },
+        WalEntry::Certificate {
+            certificate: certificate.clone(),
+        },
+    ]
+    .into_iter()
+    .for_each(json_round_trip);
+
+    let messages: Vec<Message> = vec![
+        Event::AwaitingBlock { height: 2 }.into(),
+        Event::Idle.into(),
+        Event::Broadcast { vote }.into(),
+        Event::Commit {
+            height: 2,
+            round: 1,
+            block_id,
+            certificate: Some(certificate.clone()),
+        }
+        .into(),
+        Event::Commit {
+            height: 2,
+            round: 1,
+            block_id,
+            certificate: None,
+        }
+        .into(),
+        Event::ForcedCommit {
+            height: 2,
+            round: 1,
+        }
+        .into(),
+        Event::SyncRequired { from: 2, to: 5 }.into(),
+        Event::BadVote { vote }.into(),
+        Event::Equivocation {
+            first: vote,
+            second: other,
+        }
+        .into(),
+        Event::Proposal { proposal }.into(),
+        Event::BadProposal { proposal }.into(),
+        Event::InvalidBlock {
+            proposal,
+            error: Error::BlockValidation,
+        }
+        .into(),
+        Notification::Kill.into(),
+        Notification::NewValidator {
+            height: 2,
+            validity: 10,
+            validator: key,
+        }
+        .into(),
+        Notification::Stake {
+            height: 2,
+            validity: 10,
+            validator: identity(&key),
+            stake: Stake { key, value: 5 },
+        }
+        .into(),
+        Notification::Vote { vote }.into(),
+        Notification::SyncCommit {
+            certificate: certificate.clone(),
+        }
+        .into(),
+        Notification::Timeout {
+            height: 2,
+            round: 1,
+            step: Step::Prevote,
+        }
+        .into(),
+        Notification::BlockAuthorized {
+            height: 2,
+            block_id,
+        }
+        .into(),
+        Notification::BlockProposeAuthorized {
+            height: 2,
+            block_id,
+        }
+        .into(),
+        Notification::Proposal { proposal }.into(),
+        Notification::RemoveValidator {
+            height: 2,
+            validator: key,
+        }
+        .into(),
+        Notification::RotateKey {
+            old: key,
+            new: secrets[1].public_key(),
+            height: 4,
+        }
+        .into(),
+        Request::Commit {
+            id: 1,
+            height: 2,
+            round: 1,
+        }
+        .into(),
+        Request::CommitCertificate { id: 2, height: 2 }.into(),
+        Request::Identity { id: 3, height: 2 }.into(),
+        Request::Initialize {
+            id: 4,
+            start: 2,
+            validity: 10,
+        }
+        .into(),
+        Request::Round { id: 5 }.into(),
+        Message::Response(Response::Commit {
+            id: 1,
+            committed: true,
+        }),
+        Message::Response(Response::CommitCertificate {
+            id: 2,
+            certificate: Some(certificate),
+        }),
+        Message::Response(Response::Identity {
+            id: 3,
+            public: Some(key),
+        }),
+        Message::Response(Response::Identity {
+            id: 3,
+            public: None,
+        }),
+        Message::Response(Response::Initialize {
+            id: 4,
+            initialized: false,
+        }),
+        Message::Response(Response::Round {
+            id: 5,
+            height: 2,
+            round: 1,
+            leader: key,
+            step: Some(Step::Commit),
+        }),
+    ];
+
+    messages.into_iter().for_each(json_round_trip);
+}
+
+#[test]
+#[cfg(feature = "file-keychain")]
+fn kdf_params_round_trip() {
+    json_round_trip(KdfParams::RECOMMENDED);
+    json_round_trip(KdfParams::MAX);
+}
+
+#[test]
+#[cfg(feature = "remote-signer")]
+fn signer_messages_round_trip() {
+    let secret = MemoryKeychain::secret("signer");
+    let block_id = Hasher::hash("some block");
+    let vote =
+        Vote::signed_with_key::<MemoryKeychain>(&secret, CHAIN_ID, 2, 1, None, Step::Precommit);
+
+    [
+        SignerRequest::PublicKey { height: 2 },
+        SignerRequest::SignVote {
+            chain_id: CHAIN_ID,
+            height: 2,
+            round: 1,
+            block_id: None,
+            step: Step::Precommit,
+        },
+        SignerRequest::SignVote {
+            chain_id: CHAIN_ID,
+            height: 2,
+            round: 1,
+            block_id: Some(block_id),
+            step: Step::Prevote,
+        },
+        SignerRequest::SignProposal {
+            chain_id: CHAIN_ID,
+            height: 2,
+            round: 1,
+            block_id,
+            pol_round: Some(0),
+            timestamp: 1_000,
+        },
+    ]
+    .into_iter()
+    .for_each(json_round_trip);
+
+    [
+        SignerResponse::PublicKey {
+            key: Some(secret.public_key()),
+        },
+        SignerResponse::PublicKey { key: None },
+        SignerResponse::Signature {
+            signature: *vote.signature(),
+        },
+        SignerResponse::KeyNotFound,
+        SignerResponse::Rejected,
+    ]
+    .into_iter()
+    .for_each(json_round_trip);
+}
+
+#[test]
+fn validator_stakes_round_trip() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secrets: Vec<SecretKey> = (0..3).map(|_| SecretKey::random(rng)).collect();
+
+    let stakes = ValidatorStakes::try_from_iter(secrets.iter().enumerate().map(|(i, s)| {
+        let key = s.public_key();
+
+        (identity(&key), i as u64..10, Stake { key, value: 1 })
+    }))
+    .expect("failed to create stakes");
+
+    json_round_trip(stakes.clone());
+
+    // The serialization doesn't depend on the order of the hash maps
+    let reversed = ValidatorStakes::try_from_iter(
+        stakes
+            .iter()
+            .map(|(validator, range, stake)| (*validator, range.clone(), *stake))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev(),
+    )
+    .expect("failed to create stakes");
+
+    assert_eq!(
+        serde_json::to_string(&stakes).unwrap(),
+        serde_json::to_string(&reversed).unwrap()
+    );
+
+    // Conflicting stakes are rejected
+    let key = secrets[0].public_key();
+    let conflicting = serde_json::to_string(&[
+        (identity(&key), 0..10, Stake { key, value: 1 }),
+        (identity(&key), 5..15, Stake { key, value: 2 }),
+    ])
+    .unwrap();
+
+    serde_json::from_str::<ValidatorStakes>(&conflicting)
+        .expect_err("conflicting stakes shouldn't be deserialized");
+}
@@ -0,0 +1,256 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use fuel_crypto::{Hasher, SecretKey};
+use fuel_types::Bytes32;
+
+use std::fs;
+use std::path::PathBuf;
+
+fn record_path(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("fuel-bft-{}-{}.signed", name, std::process::id()));
+
+    fs::remove_file(&path).ok();
+
+    path
+}
+
+fn vote<K>(
+    keychain: &K,
+    height: Height,
+    round: Round,
+    block_id: Option<Bytes32>,
+    step: Step,
+) -> Result<Vote, Error>
+where
+    K: Keychain,
+{
+    Vote::signed(keychain, CHAIN_ID, height, round, block_id, step)
+}
+
+#[test]
+fn slashing_protection_refuses_conflicting_votes() {
+    let mut keychain = MemoryKeychain::default();
+    keychain.insert(.., "validator");
+
+    let keychain = SlashingProtection::new(keychain);
+
+    let a = Hasher::hash("a");
+    let b = Hasher::hash("b");
+
+    let prevote = vote(&keychain, 5, 1, Some(a), Step::Prevote).expect("failed to sign");
+
+    prevote
+        .validate::<MemoryKeychain>(CHAIN_ID)
+        .expect("invalid signature");
+
+    assert_eq!(
+        Some(LastSigned {
+            height: 5,
+            round: 1,
+            step: Step::Prevote,
+            digest: prevote.digest(CHAIN_ID, Hasher::default()).digest(),
+        }),
+        keychain.last_signed()
+    );
+
+    // The same vote can be signed again
+    vote(&keychain, 5, 1, Some(a), Step::Prevote).expect("same vote");
+
+    let double_sign = Err(Error::DoubleSign {
+        height: 5,
+        round: 1,
+        step: Step::Prevote,
+    });
+
+    assert_eq!(double_sign, vote(&keychain, 5, 1, Some(b), Step::Prevote));
+    assert_eq!(double_sign, vote(&keychain, 5, 1, None, Step::Prevote));
+
+    // Regressions are refused
+    vote(&keychain, 5, 1, Some(a), Step::Propose).expect_err("previous step");
+    vote(&keychain, 5, 0, Some(a), Step::Precommit).expect_err("previous round");
+    vote(&keychain, 4, 3, Some(a), Step::Commit).expect_err("previous height");
+
+    // Proposals are ordered as the propose step
+    Proposal::signed(&keychain, CHAIN_ID, 5, 1, a, None, 0).expect_err("previous step");
+
+    vote(&keychain, 5, 1, Some(a), Step::Precommit).expect("next step");
+    vote(&keychain, 5, 2, None, Step::NewRound).expect("next round");
+
+    Proposal::signed(&keychain, CHAIN_ID, 5, 2, b, Some(1), 0).expect("next step");
+    Proposal::signed(&keychain, CHAIN_ID, 5, 2, a, None, 0).expect_err("conflicting proposal");
+
+    let new_round = vote(&keychain, 6, 0, None, Step::NewRound).expect("next height");
+
+    // The rejected signatures didn't change the record
+    assert_eq!(
+        Some(LastSigned {
+            height: 6,
+            round: 0,
+            step: Step::NewRound,
+            digest: new_round.digest(CHAIN_ID, Hasher::default()).digest(),
+        }),
+        keychain.last_signed()
+    );
+
+    // Opaque digests are not signed
+    keychain
+        .sign(7, Hasher::default().chain(b"digest"))
+        .expect_err("opaque digest");
+}
+
+#[test]
+fn slashing_protection_compares_signed_messages() {
+    let mut keychain = MemoryKeychain::default();
+    keychain.insert(.., "validator");
+
+    let keychain = SlashingProtection::new(keychain);
+
+    let a = Hasher::hash("a");
+
+    let proposal =
+        Proposal::signed(&keychain, CHAIN_ID, 5, 1, a, Some(0), 1_000).expect("failed to sign");
+
+    assert_eq!(
+        Some(LastSigned {
+            height: 5,
+            round: 1,
+            step: Step::Propose,
+            digest: proposal.digest(CHAIN_ID, Hasher::default()).digest(),
+        }),
+        keychain.last_signed()
+    );
+
+    // The same proposal can be signed again
+    Proposal::signed(&keychain, CHAIN_ID, 5, 1, a, Some(0), 1_000).expect("same proposal");
+
+    let double_sign = Some(Error::DoubleSign {
+        height: 5,
+        round: 1,
+        step: Step::Propose,
+    });
+
+    // A proposal of the same block with other signed fields is a conflicting message
+    assert_eq!(
+        double_sign,
+        Proposal::signed(&keychain, CHAIN_ID, 5, 1, a, Some(0), 2_000).err()
+    );
+    assert_eq!(
+        double_sign,
+        Proposal::signed(&keychain, CHAIN_ID, 5, 1, a, None, 1_000).err()
+    );
+
+    // A vote of the same step and block is not the signed proposal
+    assert_eq!(
+        double_sign,
+        vote(&keychain, 5, 1, Some(a), Step::Propose).err()
+    );
+
+    // Neither is the same message for another chain
+    assert_eq!(
+        double_sign,
+        Proposal::signed(&keychain, CHAIN_ID + 1, 5, 1, a, Some(0), 1_000).err()
+    );
+
+    vote(&keychain, 5, 1, Some(a), Step::Prevote).expect("next step");
+}
+
+#[tokio::test]
+async fn slashing_protection_persists_across_restarts() {
+    let path = record_path("restart");
+
+    let passwords = ["validator_0", "validator_1", "validator_2", "validator_3"];
+    let secrets: Vec<SecretKey> = passwords.iter().map(MemoryKeychain::secret).collect();
+
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+    secrets.iter().for_each(|s| {
+        reactor
+            .add_validator(s.public_key(), 0, 10)
+            .expect("failed to add validator");
+    });
+
+    // The node is the leader of the first height
+    let leader = *reactor.leader(0).expect("failed to define leader");
+    let node = secrets
+        .iter()
+        .position(|s| s.public_key() == leader)
+        .expect("the leader is a validator");
+
+    let mut memory = MemoryKeychain::default();
+    memory.insert(.., passwords[node]);
+
+    let keychain =
+        SlashingProtection::open(memory.clone(), &path).expect("failed to open protection");
+
+    assert_eq!(Some(path.as_path()), keychain.path());
+    assert_eq!(None, keychain.last_signed());
+
+    let block_id = Hasher::hash("block");
+    let mut moderator = QueueModerator::default();
+
+    reactor
+        .receive(
+            &keychain,
+            &mut moderator,
+            Message::Notification(Notification::BlockProposeAuthorized {
+                height: 0,
+                block_id,
+            }),
+        )
+        .await;
+
+    reactor
+        .heartbeat(&keychain, &mut moderator)
+        .await
+        .expect("heartbeat failed");
+
+    // The protected keychain signed the proposal and the commit of the own block
+    assert_eq!(1, reactor.height());
+    assert!(moderator.outbound.iter().any(|m| matches!(
+        m,
+        Message::Event(Event::Commit { height: 0, block_id: b, .. }) if b == &block_id
+    )));
+
+    let last = LastSigned {
+        height: 0,
+        round: 0,
+        step: Step::Commit,
+        digest: Vote::signed_with_key::<MemoryKeychain>(
+            &secrets[node],
+            CHAIN_ID,
+            0,
+            0,
+            Some(block_id),
+            Step::Commit,
+        )
+        .digest(CHAIN_ID, Hasher::default())
+        .digest(),
+    };
+
+    assert_eq!(Some(last), keychain.last_signed());
+
+    // A restarted node can't sign a conflicting vote
+    drop(keychain);
+
+    let keychain = SlashingProtection::open(memory, &path).expect("failed to open protection");
+
+    assert_eq!(Some(last), keychain.last_signed());
+    assert_eq!(
+        Err(Error::DoubleSign {
+            height: 0,
+            round: 0,
+            step: Step::Commit,
+        }),
+        vote(&keychain, 0, 0, Some(Hasher::hash("other")), Step::Commit)
+    );
+
+    // A corrupted record is not silently discarded
+    fs::write(&path, [0xff]).expect("failed to corrupt record");
+
+    SlashingProtection::open(MemoryKeychain::default(), &path).expect_err("corrupted record");
+
+    fs::remove_file(&path).ok();
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use fuel_crypto::SecretKey;
+use fuel_types::Bytes64;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn stakes(rng: &mut StdRng) -> Vec<(Bytes64, Height, u64, Stake)> {
+    (0..8u8)
+        .map(|i| {
+            let key = SecretKey::random(rng).public_key();
+            let validator = Bytes64::from([i; Bytes64::LEN]);
+            let stake = Stake {
+                key,
+                value: i as u64 + 1,
+            };
+
+            (validator, i as Height, 10, stake)
+        })
+        .collect()
+}
+
+#[test]
+fn snapshot_is_deterministic() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let stakes = stakes(rng);
+
+    let mut a = Reactor::new(Config::new(CHAIN_ID));
+    let mut b = Reactor::new(Config::new(CHAIN_ID));
+
+    stakes
+        .iter()
+        .for_each(|(validator, height, validity, stake)| {
+            a.stake(*validator, *height, *validity, *stake)
+                .expect("failed to stake");
+        });
+
+    stakes
+        .iter()
+        .rev()
+        .for_each(|(validator, height, validity, stake)| {
+            b.stake(*validator, *height, *validity, *stake)
+                .expect("failed to stake");
+        });
+
+    assert_eq!(a.snapshot(), b.snapshot());
+
+    let snapshot = a.snapshot();
+    let restored = Reactor::restore(Config::new(CHAIN_ID), &snapshot).expect("failed to restore");
+
+    assert_eq!(snapshot, restored.snapshot());
+    assert_eq!(a.stakes(), restored.stakes());
+    assert_eq!(a.height(), restored.height());
+}
+
+#[test]
+fn snapshot_rejects_invalid_encoding() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+
+    stakes(rng)
+        .into_iter()
+        .for_each(|(validator, height, validity, stake)| {
+            reactor
+                .stake(validator, height, validity, stake)
+                .expect("failed to stake");
+        });
+
+    let snapshot = reactor.snapshot();
+
+    // The snapshot is prefixed by the version of its encoding
+    assert_eq!(0x01, snapshot[0]);
+
+    let mut unsupported = snapshot.clone();
+    unsupported[0] = 0xff;
+    let err = Reactor::restore(Config::new(CHAIN_ID), &unsupported)
+        .expect_err("unknown versions shouldn't be restored");
+    assert_eq!(Error::UnsupportedVersion { version: 0xff }, err);
+
+    let err = Reactor::restore(Config::new(CHAIN_ID), &snapshot[..snapshot.len() - 1])
+        .expect_err("truncated snapshots shouldn't be restored");
+    assert_eq!(Error::InvalidEncoding, err);
+
+    let mut trailing = snapshot;
+    trailing.push(0x00);
+    let err = Reactor::restore(Config::new(CHAIN_ID), &trailing)
+        .expect_err("trailing bytes shouldn't be restored");
+    assert_eq!(Error::InvalidEncoding, err);
+}
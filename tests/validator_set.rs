@@ -0,0 +1,350 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use fuel_crypto::{PublicKey, SecretKey};
+use fuel_types::Bytes64;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn keys(n: usize) -> Vec<PublicKey> {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    (0..n)
+        .map(|_| SecretKey::random(rng).public_key())
+        .collect()
+}
+
+#[test]
+fn validator_set_hash_is_deterministic() {
+    let keys = keys(3);
+
+    let set = ValidatorSet::new([(keys[0], 1), (keys[1], 2), (keys[2], 3)]);
+    let reversed = ValidatorSet::new([(keys[2], 3), (keys[1], 2), (keys[0], 1)]);
+    let merged = ValidatorSet::new([(keys[2], 1), (keys[1], 2), (keys[0], 1), (keys[2], 2)]);
+
+    assert_eq!(set, reversed);
+    assert_eq!(set, merged);
+    assert_eq!(set.hash(), reversed.hash());
+    assert_eq!(6, set.total_power());
+    assert_eq!(2, set.voting_power(&keys[1]));
+
+    // Keys without voting power are not part of the set
+    let zero = ValidatorSet::new([(keys[0], 1), (keys[1], 2), (keys[2], 3), (keys[0], 0)]);
+    let other = ValidatorSet::new([(keys[0], 1), (keys[1], 2), (keys[2], 0)]);
+
+    assert_eq!(set.hash(), zero.hash());
+    assert_eq!(2, other.len());
+    assert!(!other.contains(&keys[2]));
+
+    // The hash commits to the voting power
+    let heavier = ValidatorSet::new([(keys[0], 1), (keys[1], 2), (keys[2], 4)]);
+
+    assert_ne!(set.hash(), heavier.hash());
+    assert_ne!(set.hash(), ValidatorSet::default().hash());
+
+    // The encoding is canonical
+    let bytes = set.to_bytes();
+    assert_eq!(Ok(set.clone()), ValidatorSet::from_bytes(&bytes));
+
+    let mut unsorted = vec![CODEC_VERSION];
+    set.as_slice()
+        .iter()
+        .rev()
+        .copied()
+        .collect::<Vec<_>>()
+        .encode(&mut unsorted);
+    assert_eq!(
+        Err(DecodeError::NonCanonical),
+        ValidatorSet::from_bytes(&unsorted)
+    );
+}
+
+#[test]
+fn validator_set_updates_take_effect_at_epoch_boundary() {
+    let keys = keys(4);
+
+    let config = Config::new(CHAIN_ID);
+    let mut reactor = Reactor::new(config);
+
+    keys[..3].iter().for_each(|k| {
+        reactor
+            .add_validator(*k, 0, 10)
+            .expect("failed to add validator");
+    });
+
+    let genesis = reactor.validator_set(0);
+
+    // An update committed at height 3 is applied from height 5
+    let height = 3;
+    let boundary = height + config.validator_set_delay;
+
+    reactor
+        .schedule_validator(keys[3], height, 10)
+        .expect("failed to schedule validator");
+
+    (0..boundary).for_each(|h| assert_eq!(genesis, reactor.validator_set(h)));
+
+    assert!(reactor.validator_set(boundary).contains(&keys[3]));
+    assert_ne!(genesis.hash(), reactor.validator_set(boundary).hash());
+
+    // A scheduled window of an existing validator replaces its current one at the boundary
+    reactor
+        .schedule_validator(keys[0], height, 1)
+        .expect("failed to schedule validator");
+
+    assert!(reactor.validator_set(boundary - 1).contains(&keys[0]));
+    assert!(reactor.validator_set(boundary + 1).contains(&keys[0]));
+    assert!(!reactor.validator_set(boundary + 2).contains(&keys[0]));
+
+    // Scheduled stakes are applied at the same boundary
+    let identity = Bytes64::from([0x01; Bytes64::LEN]);
+
+    reactor
+        .schedule_stake(
+            identity,
+            boundary + 2,
+            3,
+            Stake {
+                key: keys[1],
+                value: 5,
+            },
+        )
+        .expect("failed to schedule stake");
+
+    assert_eq!(
+        1,
+        reactor.validator_set(boundary + 3).voting_power(&keys[1])
+    );
+    assert_eq!(
+        6,
+        reactor.validator_set(boundary + 4).voting_power(&keys[1])
+    );
+}
+
+async fn notify(reactor: &mut Reactor, notification: Notification) {
+    reactor
+        .receive(
+            &MemoryKeychain::default(),
+            &mut NullModerator,
+            notification.into(),
+        )
+        .await;
+}
+
+async fn commit(reactor: &mut Reactor, secrets: &[&SecretKey]) {
+    let certificate = height_certificate(reactor, secrets, 0);
+
+    reactor
+        .sync_commit::<MemoryKeychain, _>(&mut NullModerator, certificate)
+        .await
+        .expect("failed to commit height");
+}
+
+#[tokio::test]
+async fn validator_removal_takes_effect_at_epoch_boundary() {
+    let keys = keys(4);
+
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+
+    keys.iter().for_each(|k| {
+        reactor
+            .add_validator(*k, 0, 10)
+            .expect("failed to add validator");
+    });
+
+    // A removal committed at height 3 is applied from height 5
+    notify(
+        &mut reactor,
+        Notification::RemoveValidator {
+            height: 3,
+            validator: keys[3],
+        },
+    )
+    .await;
+
+    let before = reactor.validator_set(4);
+    let after = reactor.validator_set(5);
+
+    assert!(before.contains(&keys[3]));
+    assert!(!after.contains(&keys[3]));
+    assert_eq!(4, before.total_power());
+    assert_eq!(3, after.total_power());
+    assert_eq!(after, reactor.validator_set(10));
+
+    // The removal is recovered from the write-ahead log
+    let recovered: Reactor =
+        Reactor::recover(Config::new(CHAIN_ID), reactor.wal().clone()).expect("failed to recover");
+
+    assert_eq!(before, recovered.validator_set(4));
+    assert_eq!(after, recovered.validator_set(5));
+}
+
+#[tokio::test]
+async fn key_rotation_takes_effect_at_epoch_boundary() {
+    let keys = keys(5);
+
+    let mut config = Config::new(CHAIN_ID);
+    config.validator_set_delay = 4;
+
+    let mut reactor = Reactor::new(config);
+
+    keys[..4].iter().for_each(|k| {
+        reactor
+            .add_validator(*k, 0, 10)
+            .expect("failed to add validator");
+    });
+
+    // A rotation committed at height 3 is applied from height 7, as the other set updates
+    notify(
+        &mut reactor,
+        Notification::RotateKey {
+            old: keys[3],
+            new: keys[4],
+            height: 3,
+        },
+    )
+    .await;
+
+    assert!(reactor.validator_set(6).contains(&keys[3]));
+    assert!(!reactor.validator_set(6).contains(&keys[4]));
+    assert!(!reactor.validator_set(7).contains(&keys[3]));
+    assert!(reactor.validator_set(7).contains(&keys[4]));
+}
+
+#[tokio::test]
+async fn key_rotation_mid_epoch() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let mut secrets: Vec<SecretKey> = (0..4).map(|_| SecretKey::random(rng)).collect();
+    secrets.sort_by_key(|s| s.public_key());
+
+    // The new key keeps the ordering of the rotated one, so the priority ties are resolved in the
+    // same way
+    let rotated = std::iter::repeat_with(|| SecretKey::random(rng))
+        .find(|s| s.public_key() < secrets[1].public_key())
+        .expect("infinite iterator");
+
+    let old = secrets[0].public_key();
+    let new = rotated.public_key();
+
+    let mut reference = Reactor::new(Config::new(CHAIN_ID));
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+
+    secrets.iter().enumerate().for_each(|(i, s)| {
+        let key = s.public_key();
+        let identity = identity(&key);
+        let stake = Stake {
+            key,
+            value: i as u64 + 1,
+        };
+
+        reference
+            .stake(identity, 0, 10, stake)
+            .expect("failed to add stake");
+        reactor
+            .stake(identity, 0, 10, stake)
+            .expect("failed to add stake");
+    });
+
+    // The key is rotated in the middle of the stake window, from the epoch boundary of the
+    // height that included the rotation
+    let height = 0;
+    let from_height = height + Config::DEFAULT_VALIDATOR_SET_DELAY;
+
+    notify(&mut reactor, Notification::RotateKey { old, new, height }).await;
+
+    assert!(reactor.validator_set(1).contains(&old));
+    assert!(!reactor.validator_set(1).contains(&new));
+    assert!(!reactor.validator_set(2).contains(&old));
+    assert_eq!(1, reactor.validator_set(2).voting_power(&new));
+    assert_eq!(1, reactor.validator_set(10).voting_power(&new));
+    assert_eq!(0, reactor.validator_set(11).voting_power(&new));
+    assert_eq!(
+        reactor.validator_set(1).total_power(),
+        reactor.validator_set(2).total_power()
+    );
+
+    // The old key votes for the heights before the rotation
+    let signers: Vec<&SecretKey> = secrets.iter().collect();
+
+    commit(&mut reference, &signers).await;
+    commit(&mut reactor, &signers).await;
+    commit(&mut reference, &signers).await;
+    commit(&mut reactor, &signers).await;
+
+    assert_eq!(from_height, reactor.height());
+
+    // The old key is no longer part of the quorum
+    let stale = height_certificate(&reactor, &signers, 0);
+    assert_eq!(
+        Err(Error::ValidatorNotFound),
+        stale.verify::<MemoryKeychain>(CHAIN_ID, reactor.stakes())
+    );
+
+    // The rotated validator preserves its proposer priority
+    let leaders = |reactor: &Reactor| {
+        (0..10)
+            .map(|r| *reactor.leader(r).expect("failed to elect leader"))
+            .map(|key| if key == old { new } else { key })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(leaders(&reference), leaders(&reactor));
+    assert!(leaders(&reactor).contains(&new));
+
+    let signers: Vec<&SecretKey> = [&rotated].into_iter().chain(&secrets[1..]).collect();
+
+    commit(&mut reference, &secrets.iter().collect::<Vec<_>>()).await;
+    commit(&mut reactor, &signers).await;
+
+    assert_eq!(leaders(&reference), leaders(&reactor));
+}
+
+#[tokio::test]
+async fn key_rotation_rejects_current_height() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secrets: Vec<SecretKey> = (0..5).map(|_| SecretKey::random(rng)).collect();
+    let signers: Vec<&SecretKey> = secrets[..4].iter().collect();
+
+    let old = secrets[0].public_key();
+    let new = secrets[4].public_key();
+
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+
+    signers.iter().for_each(|s| {
+        reactor
+            .add_validator(s.public_key(), 0, 10)
+            .expect("failed to add validator");
+    });
+
+    commit(&mut reactor, &signers).await;
+
+    let entries = reactor.wal().entries().expect("failed to read log").len();
+
+    // The votes of the current and past heights are signed with the old key
+    for from_height in [0, 1] {
+        assert_eq!(
+            Err(Error::HeightMismatch {
+                expected: 2,
+                found: from_height,
+            }),
+            reactor.rotate_key(old, new, from_height)
+        );
+    }
+
+    assert!(reactor.validator_set(1).contains(&old));
+    assert!(!reactor.validator_set(1).contains(&new));
+    assert_eq!(
+        entries,
+        reactor.wal().entries().expect("failed to read log").len()
+    );
+
+    reactor
+        .rotate_key(old, new, 2)
+        .expect("failed to rotate key");
+
+    assert!(reactor.validator_set(1).contains(&old));
+    assert!(reactor.validator_set(2).contains(&new));
+}
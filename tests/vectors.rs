@@ -1,3 +1,6 @@
+mod common;
+
+use common::*;
 use fuel_bft::*;
 
 use async_trait::async_trait;
@@ -23,9 +26,26 @@ where
     SecretKey::random(rng)
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DummyValidator {
+    pub rejected: Vec<Bytes32>,
+}
+
+#[async_trait]
+impl BlockValidator for DummyValidator {
+    async fn validate_block(&mut self, _height: Height, block_id: &Bytes32) -> Result<(), Error> {
+        if self.rejected.contains(block_id) {
+            return Err(Error::BlockValidation);
+        }
+
+        Ok(())
+    }
+}
+
 pub struct DummyModerator {
     pub time: OffsetDateTime,
     pub rng: StdRng,
+    pub validator: Option<DummyValidator>,
 
     inbound: Vec<Message>,
     outbound: Vec<Message>,
@@ -36,6 +56,7 @@ impl Default for DummyModerator {
         Self {
             time: OffsetDateTime::UNIX_EPOCH,
             rng: StdRng::seed_from_u64(8586),
+            validator: None,
             inbound: Vec::with_capacity(Config::DEFAULT_CAPACITY),
             outbound: Vec::with_capacity(Config::DEFAULT_CAPACITY),
         }
@@ -50,13 +71,7 @@ impl DummyModerator {
             reactor.receive(keychain, self, m).await;
         }
 
-        let outbound: Vec<Message> = self.outbound.drain(..).collect();
-
-        for m in outbound {
-            match m {
-                _ => (),
-            }
-        }
+        self.outbound.clear();
     }
 
     pub fn notify(
@@ -93,7 +108,7 @@ impl DummyModerator {
             .as_slice()
             .iter()
             .enumerate()
-            .find_map(|(i, x)| matches!(x, Message::Response(r) if r.id() == id).then(|| i))
+            .find_map(|(i, x)| matches!(x, Message::Response(r) if r.id() == id).then_some(i))
             .unwrap_or(usize::MAX);
 
         let response = self.outbound.swap_remove(index);
@@ -116,7 +131,7 @@ impl DummyModerator {
                     Message::Event(e) => f(e),
                     _ => false,
                 }
-                .then(|| i)
+                .then_some(i)
             })
             .map(|i| self.outbound.remove(i))
     }
@@ -130,6 +145,12 @@ impl Moderator for DummyModerator {
         self.time
     }
 
+    fn block_validator(&mut self) -> Option<&mut dyn BlockValidator> {
+        self.validator
+            .as_mut()
+            .map(|v| v as &mut dyn BlockValidator)
+    }
+
     async fn inbound(&mut self) -> Result<Option<Message>, Self::Error> {
         self.inbound_blocking()
     }
@@ -196,11 +217,7 @@ impl Token {
         let count = split.clone().count();
 
         for (i, x) in split.enumerate() {
-            match y
-                .as_hash()
-                .map(|h| h.get(&Yaml::String(x.into())))
-                .flatten()
-            {
+            match y.as_hash().and_then(|h| h.get(&Yaml::String(x.into()))) {
                 Some(v) => y = v,
                 None => return None,
             };
@@ -261,7 +278,7 @@ impl Token {
                         "defaultValidatorC" => {
                             Self::Secret(named_secret(Validator::DEFAULT_VALIDATOR_C))
                         }
-                        _ => Self::Validator(named_secret(name).public_key()),
+                        _ => Self::Secret(named_secret(name)),
                     });
                 }
 
@@ -336,6 +353,15 @@ pub enum Statement {
     AssertHeight {
         height: Height,
     },
+    AssertLocked {
+        block_id: Bytes32,
+        round: Round,
+    },
+    AssertNoStep {
+        validator: PublicKey,
+        height: Height,
+        round: Round,
+    },
     AssertNoValidators,
     AssertRound {
         round: Round,
@@ -362,18 +388,63 @@ pub enum Statement {
         height: Height,
     },
     Commit,
+    Elapse {
+        millis: u64,
+    },
+    ExpectBadProposal {
+        height: Height,
+        round: Round,
+        validator: PublicKey,
+    },
     ExpectBlockRequest {
         height: Height,
     },
+    ExpectCertificate {
+        block_id: Bytes32,
+        height: Height,
+        round: Round,
+    },
+    ExpectNoCertificate {
+        height: Height,
+    },
+    ExpectUncertifiedCommit {
+        block_id: Bytes32,
+        height: Height,
+        round: Round,
+    },
     ExpectCommit {
         block_id: Bytes32,
         height: Height,
         round: Round,
     },
-    ExpectVote {
+    ExpectEquivocation {
+        height: Height,
+        round: Round,
+        step: Step,
+        validator: PublicKey,
+    },
+    ExpectInvalidBlock {
+        block_id: Bytes32,
+        height: Height,
+        round: Round,
+        validator: PublicKey,
+    },
+    ExpectProposal {
         block_id: Bytes32,
         height: Height,
         round: Round,
+        pol_round: Option<Round>,
+        validator: PublicKey,
+    },
+    ExpectNoSyncRequired,
+    ExpectSyncRequired {
+        from: Height,
+        to: Height,
+    },
+    ExpectVote {
+        block_id: Option<Bytes32>,
+        height: Height,
+        round: Round,
         step: Step,
         validator: PublicKey,
     },
@@ -383,13 +454,38 @@ pub enum Statement {
     Initialize {
         password: String,
     },
+    Proposal {
+        block_id: Bytes32,
+        height: Height,
+        round: Round,
+        pol_round: Option<Round>,
+        secret: SecretKey,
+    },
+    RejectBlock {
+        block_id: Bytes32,
+    },
+    Restart,
+    Restore,
     SkipRounds {
         rounds: u64,
     },
-    Vote {
+    Stake {
+        validator: PublicKey,
+        height: Height,
+        validity: u64,
+        value: u64,
+    },
+    SyncCommit {
         block_id: Bytes32,
         height: Height,
         round: Round,
+        signers: Vec<SecretKey>,
+    },
+    ValidateBlocks,
+    Vote {
+        block_id: Option<Bytes32>,
+        height: Height,
+        round: Round,
         secret: SecretKey,
         step: Step,
     },
@@ -428,6 +524,19 @@ impl From<&Yaml> for Statement {
                     return Self::AssertHeight { height };
                 }
 
+                if let Some(t) = h.get(&Yaml::String("assertLocked".into())) {
+                    return Self::AssertLocked {
+                        block_id: Hasher::hash(
+                            Token::get(t, "blockSeed")
+                                .expect("assertLocked expects a blockSeed argument")
+                                .string(),
+                        ),
+                        round: Token::get(t, "round")
+                            .expect("assertLocked expects a round argument")
+                            .integer(),
+                    };
+                }
+
                 if let Some(Token::Integer(round)) = Token::get(y, "assertRound") {
                     return Self::AssertRound { round };
                 }
@@ -443,6 +552,20 @@ impl From<&Yaml> for Statement {
                     };
                 }
 
+                if let Some(t) = h.get(&Yaml::String("assertNoStep".into())) {
+                    return Self::AssertNoStep {
+                        validator: Token::get(t, "validator")
+                            .expect("assertNoStep expects a validator argument")
+                            .validator(),
+                        height: Token::get(t, "height")
+                            .expect("assertNoStep expects a height argument")
+                            .integer(),
+                        round: Token::get(t, "round")
+                            .expect("assertNoStep expects a round argument")
+                            .integer(),
+                    };
+                }
+
                 if let Some(t) = h.get(&Yaml::String("assertStep".into())) {
                     return Self::AssertStep {
                         validator: Token::get(t, "validator")
@@ -496,6 +619,24 @@ impl From<&Yaml> for Statement {
                     };
                 }
 
+                if let Some(Token::Integer(millis)) = Token::get(y, "elapse") {
+                    return Self::Elapse { millis };
+                }
+
+                if let Some(t) = h.get(&Yaml::String("expectBadProposal".into())) {
+                    return Self::ExpectBadProposal {
+                        height: Token::get(t, "height")
+                            .expect("expectBadProposal expects a height argument")
+                            .integer(),
+                        round: Token::get(t, "round")
+                            .expect("expectBadProposal expects a round argument")
+                            .integer(),
+                        validator: Token::get(t, "validator")
+                            .expect("expectBadProposal expects a validator argument")
+                            .validator(),
+                    };
+                }
+
                 if let Some(t) = h.get(&Yaml::String("expectBlockRequest".into())) {
                     return Self::ExpectBlockRequest {
                         height: Token::get(t, "height")
@@ -504,6 +645,46 @@ impl From<&Yaml> for Statement {
                     };
                 }
 
+                if let Some(t) = h.get(&Yaml::String("expectCertificate".into())) {
+                    return Self::ExpectCertificate {
+                        block_id: Hasher::hash(
+                            Token::get(t, "blockSeed")
+                                .expect("expectCertificate expects a blockSeed argument")
+                                .string(),
+                        ),
+                        height: Token::get(t, "height")
+                            .expect("expectCertificate expects a height argument")
+                            .integer(),
+                        round: Token::get(t, "round")
+                            .expect("expectCertificate expects a round argument")
+                            .integer(),
+                    };
+                }
+
+                if let Some(t) = h.get(&Yaml::String("expectNoCertificate".into())) {
+                    return Self::ExpectNoCertificate {
+                        height: Token::get(t, "height")
+                            .expect("expectNoCertificate expects a height argument")
+                            .integer(),
+                    };
+                }
+
+                if let Some(t) = h.get(&Yaml::String("expectUncertifiedCommit".into())) {
+                    return Self::ExpectUncertifiedCommit {
+                        block_id: Hasher::hash(
+                            Token::get(t, "blockSeed")
+                                .expect("expectUncertifiedCommit expects a blockSeed argument")
+                                .string(),
+                        ),
+                        height: Token::get(t, "height")
+                            .expect("expectUncertifiedCommit expects a height argument")
+                            .integer(),
+                        round: Token::get(t, "round")
+                            .expect("expectUncertifiedCommit expects a round argument")
+                            .integer(),
+                    };
+                }
+
                 if let Some(t) = h.get(&Yaml::String("expectCommit".into())) {
                     return Self::ExpectCommit {
                         block_id: Hasher::hash(
@@ -520,13 +701,78 @@ impl From<&Yaml> for Statement {
                     };
                 }
 
-                if let Some(t) = h.get(&Yaml::String("expectVote".into())) {
-                    return Self::ExpectVote {
+                if let Some(t) = h.get(&Yaml::String("expectEquivocation".into())) {
+                    return Self::ExpectEquivocation {
+                        height: Token::get(t, "height")
+                            .expect("expectEquivocation expects a height argument")
+                            .integer(),
+                        round: Token::get(t, "round")
+                            .expect("expectEquivocation expects a round argument")
+                            .integer(),
+                        step: Token::get(t, "step")
+                            .expect("expectEquivocation expects a step argument")
+                            .step(),
+                        validator: Token::get(t, "validator")
+                            .expect("expectEquivocation expects a validator argument")
+                            .validator(),
+                    };
+                }
+
+                if let Some(t) = h.get(&Yaml::String("expectInvalidBlock".into())) {
+                    return Self::ExpectInvalidBlock {
                         block_id: Hasher::hash(
                             Token::get(t, "blockSeed")
-                                .expect("expectVote expects a blockSeed argument")
+                                .expect("expectInvalidBlock expects a blockSeed argument")
                                 .string(),
                         ),
+                        height: Token::get(t, "height")
+                            .expect("expectInvalidBlock expects a height argument")
+                            .integer(),
+                        round: Token::get(t, "round")
+                            .expect("expectInvalidBlock expects a round argument")
+                            .integer(),
+                        validator: Token::get(t, "validator")
+                            .expect("expectInvalidBlock expects a validator argument")
+                            .validator(),
+                    };
+                }
+
+                // A proposal without a polRound is not a re-proposal of a locked block
+                if let Some(t) = h.get(&Yaml::String("expectProposal".into())) {
+                    return Self::ExpectProposal {
+                        block_id: Hasher::hash(
+                            Token::get(t, "blockSeed")
+                                .expect("expectProposal expects a blockSeed argument")
+                                .string(),
+                        ),
+                        height: Token::get(t, "height")
+                            .expect("expectProposal expects a height argument")
+                            .integer(),
+                        round: Token::get(t, "round")
+                            .expect("expectProposal expects a round argument")
+                            .integer(),
+                        pol_round: Token::get(t, "polRound").map(Token::integer),
+                        validator: Token::get(t, "validator")
+                            .expect("expectProposal expects a validator argument")
+                            .validator(),
+                    };
+                }
+
+                if let Some(t) = h.get(&Yaml::String("expectSyncRequired".into())) {
+                    return Self::ExpectSyncRequired {
+                        from: Token::get(t, "from")
+                            .expect("expectSyncRequired expects a from argument")
+                            .integer(),
+                        to: Token::get(t, "to")
+                            .expect("expectSyncRequired expects a to argument")
+                            .integer(),
+                    };
+                }
+
+                // A vote without a block seed is a nil vote
+                if let Some(t) = h.get(&Yaml::String("expectVote".into())) {
+                    return Self::ExpectVote {
+                        block_id: Token::get(t, "blockSeed").map(|s| Hasher::hash(s.string())),
                         height: Token::get(t, "height")
                             .expect("expectVote expects a height argument")
                             .integer(),
@@ -546,17 +792,90 @@ impl From<&Yaml> for Statement {
                     return Self::Initialize { password };
                 }
 
+                if let Some(t) = h.get(&Yaml::String("proposal".into())) {
+                    return Self::Proposal {
+                        block_id: Hasher::hash(
+                            Token::get(t, "blockSeed")
+                                .expect("proposal expects a blockSeed argument")
+                                .string(),
+                        ),
+                        height: Token::get(t, "height")
+                            .expect("proposal expects a height argument")
+                            .integer(),
+                        round: Token::get(t, "round")
+                            .expect("proposal expects a round argument")
+                            .integer(),
+                        pol_round: Token::get(t, "polRound").map(Token::integer),
+                        secret: Token::get(t, "secret")
+                            .expect("proposal expects a secret argument")
+                            .secret(),
+                    };
+                }
+
+                if let Some(Token::String(seed)) = Token::get(y, "rejectBlock.blockSeed") {
+                    return Self::RejectBlock {
+                        block_id: Hasher::hash(seed),
+                    };
+                }
+
                 if let Some(Token::Integer(rounds)) = Token::get(y, "skipRounds") {
                     return Self::SkipRounds { rounds };
                 }
 
-                if let Some(t) = h.get(&Yaml::String("vote".into())) {
-                    return Self::Vote {
+                if let Some(t) = h.get(&Yaml::String("stake".into())) {
+                    return Self::Stake {
+                        validator: Token::get(t, "validator")
+                            .expect("stake expects a validator argument")
+                            .validator(),
+                        height: Token::get(t, "height")
+                            .expect("stake expects a height argument")
+                            .integer(),
+                        validity: Token::get(t, "validity")
+                            .expect("stake expects a validity argument")
+                            .integer(),
+                        value: Token::get(t, "value")
+                            .expect("stake expects a value argument")
+                            .integer(),
+                    };
+                }
+
+                // The certificate is composed by precommits of the signers
+                if let Some(t) = h.get(&Yaml::String("syncCommit".into())) {
+                    let signers = t
+                        .as_hash()
+                        .and_then(|h| h.get(&Yaml::String("signers".into())))
+                        .and_then(|s| s.as_vec())
+                        .expect("syncCommit expects a signers list")
+                        .iter()
+                        .map(|secret| {
+                            let mut h = yaml_rust::yaml::Hash::new();
+                            h.insert(Yaml::String("secret".into()), secret.clone());
+
+                            Token::get(&Yaml::Hash(h), "secret")
+                                .expect("syncCommit expects secrets as signers")
+                                .secret()
+                        })
+                        .collect();
+
+                    return Self::SyncCommit {
                         block_id: Hasher::hash(
                             Token::get(t, "blockSeed")
-                                .expect("expectVote expects a blockSeed argument")
+                                .expect("syncCommit expects a blockSeed argument")
                                 .string(),
                         ),
+                        height: Token::get(t, "height")
+                            .expect("syncCommit expects a height argument")
+                            .integer(),
+                        round: Token::get(t, "round")
+                            .expect("syncCommit expects a round argument")
+                            .integer(),
+                        signers,
+                    };
+                }
+
+                if let Some(t) = h.get(&Yaml::String("vote".into())) {
+                    return Self::Vote {
+                        block_id: Token::get(t, "blockSeed").map(|s| Hasher::hash(s.string())),
                         height: Token::get(t, "height")
                             .expect("expectVote expects a height argument")
                             .integer(),
@@ -579,12 +898,20 @@ impl From<&Yaml> for Statement {
 
             Yaml::String(s) if s == "commit" => Self::Commit,
 
+            Yaml::String(s) if s == "expectNoSyncRequired" => Self::ExpectNoSyncRequired,
+
             Yaml::String(s) if s == "flush" => Self::Flush,
 
             Yaml::String(s) if s == "heartbeat" => Self::Heartbeat,
 
             Yaml::String(s) if s == "initializeDefault" => Self::InitializeDefault,
 
+            Yaml::String(s) if s == "restart" => Self::Restart,
+
+            Yaml::String(s) if s == "restore" => Self::Restore,
+
+            Yaml::String(s) if s == "validateBlocks" => Self::ValidateBlocks,
+
             _ => panic!("invalid statement {:?}", y),
         }
     }
@@ -604,7 +931,9 @@ impl Statement {
                 height,
                 validity,
             } => {
-                reactor.add_validator(validator, height, validity);
+                reactor
+                    .add_validator(validator, height, validity)
+                    .expect("failed to add validator");
             }
 
             Statement::AddDefaultValidators { height, validity } => [
@@ -631,10 +960,26 @@ impl Statement {
                 assert_eq!(height, reactor.height(), "unexpected height");
             }
 
+            Statement::AssertLocked { block_id, round } => {
+                assert_eq!(
+                    Some((round, &block_id)),
+                    reactor.locked(),
+                    "unexpected lock"
+                );
+            }
+
+            Statement::AssertNoStep {
+                validator,
+                height,
+                round,
+            } => {
+                assert_eq!(None, reactor.validator_step(height, round, &validator));
+            }
+
             Statement::AssertNoValidators => {
                 let round = reactor.round(moderator.time);
 
-                let err = reactor.leader(round).err().expect("no validators expected");
+                let err = reactor.leader(round).expect_err("no validators expected");
                 assert_eq!(Error::ValidatorNotFound, err, "unexpected validator");
             }
 
@@ -714,6 +1059,30 @@ impl Statement {
                 }
             }
 
+            Statement::Elapse { millis } => {
+                moderator.time = moderator
+                    .time
+                    .saturating_add(time::Duration::milliseconds(millis as i64))
+            }
+
+            Statement::ExpectBadProposal {
+                height,
+                round,
+                validator,
+            } => {
+                moderator
+                    .take_event(|e| match e {
+                        Event::BadProposal { proposal } => {
+                            proposal.height() == height
+                                && proposal.round() == round
+                                && proposal.proposer() == &validator
+                        }
+
+                        _ => false,
+                    })
+                    .expect("the `BadProposal` event wasn't emitted by the reactor");
+            }
+
             Statement::ExpectBlockRequest { height } => {
                 moderator
                     .take_event(|e| e == &Event::AwaitingBlock { height })
@@ -727,15 +1096,156 @@ impl Statement {
             } => {
                 moderator
                     .take_event(|e| {
-                        e == &Event::Commit {
-                            height,
-                            round,
-                            block_id,
-                        }
+                        matches!(e, Event::Commit {
+                            height: h,
+                            round: r,
+                            block_id: b,
+                            ..
+                        } if h == &height && r == &round && b == &block_id)
                     })
                     .expect("the `Commit` event wasn't emitted by the reactor");
             }
 
+            Statement::ExpectUncertifiedCommit {
+                block_id,
+                height,
+                round,
+            } => {
+                moderator
+                    .take_event(|e| {
+                        matches!(e, Event::Commit {
+                            height: h,
+                            round: r,
+                            block_id: b,
+                            certificate: None,
+                        } if h == &height && r == &round && b == &block_id)
+                    })
+                    .expect("the uncertified `Commit` event wasn't emitted by the reactor");
+            }
+
+            Statement::ExpectNoCertificate { height } => {
+                let id = moderator.rng.gen();
+
+                let response = moderator.request(
+                    runtime,
+                    keychain,
+                    reactor,
+                    Request::CommitCertificate { id, height },
+                );
+
+                assert_eq!(
+                    Response::CommitCertificate {
+                        id,
+                        certificate: None
+                    },
+                    response
+                );
+            }
+
+            Statement::ExpectNoSyncRequired => {
+                let sync = moderator.take_event(|e| matches!(e, Event::SyncRequired { .. }));
+
+                assert_eq!(None, sync, "unexpected `SyncRequired` event");
+            }
+
+            Statement::ExpectSyncRequired { from, to } => {
+                moderator
+                    .take_event(|e| {
+                        matches!(e, Event::SyncRequired { from: f, to: t } if f == &from && t == &to)
+                    })
+                    .expect("the `SyncRequired` event wasn't emitted by the reactor");
+            }
+
+            Statement::ExpectCertificate {
+                block_id,
+                height,
+                round,
+            } => {
+                let id = moderator.rng.gen();
+
+                let response = moderator.request(
+                    runtime,
+                    keychain,
+                    reactor,
+                    Request::CommitCertificate { id, height },
+                );
+
+                let certificate = match response {
+                    Response::CommitCertificate {
+                        certificate: Some(certificate),
+                        ..
+                    } => certificate,
+                    _ => panic!("unexpected certificate response: {:?}", response),
+                };
+
+                assert_eq!(height, certificate.height());
+                assert_eq!(round, certificate.round());
+                assert_eq!(&block_id, certificate.block_id());
+
+                certificate
+                    .verify::<MemoryKeychain>(CHAIN_ID, reactor.stakes())
+                    .expect("the commit certificate isn't valid");
+            }
+
+            Statement::ExpectEquivocation {
+                height,
+                round,
+                step,
+                validator,
+            } => {
+                let evidence = moderator
+                    .take_event(|e| match e {
+                        Event::Equivocation { first, .. } => {
+                            first.height() == height
+                                && first.round() == round
+                                && first.step() == step
+                                && first.validator() == &validator
+                        }
+
+                        _ => false,
+                    })
+                    .expect("the `Equivocation` event wasn't emitted by the reactor");
+
+                let (first, second) = match evidence {
+                    Message::Event(Event::Equivocation { first, second }) => (first, second),
+                    _ => unreachable!(),
+                };
+
+                assert_eq!(first.height(), second.height());
+                assert_eq!(first.round(), second.round());
+                assert_eq!(first.step(), second.step());
+                assert_eq!(first.validator(), second.validator());
+                assert_ne!(first.block_id(), second.block_id());
+
+                first
+                    .validate::<MemoryKeychain>(CHAIN_ID)
+                    .expect("the first vote isn't valid");
+                second
+                    .validate::<MemoryKeychain>(CHAIN_ID)
+                    .expect("the second vote isn't valid");
+            }
+
+            Statement::ExpectInvalidBlock {
+                block_id,
+                height,
+                round,
+                validator,
+            } => {
+                moderator
+                    .take_event(|e| match e {
+                        Event::InvalidBlock { proposal, error } => {
+                            proposal.block_id() == &block_id
+                                && proposal.height() == height
+                                && proposal.round() == round
+                                && proposal.proposer() == &validator
+                                && error == &Error::BlockValidation
+                        }
+
+                        _ => false,
+                    })
+                    .expect("the `InvalidBlock` event wasn't emitted by the reactor");
+            }
+
             Statement::ExpectVote {
                 block_id,
                 height,
@@ -746,7 +1256,7 @@ impl Statement {
                 let vote = moderator
                     .take_event(|e| match e {
                         Event::Broadcast { vote } => {
-                            vote.block_id() == &block_id
+                            vote.block_id() == block_id.as_ref()
                                 && vote.height() == height
                                 && vote.round() == round
                                 && vote.step() == step
@@ -762,14 +1272,85 @@ impl Statement {
                     _ => unreachable!(),
                 };
 
-                vote.validate::<MemoryKeychain>()
+                vote.validate::<MemoryKeychain>(CHAIN_ID)
                     .expect("the received vote isn't valid");
             }
 
+            Statement::ExpectProposal {
+                block_id,
+                height,
+                round,
+                pol_round,
+                validator,
+            } => {
+                let proposal = moderator
+                    .take_event(|e| match e {
+                        Event::Proposal { proposal } => {
+                            proposal.block_id() == &block_id
+                                && proposal.height() == height
+                                && proposal.round() == round
+                                && proposal.pol_round() == pol_round
+                                && proposal.proposer() == &validator
+                        }
+
+                        _ => false,
+                    })
+                    .expect("the `Proposal` event wasn't emitted by the reactor");
+
+                let proposal = match proposal {
+                    Message::Event(Event::Proposal { proposal }) => proposal,
+                    _ => unreachable!(),
+                };
+
+                proposal
+                    .validate::<MemoryKeychain>(CHAIN_ID)
+                    .expect("the received proposal isn't valid");
+            }
+
             Statement::Flush => runtime.block_on(async {
                 moderator.flush(keychain, reactor).await;
             }),
 
+            Statement::Proposal {
+                block_id,
+                height,
+                round,
+                pol_round,
+                secret,
+            } => moderator.notify(
+                runtime,
+                keychain,
+                reactor,
+                Notification::Proposal {
+                    proposal: Proposal::signed_with_key::<MemoryKeychain>(
+                        &secret, CHAIN_ID, height, round, block_id, pol_round, 0,
+                    ),
+                },
+            ),
+
+            Statement::RejectBlock { block_id } => moderator
+                .validator
+                .get_or_insert_with(Default::default)
+                .rejected
+                .push(block_id),
+
+            Statement::Restart => {
+                let wal = reactor.wal().clone();
+
+                *reactor = Reactor::recover(Config::new(CHAIN_ID), wal)
+                    .expect("failed to recover the reactor from its write-ahead log");
+            }
+
+            Statement::Restore => {
+                let snapshot = reactor.snapshot();
+                let restored = Reactor::restore(Config::new(CHAIN_ID), &snapshot)
+                    .expect("failed to restore the reactor from its snapshot");
+
+                assert_eq!(snapshot, restored.snapshot());
+
+                *reactor = restored;
+            }
+
             Statement::Heartbeat => runtime.block_on(async {
                 reactor
                     .heartbeat(keychain, moderator)
@@ -814,6 +1395,58 @@ impl Statement {
                 ))
             }
 
+            Statement::Stake {
+                validator,
+                height,
+                validity,
+                value,
+            } => {
+                reactor
+                    .stake(
+                        identity(&validator),
+                        height,
+                        validity,
+                        Stake {
+                            key: validator,
+                            value,
+                        },
+                    )
+                    .expect("failed to add stake");
+            }
+
+            Statement::SyncCommit {
+                block_id,
+                height,
+                round,
+                signers,
+            } => {
+                let votes = signers.iter().map(|secret| {
+                    Vote::signed_with_key::<MemoryKeychain>(
+                        secret,
+                        CHAIN_ID,
+                        height,
+                        round,
+                        Some(block_id),
+                        Step::Precommit,
+                    )
+                });
+
+                let validator_set = reactor.validator_set(height).hash();
+                let certificate =
+                    CommitCertificate::new(height, round, block_id, validator_set, votes);
+
+                moderator.notify(
+                    runtime,
+                    keychain,
+                    reactor,
+                    Notification::SyncCommit { certificate },
+                )
+            }
+
+            Statement::ValidateBlocks => {
+                moderator.validator.get_or_insert_with(Default::default);
+            }
+
             Statement::Vote {
                 block_id,
                 height,
@@ -826,7 +1459,7 @@ impl Statement {
                 reactor,
                 Notification::Vote {
                     vote: Vote::signed_with_key::<MemoryKeychain>(
-                        &secret, height, round, block_id, step,
+                        &secret, CHAIN_ID, height, round, block_id, step,
                     ),
                 },
             ),
@@ -870,7 +1503,7 @@ fn vectors() {
         let runtime = &Runtime::new().expect("failed to create async runtime");
         let moderator = &mut DummyModerator::default();
         let keychain = &mut MemoryKeychain::default();
-        let reactor = &mut Reactor::default();
+        let reactor = &mut Reactor::new(Config::new(CHAIN_ID));
 
         program
             .iter()
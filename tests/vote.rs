@@ -0,0 +1,108 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use fuel_crypto::{Hasher, SecretKey};
+use fuel_types::Bytes32;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn nil_digest() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secret = SecretKey::random(rng);
+
+    let nil = Vote::signed_with_key::<MemoryKeychain>(&secret, CHAIN_ID, 1, 2, None, Step::Prevote);
+    let zeroed = Vote::signed_with_key::<MemoryKeychain>(
+        &secret,
+        CHAIN_ID,
+        1,
+        2,
+        Some(Bytes32::zeroed()),
+        Step::Prevote,
+    );
+
+    assert!(nil.is_nil());
+    assert!(!zeroed.is_nil());
+    assert_eq!(None, nil.block_id());
+    assert_eq!(Some(&Bytes32::zeroed()), zeroed.block_id());
+
+    let nil_digest = nil.digest(CHAIN_ID, Hasher::default()).digest();
+    let zeroed_digest = zeroed.digest(CHAIN_ID, Hasher::default()).digest();
+
+    assert_ne!(nil_digest, zeroed_digest);
+
+    nil.validate::<MemoryKeychain>(CHAIN_ID)
+        .expect("failed to validate nil vote");
+    zeroed
+        .validate::<MemoryKeychain>(CHAIN_ID)
+        .expect("failed to validate zeroed vote");
+
+    // A nil signature cannot be used for a block vote
+    let forged = Vote::new(
+        *zeroed.validator(),
+        *nil.signature(),
+        1,
+        2,
+        Some(Bytes32::zeroed()),
+        Step::Prevote,
+    );
+
+    forged
+        .validate::<MemoryKeychain>(CHAIN_ID)
+        .expect_err("nil signature should not validate a block vote");
+}
+
+#[test]
+fn chain_separation() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secrets: Vec<SecretKey> = (0..4).map(|_| SecretKey::random(rng)).collect();
+
+    let mainnet = CHAIN_ID;
+    let testnet = mainnet + 1;
+    let block_id = Bytes32::zeroed();
+
+    let vote = Vote::signed_with_key::<MemoryKeychain>(
+        &secrets[0],
+        testnet,
+        1,
+        2,
+        Some(block_id),
+        Step::Precommit,
+    );
+
+    assert_ne!(
+        vote.digest(mainnet, Hasher::default()).digest(),
+        vote.digest(testnet, Hasher::default()).digest()
+    );
+
+    vote.validate::<MemoryKeychain>(testnet)
+        .expect("failed to validate vote for its chain");
+    assert_eq!(
+        Err(Error::InvalidSignature),
+        vote.validate::<MemoryKeychain>(mainnet)
+    );
+
+    // A certificate of a different chain doesn't prove a commit
+    let stakes = ValidatorStakes::try_from_iter(secrets.iter().map(|s| {
+        let key = s.public_key();
+
+        (identity(&key), 0..10, Stake { key, value: 1 })
+    }))
+    .expect("failed to create stakes");
+
+    let votes = secrets[..3].iter().map(|s| {
+        Vote::signed_with_key::<MemoryKeychain>(s, testnet, 1, 2, Some(block_id), Step::Precommit)
+    });
+    let validator_set = ValidatorSet::from_stakes(&stakes, 1).hash();
+    let certificate = CommitCertificate::new(1, 2, block_id, validator_set, votes);
+
+    certificate
+        .verify::<MemoryKeychain>(testnet, &stakes)
+        .expect("failed to verify certificate for its chain");
+    assert_eq!(
+        Err(Error::InvalidSignature),
+        certificate.verify::<MemoryKeychain>(mainnet, &stakes)
+    );
+}
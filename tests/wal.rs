@@ -0,0 +1,579 @@
+mod common;
+
+use common::*;
+use fuel_bft::*;
+
+use fuel_crypto::{Hasher, SecretKey};
+use fuel_types::Bytes64;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Log that replays its entries but refuses new ones.
+#[derive(Debug, Clone)]
+struct ReadOnlyWal {
+    entries: Vec<WalEntry>,
+}
+
+impl Wal for ReadOnlyWal {
+    type Error = &'static str;
+
+    fn append(&mut self, _entry: &WalEntry) -> Result<(), Self::Error> {
+        Err("read-only log")
+    }
+
+    fn entries(&self) -> Result<Vec<WalEntry>, Self::Error> {
+        Ok(self.entries.clone())
+    }
+
+    fn prune(&mut self, _committed: Height) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+fn wal_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("fuel-bft-{}-{}.wal", name, std::process::id()));
+
+    fs::remove_file(&path).ok();
+
+    path
+}
+
+fn entries(rng: &mut StdRng) -> Vec<WalEntry> {
+    let secret = SecretKey::random(rng);
+    let validator = secret.public_key();
+    let block_id = Hasher::hash("some block");
+
+    let vote = |height, step| {
+        Vote::signed_with_key::<MemoryKeychain>(&secret, CHAIN_ID, height, 0, Some(block_id), step)
+    };
+
+    vec![
+        WalEntry::Validator {
+            height: 0,
+            validity: 10,
+            validator,
+        },
+        WalEntry::Stake {
+            height: 0,
+            validity: 1,
+            validator: Bytes64::from([0xfa; Bytes64::LEN]),
+            stake: Stake {
+                key: validator,
+                value: 15,
+            },
+        },
+        WalEntry::RotateKey {
+            old: validator,
+            new: SecretKey::random(rng).public_key(),
+            from_height: 5,
+        },
+        WalEntry::RemoveValidator {
+            height: 8,
+            validator,
+        },
+        WalEntry::BlockAuthorized {
+            height: 1,
+            block_id,
+        },
+        WalEntry::BlockProposeAuthorized {
+            height: 1,
+            block_id,
+        },
+        WalEntry::Proposal {
+            proposal: Proposal::signed_with_key::<MemoryKeychain>(
+                &secret, CHAIN_ID, 1, 0, block_id, None, 1_000,
+            ),
+        },
+        WalEntry::Vote {
+            vote: vote(1, Step::Prevote),
+        },
+        WalEntry::Vote {
+            vote: Vote::signed_with_key::<MemoryKeychain>(
+                &secret,
+                CHAIN_ID,
+                1,
+                0,
+                None,
+                Step::Precommit,
+            ),
+        },
+        WalEntry::ProofOfLock {
+            height: 1,
+            round: 0,
+            block_id,
+        },
+        WalEntry::Round {
+            height: 1,
+            round: 1,
+        },
+        WalEntry::Commit {
+            rounds: 2,
+            certificate: CommitCertificate::new(
+                1,
+                1,
+                block_id,
+                Hasher::hash("validator set"),
+                [vote(1, Step::Precommit)],
+            ),
+        },
+        WalEntry::Certificate {
+            certificate: CommitCertificate::new(
+                1,
+                1,
+                block_id,
+                Hasher::hash("validator set"),
+                [vote(1, Step::Commit)],
+            ),
+        },
+        WalEntry::Vote {
+            vote: vote(2, Step::Prevote),
+        },
+    ]
+}
+
+#[test]
+fn file_wal_recovers_entries() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let path = wal_path("recover");
+    let entries = entries(rng);
+
+    let mut wal = FileWal::open(&path).expect("failed to open wal");
+
+    entries
+        .iter()
+        .for_each(|e| wal.append(e).expect("failed to append entry"));
+
+    drop(wal);
+
+    let wal = FileWal::open(&path).expect("failed to reopen wal");
+    let recovered = wal.entries().expect("failed to read entries");
+
+    assert_eq!(entries, recovered);
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn file_wal_discards_torn_entry() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let path = wal_path("torn");
+    let entries = entries(rng);
+
+    let mut wal = FileWal::open(&path).expect("failed to open wal");
+
+    entries
+        .iter()
+        .for_each(|e| wal.append(e).expect("failed to append entry"));
+
+    // Simulate a crash in the middle of a write
+    let len = fs::metadata(&path).expect("failed to read metadata").len();
+    let file = OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .expect("failed to open file");
+    file.set_len(len - 3).expect("failed to truncate file");
+
+    let recovered = wal.entries().expect("failed to read entries");
+    assert_eq!(&entries[..entries.len() - 1], recovered.as_slice());
+
+    // Corrupted entries that are not the tail of the log are not expected
+    let mut bytes = fs::read(&path).expect("failed to read file");
+    bytes[8] ^= 0xff;
+    fs::File::create(&path)
+        .and_then(|mut f| f.write_all(&bytes))
+        .expect("failed to write file");
+
+    wal.entries()
+        .expect_err("a corrupted log shouldn't be recovered");
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn file_wal_appends_after_torn_entry() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let path = wal_path("torn-append");
+    let entries = entries(rng);
+    let (last, entries) = entries.split_last().expect("no entries");
+
+    let mut wal = FileWal::open(&path).expect("failed to open wal");
+
+    entries
+        .iter()
+        .chain([last])
+        .for_each(|e| wal.append(e).expect("failed to append entry"));
+
+    drop(wal);
+
+    // Simulate a crash in the middle of the write of the last entry
+    let len = fs::metadata(&path).expect("failed to read metadata").len();
+    let file = OpenOptions::new()
+        .write(true)
+        .open(&path)
+        .expect("failed to open file");
+    file.set_len(len - 3).expect("failed to truncate file");
+
+    let mut wal = FileWal::open(&path).expect("failed to open wal");
+    assert_eq!(
+        entries,
+        wal.entries().expect("failed to read entries").as_slice()
+    );
+
+    wal.append(last).expect("failed to append entry");
+
+    let recovered = FileWal::open(&path)
+        .and_then(|wal| wal.entries())
+        .expect("failed to read entries");
+
+    assert_eq!(entries, &recovered[..recovered.len() - 1]);
+    assert_eq!(Some(last), recovered.last());
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn file_wal_rejects_corrupted_length_prefix() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let path = wal_path("corrupted-len");
+    let entries = entries(rng);
+
+    let mut wal = FileWal::open(&path).expect("failed to open wal");
+
+    entries
+        .iter()
+        .for_each(|e| wal.append(e).expect("failed to append entry"));
+
+    drop(wal);
+
+    // The length prefix of the second entry points past the end of the file
+    let mut bytes = fs::read(&path).expect("failed to read file");
+    let first = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize + 8;
+    bytes[first..first + 4].copy_from_slice(&u32::MAX.to_be_bytes());
+    fs::write(&path, &bytes).expect("failed to write file");
+
+    let err = FileWal::open(&path).expect_err("a corrupted log shouldn't be recovered");
+    assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+
+    // The entries that follow the corrupted frame are preserved
+    assert_eq!(bytes, fs::read(&path).expect("failed to read file"));
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn file_wal_prunes_committed_heights() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let path = wal_path("prune");
+    let entries = entries(rng);
+
+    let mut wal = FileWal::open(&path)
+        .expect("failed to open wal")
+        .with_compaction_threshold(0);
+    let mut memory = MemoryWal::default();
+
+    for e in entries.iter() {
+        wal.append(e).expect("failed to append entry");
+        memory.append(e).expect("failed to append entry");
+    }
+
+    wal.prune(1).expect("failed to prune wal");
+    memory.prune(1).expect("failed to prune wal");
+
+    let pruned = wal.entries().expect("failed to read entries");
+    let expected: Vec<WalEntry> = entries.into_iter().filter(|e| !e.is_expired(1)).collect();
+
+    assert_eq!(expected, pruned);
+    assert_eq!(expected, memory.entries().expect("failed to read entries"));
+
+    // Only the validator updates, the commit, its certificate and the vote of the next height are
+    // retained
+    assert_eq!(6, pruned.len());
+
+    // The log remains appendable after the compaction
+    wal.append(&WalEntry::Round {
+        height: 2,
+        round: 1,
+    })
+    .expect("failed to append entry");
+    assert_eq!(7, wal.entries().expect("failed to read entries").len());
+
+    fs::remove_file(&path).ok();
+}
+
+#[test]
+fn file_wal_compacts_after_snapshot_or_threshold() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let path = wal_path("compaction");
+    let entries = entries(rng);
+
+    let mut wal = FileWal::open(&path).expect("failed to open wal");
+
+    for e in entries.iter() {
+        wal.append(e).expect("failed to append entry");
+    }
+
+    // The log didn't grow past the threshold, so the expired entries are retained
+    wal.prune(1).expect("failed to prune wal");
+    assert_eq!(entries, wal.entries().expect("failed to read entries"));
+
+    // A snapshot supersedes the previous entries, so the log is compacted
+    let snapshot = WalEntry::Snapshot {
+        snapshot: Reactor::new(Config::new(CHAIN_ID)).snapshot(),
+    };
+
+    wal.append(&snapshot).expect("failed to append entry");
+    wal.prune(1).expect("failed to prune wal");
+    assert_eq!(
+        vec![snapshot.clone()],
+        wal.entries().expect("failed to read entries")
+    );
+
+    // The log is compacted once it grows past the threshold
+    let mut wal = FileWal::open(&path)
+        .expect("failed to open wal")
+        .with_compaction_threshold(1);
+
+    for e in entries.iter() {
+        wal.append(e).expect("failed to append entry");
+    }
+
+    wal.prune(1).expect("failed to prune wal");
+
+    let expected: Vec<WalEntry> = [snapshot]
+        .into_iter()
+        .chain(entries)
+        .filter(|e| !e.is_expired(1))
+        .collect();
+
+    assert_eq!(expected, wal.entries().expect("failed to read entries"));
+
+    fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn reactor_prunes_validator_set_updates_captured_by_snapshot() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let secrets: Vec<_> = (0..5).map(|_| SecretKey::random(rng)).collect();
+    let removed = secrets[4].public_key();
+
+    let mut reactor = Reactor::new(Config::new(CHAIN_ID));
+
+    secrets.iter().for_each(|s| {
+        reactor
+            .add_validator(s.public_key(), 0, 100)
+            .expect("failed to add validator");
+    });
+
+    let removal = reactor.height() + 2;
+    reactor
+        .remove_validator(removed, removal)
+        .expect("failed to remove validator");
+
+    let is_logged = |reactor: &Reactor, f: fn(&WalEntry) -> bool| {
+        reactor
+            .wal()
+            .entries()
+            .expect("failed to read entries")
+            .iter()
+            .any(f)
+    };
+
+    while reactor.height() <= removal {
+        // The removal is retained until it takes effect
+        assert!(is_logged(&reactor, |e| matches!(
+            e,
+            WalEntry::RemoveValidator { .. }
+        )));
+
+        let height = reactor.height();
+        let block_id = Hasher::hash(height.to_be_bytes());
+        let votes = secrets[..4].iter().map(|s| {
+            Vote::signed_with_key::<MemoryKeychain>(
+                s,
+                CHAIN_ID,
+                height,
+                0,
+                Some(block_id),
+                Step::Precommit,
+            )
+        });
+
+        let validator_set = reactor.validator_set(height).hash();
+        let certificate = CommitCertificate::new(height, 0, block_id, validator_set, votes);
+
+        reactor
+            .sync_commit::<MemoryKeychain, _>(&mut NullModerator, certificate)
+            .await
+            .expect("failed to commit height");
+    }
+
+    // The stakes are captured by the snapshot, so the updates are discarded
+    assert!(is_logged(&reactor, |e| matches!(
+        e,
+        WalEntry::Snapshot { .. }
+    )));
+    assert!(!is_logged(&reactor, |e| matches!(
+        e,
+        WalEntry::RemoveValidator { .. } | WalEntry::Validator { .. }
+    )));
+
+    let recovered: Reactor =
+        Reactor::recover(Config::new(CHAIN_ID), reactor.wal().clone()).expect("failed to recover");
+
+    assert_eq!(reactor.snapshot(), recovered.snapshot());
+    assert!(!recovered.validator_set(reactor.height()).contains(&removed));
+}
+
+#[tokio::test]
+async fn reactor_state_is_not_changed_if_vote_is_not_logged() {
+    let passwords = ["validator_0", "validator_1", "validator_2", "validator_3"];
+    let block_id = Hasher::hash("some block");
+
+    let entries = passwords
+        .iter()
+        .map(|p| WalEntry::Validator {
+            height: 0,
+            validity: 10,
+            validator: MemoryKeychain::secret(p).public_key(),
+        })
+        .collect();
+
+    let mut reactor: Reactor<ReadOnlyWal> =
+        Reactor::recover(Config::new(CHAIN_ID), ReadOnlyWal { entries })
+            .expect("failed to recover");
+
+    let mut keychain = MemoryKeychain::default();
+    keychain.insert(.., passwords[0]);
+
+    let public = MemoryKeychain::secret(passwords[0]).public_key();
+    let mut moderator = QueueModerator::default();
+
+    // The precommits achieve consensus, but the commit vote of the node can't be logged
+    for p in &passwords[1..] {
+        let vote = Vote::signed_with_key::<MemoryKeychain>(
+            &MemoryKeychain::secret(p),
+            CHAIN_ID,
+            0,
+            0,
+            Some(block_id),
+            Step::Precommit,
+        );
+
+        reactor
+            .receive(
+                &keychain,
+                &mut moderator,
+                Notification::Vote { vote }.into(),
+            )
+            .await;
+    }
+
+    assert_eq!(0, reactor.height());
+    assert_eq!(None, reactor.validator_step(0, 0, &public));
+    assert!(!moderator
+        .outbound
+        .iter()
+        .any(|m| matches!(m, Message::Event(Event::Broadcast { .. }))));
+}
+
+#[tokio::test]
+async fn reactor_block_is_not_authorized_if_not_logged() {
+    let mut reactor: Reactor<ReadOnlyWal> = Reactor::with_wal(
+        Config::new(CHAIN_ID),
+        ReadOnlyWal {
+            entries: Vec::new(),
+        },
+    );
+
+    let mut keychain = MemoryKeychain::default();
+    keychain.insert(.., "validator");
+
+    let snapshot = reactor.snapshot();
+    let block_id = Hasher::hash("some block");
+
+    let notifications = [
+        Notification::BlockAuthorized {
+            height: 1,
+            block_id,
+        },
+        Notification::BlockProposeAuthorized {
+            height: 1,
+            block_id,
+        },
+    ];
+
+    for notification in notifications {
+        reactor
+            .receive(&keychain, &mut NullModerator, notification.into())
+            .await;
+    }
+
+    assert_eq!(snapshot, reactor.snapshot());
+}
+
+#[test]
+fn reactor_stake_is_applied_only_if_logged() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let key = SecretKey::random(rng).public_key();
+    let identity = Bytes64::from(<[u8; 64]>::from(key));
+
+    let stake = Stake { key, value: 10 };
+    let conflicting = Stake { key, value: 20 };
+
+    // A stake that can't be logged isn't applied
+    let mut reactor: Reactor<ReadOnlyWal> = Reactor::with_wal(
+        Config::new(CHAIN_ID),
+        ReadOnlyWal {
+            entries: Vec::new(),
+        },
+    );
+
+    assert_eq!(
+        Err(Error::WalFailure),
+        reactor.stake(identity, 0, 10, stake)
+    );
+    assert_eq!(0, reactor.validator_set(0).len());
+
+    // A stake refused by the metadata isn't logged
+    let mut reactor: Reactor = Reactor::new(Config::new(CHAIN_ID));
+
+    reactor
+        .stake(identity, 0, 10, stake)
+        .expect("failed to add stake");
+
+    assert!(matches!(
+        reactor.stake(identity, 5, 10, conflicting),
+        Err(Error::DuplicatedStake { .. })
+    ));
+    assert_eq!(1, reactor.wal().entries().expect("failed to read").len());
+}
+
+#[test]
+fn reactor_recovery_preserves_log() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let entries = entries(rng);
+
+    let mut wal = MemoryWal::default();
+
+    entries
+        .iter()
+        .for_each(|e| wal.append(e).expect("failed to append entry"));
+
+    // Every recovery replays the same entries without logging them again
+    let reactor: Reactor = Reactor::recover(Config::new(CHAIN_ID), wal).expect("failed to recover");
+    assert_eq!(
+        entries,
+        reactor.wal().entries().expect("failed to read entries")
+    );
+
+    let reactor: Reactor =
+        Reactor::recover(Config::new(CHAIN_ID), reactor.wal().clone()).expect("failed to recover");
+    assert_eq!(
+        entries,
+        reactor.wal().entries().expect("failed to read entries")
+    );
+}